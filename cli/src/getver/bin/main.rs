@@ -1,29 +1,143 @@
-use core::calculate_version;
+use core::{calculate_version_with_decision, SemanticVersion, VersionReq};
+use std::cmp::Ordering;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use logging::{init_tracing, LogFormat};
+
+#[path = "../../logging.rs"]
+mod logging;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Current Version
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Current Version, the initial version is printed when there is none yet.
     /// #Example:
     /// v2.3.5
-    #[clap(short, long, value_parser)]
-    current_version: String,
+    #[clap(short, long, value_parser, env = "SEMVER_CURRENT_VERSION")]
+    current_version: Option<String>,
+    /// Version of the first release, printed when no current version is given.
+    #[clap(
+        long,
+        value_parser,
+        default_value = "v0.1.0",
+        env = "SEMVER_INITIAL_VERSION"
+    )]
+    initial_version: String,
     /// Semantic Version Comment
-    /// 
+    ///
     /// # Example:
     /// feat: this is a feature.
-    #[clap(short, long, value_parser)]
-    comment: String,
+    #[clap(
+        short = 'm',
+        long,
+        value_parser,
+        required = true,
+        env = "SEMVER_COMMENT"
+    )]
+    comment: Option<String>,
+    /// Logs what the command does on stderr, `-vv` for every decision.
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Format of the logs, `json` emitting one object per event.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = LogFormat::Text,
+        global = true,
+        env = "SEMVER_LOG_FORMAT"
+    )]
+    log_format: LogFormat,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compares two versions and prints `lt`, `eq` or `gt`.
+    Cmp {
+        left: String,
+        right: String,
+        /// Describes which component decided the comparison.
+        #[arg(long, default_value_t = false, env = "SEMVER_EXPLAIN")]
+        explain: bool,
+    },
+    /// Checks a version against a requirement, e.g. `^1.2`, and prints `true` or `false`,
+    /// exiting with 1 when it is not satisfied.
+    Satisfies {
+        requirement: String,
+        version: String,
+        /// Describes which comparator of each alternative the version fails.
+        #[arg(long, default_value_t = false, env = "SEMVER_EXPLAIN")]
+        explain: bool,
+    },
+}
+
+fn ordering_as_str(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "lt",
+        Ordering::Equal => "eq",
+        Ordering::Greater => "gt",
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
+    init_tracing(args.verbose, args.log_format);
+
+    match args.command {
+        Some(Command::Cmp {
+            left,
+            right,
+            explain,
+        }) => {
+            let left = SemanticVersion::try_from(left.as_str())?;
+            let right = SemanticVersion::try_from(right.as_str())?;
+            let comparison = left.explain_cmp(&right);
+
+            println!("{}", ordering_as_str(comparison.ordering));
+            if explain {
+                println!("{}", comparison);
+            }
+        }
+        Some(Command::Satisfies {
+            requirement,
+            version,
+            explain,
+        }) => {
+            let requirement = VersionReq::parse(&requirement)?;
+            let version = SemanticVersion::try_from(version.as_str())?;
+            let explanation = requirement.explain(&version);
+
+            println!("{}", explanation.satisfied);
+            if explain {
+                println!("{}", explanation);
+            }
+            if !explanation.satisfied {
+                std::process::exit(1);
+            }
+        }
+        None => {
+            let comment = args.comment.unwrap_or_default();
+            let semantic_comment = comment.as_str().try_into()?;
 
-    let new_version = calculate_version(args.current_version.as_str(), args.comment.as_str().try_into()?)?;
+            let new_version = match args.current_version.filter(|v| !v.is_empty()) {
+                Some(current_version) => {
+                    let (version, decision) = calculate_version_with_decision(
+                        current_version.as_str(),
+                        semantic_comment,
+                    )?;
+                    if args.verbose > 0 {
+                        eprintln!("{}", decision);
+                    }
+                    version
+                }
+                None => String::from(SemanticVersion::try_from(args.initial_version.as_str())?),
+            };
 
-    println!("{}", new_version);
+            println!("{}", new_version);
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,42 @@
+//! Logging of the `semver` and `getver` binaries.
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use clap::ValueEnum;
+use tracing_subscriber::EnvFilter;
+
+/// Format of the events logged on stderr, see [`init_tracing`].
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One line per event, for humans.
+    #[default]
+    Text,
+    /// One JSON object per event, for CI systems to index.
+    Json,
+}
+
+/// Logs the stages of the command on stderr: warnings by default, debug events with `-v` and
+/// trace events with `-vv`. `RUST_LOG`, e.g. `RUST_LOG=core=trace`, overrides the verbosity.
+pub fn init_tracing(verbose: u8, format: LogFormat) {
+    let level = match verbose {
+        0 => "warn",
+        1 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr);
+
+    match format {
+        LogFormat::Text => subscriber
+            .with_ansi(
+                io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+            )
+            .init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
+}
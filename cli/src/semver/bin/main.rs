@@ -1,6 +1,32 @@
-use core::SemanticComment;
+use core::{
+    check_versions, config_schema, detect_tag_formats, docker_tags, execute_promotion,
+    forge_from_env, insert_release, install_hook, open_vcs, plan_manifest_bump, plan_promotion,
+    plan_release, plan_updates, prepare_commit_message, regenerate_changelog, release_outputs,
+    render_unreleased, resume_release, rollback_release, sort_versions, updater_for,
+    write_azure_output, write_github_output, write_jenkins_output, write_teamcity_output, Commit,
+    Config, ForgeKind, Git, ParseCache, PushAuth, Registry, ReleaseOptions, ReleaseState,
+    SemVerError, SemanticVersion, VersionCheck, VersionFileConfig, VersionReq, CACHE_FILE_NAME,
+    JENKINS_OUTPUT_FILE_NAME, PREPARE_COMMIT_MSG_HOOK, PREPARE_COMMIT_MSG_SCRIPT,
+    RELEASE_STATE_FILE_NAME,
+};
+use std::{
+    cmp::Ordering,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use logging::{init_tracing, LogFormat};
+use style::{ColorChoice, Style};
+
+#[path = "../../logging.rs"]
+mod logging;
+#[cfg(feature = "server")]
+mod server;
+mod style;
 
-use clap::Parser;
 /// ! [`semver`] This cli parses the semantic version commit comment.
 ///
 /// It fails if the commit comment is not in valid format else
@@ -9,33 +35,1178 @@ use clap::Parser;
 /// - <semantic_type>: this is a <semantic_type>.
 /// - <semantic_type>! this is a <semantic_type>.
 ///
-/// Where <semantic_type> is [`fix`, `feat`, `refact`] and [`:`, `!`] means [`non_breaking`, `breaking`] respectively.
+/// Where <semantic_type> is [`fix`, `feat`, `refactor`] (`refact` is accepted as an alias) and [`:`, `!`] means [`non_breaking`, `breaking`] respectively.
 ///
 /// # Example:
 /// `semver --comment "feat! this is a breaking feature."`
 /// `semver --comment "fix: this is a non breaking fix."`
-/// `semver --comment "refact! this is a breaking refactor."`
+/// `semver --comment "refactor! this is a breaking refactor."`
+/// `semver parse --pr-title "feat(api): export (#123)"`
+/// `semver bump --manifest Cargo.toml --comment "feat: this is a feature."`
+/// `semver policy test --commit "docs: readme." --branch main`
+/// `semver lint --require-ticket`
+/// `semver scope --commit HEAD`
+/// `semver analyze main..feature`
+/// `semver check`
+/// `semver release --dry-run`
+/// `semver next --fetch`
+/// `semver next --package api`
+/// `semver next --registry crates-io --package foo`
+/// `semver next --registry npm --dist-tag next --package @team/web`
+/// `semver release --github-output`
+/// `semver release --azure-output`
+/// `semver next --teamcity-output`
+/// `semver next --jenkins-output semver.properties`
+/// `semver release --push --forge gitlab`
+/// `semver rollback --push`
+/// `semver release --push --remote upstream --push-token "$RELEASE_TOKEN"`
+/// `semver --output-json bump --dry-run --comment "fix: a fix."`
+/// `semver schema --types`
+/// `semver docker-tags v1.4.2 --template ghcr.io/team/app:{tag}`
+/// `semver completions bash > /etc/bash_completion.d/semver`
+/// `semver serve --port 8080`
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// `comment` is the comment from your vcs.
-    #[clap(short, long, value_parser)]
-    comment: String,
-    /// output-json controls if the output will be json.
-    #[arg(short, long, default_value_t = false)]
+    #[clap(short, long, value_parser, required = true, env = "SEMVER_COMMENT")]
+    comment: Option<String>,
+    /// output-json controls if the output (and dry-run plans) will be json.
+    #[arg(
+        short,
+        long,
+        default_value_t = false,
+        global = true,
+        env = "SEMVER_OUTPUT_JSON"
+    )]
     output_json: bool,
+    /// Appends `version`, `tag`, `bump` and `released` to the `$GITHUB_OUTPUT` file of the step.
+    #[arg(
+        long,
+        default_value_t = false,
+        global = true,
+        env = "SEMVER_GITHUB_OUTPUT"
+    )]
+    github_output: bool,
+    /// Prints the same outputs as Azure Pipelines `##vso[task.setvariable]` commands.
+    #[arg(
+        long,
+        default_value_t = false,
+        global = true,
+        env = "SEMVER_AZURE_OUTPUT"
+    )]
+    azure_output: bool,
+    /// Prints the same outputs as TeamCity service messages, the version as build number.
+    #[arg(
+        long,
+        default_value_t = false,
+        global = true,
+        env = "SEMVER_TEAMCITY_OUTPUT"
+    )]
+    teamcity_output: bool,
+    /// Writes the same outputs to a properties file for Jenkins, `semver.properties` by default.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = JENKINS_OUTPUT_FILE_NAME,
+        global = true,
+        env = "SEMVER_JENKINS_OUTPUT"
+    )]
+    jenkins_output: Option<PathBuf>,
+    /// Path to the configuration, defaults to `semver.toml` when present.
+    #[arg(long, global = true, env = "SEMVER_CONFIG")]
+    config: Option<PathBuf>,
+    /// Only accepts comments following Conventional Commits 1.0.0 exactly, e.g. `feat!: x`.
+    #[arg(
+        long,
+        default_value_t = false,
+        global = true,
+        env = "SEMVER_STRICT_SPEC"
+    )]
+    strict_spec: bool,
+    /// Colors the human-friendly output, `auto` when stdout is a terminal and `NO_COLOR` is unset.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ColorChoice::Auto,
+        global = true,
+        env = "SEMVER_COLOR"
+    )]
+    color: ColorChoice,
+    /// Parses every commit again instead of reusing the `.semver-cache.json` of previous runs.
+    #[arg(long, default_value_t = false, global = true, env = "SEMVER_NO_CACHE")]
+    no_cache: bool,
+    /// Fetches the tags, and the whole history of a shallow clone, before reading the history.
+    #[arg(long, default_value_t = false, global = true, env = "SEMVER_FETCH")]
+    fetch: bool,
+    /// Logs what the command does on stderr, `-vv` for every decision.
+    #[arg(short, long, action = clap::ArgAction::Count, global = true, env = "SEMVER_VERBOSE")]
+    verbose: u8,
+    /// Format of the logs, `json` emitting one object per event.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = LogFormat::Text,
+        global = true,
+        env = "SEMVER_LOG_FORMAT"
+    )]
+    log_format: LogFormat,
+    /// Remote `--push` pushes to, e.g. `upstream`, overriding the value of `--push`.
+    #[arg(long, global = true, env = "SEMVER_REMOTE")]
+    remote: Option<String>,
+    /// Pushes with the keys of the SSH agent of `$SSH_AUTH_SOCK`, over SSH even for an
+    /// `https` remote.
+    #[arg(
+        long,
+        default_value_t = false,
+        global = true,
+        conflicts_with_all = ["push_token", "credential_helper"],
+        env = "SEMVER_SSH_AGENT"
+    )]
+    ssh_agent: bool,
+    /// Pushes over `https` with this token, `<token>` or `<user>:<token>`, e.g.
+    /// `gitlab-ci-token:$CI_JOB_TOKEN`.
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "credential_helper",
+        hide_env_values = true,
+        env = "SEMVER_PUSH_TOKEN"
+    )]
+    push_token: Option<String>,
+    /// Pushes over `https` with this credential helper instead of the configured ones, e.g.
+    /// `store` or `!gh auth git-credential`.
+    #[arg(long, global = true, env = "SEMVER_CREDENTIAL_HELPER")]
+    credential_helper: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Bumps the `[package] version` of a Cargo manifest and the configured version files,
+    /// then prints the new version.
+    Bump {
+        /// Path to the `Cargo.toml` to bump.
+        #[arg(short, long, default_value = "Cargo.toml", env = "SEMVER_MANIFEST")]
+        manifest: PathBuf,
+        /// `comment` is the comment from your vcs.
+        #[clap(short, long, value_parser, env = "SEMVER_COMMENT")]
+        comment: String,
+        /// Also updates the requirements on this package in the other workspace members.
+        #[arg(
+            short,
+            long,
+            default_value_t = false,
+            env = "SEMVER_WORKSPACE_DEPENDENCIES"
+        )]
+        workspace_dependencies: bool,
+        /// Prints the planned changes without writing any file.
+        #[arg(long, default_value_t = false, env = "SEMVER_DRY_RUN")]
+        dry_run: bool,
+    },
+    /// Releases the commits since the last tag: computes the next version, updates the
+    /// version files and changelog, creates the release commit and tag, optionally pushes.
+    Release {
+        /// Prints every step without touching the repository.
+        #[arg(long, default_value_t = false, env = "SEMVER_DRY_RUN")]
+        dry_run: bool,
+        /// Pushes the release commit and tag to the remote.
+        #[arg(long, num_args = 0..=1, default_missing_value = "origin", env = "SEMVER_PUSH")]
+        push: Option<String>,
+        /// Branch the policy is evaluated for, defaults to the checked out branch.
+        #[clap(short, long, value_parser, env = "SEMVER_BRANCH")]
+        branch: Option<String>,
+        /// Releases a package of the `[packages]` section, tagged `<package>-v<version>`.
+        #[clap(short, long, value_parser, env = "SEMVER_PACKAGE")]
+        package: Option<String>,
+        /// Publishes the release to a forge (`gitlab`, `gitea`), with its changelog as notes.
+        #[arg(long, env = "SEMVER_FORGE")]
+        forge: Option<ForgeKind>,
+        /// Url of a self-hosted GitLab instance, implies `--forge gitlab`.
+        #[arg(long, conflicts_with = "gitea_url", env = "SEMVER_GITLAB_URL")]
+        gitlab_url: Option<String>,
+        /// Url of a Gitea or Forgejo instance, implies `--forge gitea`.
+        #[arg(long, env = "SEMVER_GITEA_URL")]
+        gitea_url: Option<String>,
+        /// Accepts a version equal to the latest one and reuses its tag instead of failing.
+        #[arg(long, default_value_t = false, env = "SEMVER_ALLOW_SAME_VERSION")]
+        allow_same_version: bool,
+        /// Version of the first release when nothing is tagged yet, `v0.1.0` unless configured.
+        #[arg(long, value_parser = parse_version, env = "SEMVER_INITIAL_VERSION")]
+        initial_version: Option<SemanticVersion>,
+        /// Finishes the release which failed halfway, from the steps recorded in
+        /// `.semver-release.json`, instead of planning a new one.
+        #[arg(
+            long,
+            default_value_t = false,
+            conflicts_with = "dry_run",
+            env = "SEMVER_RESUME"
+        )]
+        resume: bool,
+        /// Signs the release commit and tags with the key of `git config user.signingkey`,
+        /// a GPG key or, with `gpg.format = ssh`, an SSH key.
+        #[arg(long, default_value_t = false, env = "SEMVER_SIGN")]
+        sign: bool,
+    },
+    /// Undoes the release recorded in `.semver-release.json`, e.g. after a pipeline failed
+    /// halfway: deletes the created tags, reverts the release commit and its changelog entry.
+    Rollback {
+        /// Also deletes the pushed tags from the remote and pushes the revert.
+        #[arg(long, num_args = 0..=1, default_missing_value = "origin", env = "SEMVER_PUSH")]
+        push: Option<String>,
+    },
+    /// Updates the changelog, `CHANGELOG.md` unless configured, without bumping anything:
+    /// regenerates it from the version tags, e.g. to adopt `semver release` in a repository
+    /// with years of history, or refreshes its `Unreleased` section.
+    Changelog {
+        /// Renders a section for every pair of consecutive version tags, replacing the whole
+        /// changelog.
+        #[arg(long, required_unless_present = "unreleased", env = "SEMVER_ALL")]
+        all: bool,
+        /// Renders the commits since the latest version tag in an `Unreleased` section, which
+        /// `semver release` turns into the new version.
+        #[arg(long, env = "SEMVER_UNRELEASED")]
+        unreleased: bool,
+        /// Prints the changelog instead of writing it.
+        #[arg(long, default_value_t = false, env = "SEMVER_DRY_RUN")]
+        dry_run: bool,
+    },
+    /// Parses a comment like `--comment`, or the title of a pull request with `--pr-title`.
+    Parse {
+        /// `comment` is the comment from your vcs.
+        #[clap(value_parser, env = "SEMVER_COMMENT")]
+        comment: String,
+        /// Parses the title of a pull request: tolerates the `(#123)` suffix of squash merges
+        /// and reports the bump merging it causes.
+        #[arg(long, default_value_t = false, env = "SEMVER_PR_TITLE")]
+        pr_title: bool,
+        /// Branch the pull request targets, for the policy.
+        #[clap(
+            short,
+            long,
+            value_parser,
+            requires = "pr_title",
+            env = "SEMVER_BRANCH"
+        )]
+        branch: Option<String>,
+    },
+    /// Prints the tag of the next release without touching the repository.
+    Next {
+        /// Package of the `[packages]` section, tagged `<package>-v<version>`.
+        #[clap(short, long, value_parser, env = "SEMVER_PACKAGE")]
+        package: Option<String>,
+        /// Version of the first release when nothing is tagged yet, `v0.1.0` unless configured.
+        #[arg(long, value_parser = parse_version, env = "SEMVER_INITIAL_VERSION")]
+        initial_version: Option<SemanticVersion>,
+        /// Branch the policy is evaluated for, defaults to the checked out branch.
+        #[clap(short, long, value_parser, env = "SEMVER_BRANCH")]
+        branch: Option<String>,
+        /// Prints a unique version for a build between releases, e.g.
+        /// `v1.3.0-nightly.20240601+sha.abc1234`, see `snapshot_template`.
+        #[arg(long, default_value_t = false, env = "SEMVER_SNAPSHOT")]
+        snapshot: bool,
+        /// Starts from the latest version of `--package` published to a registry (`crates-io`,
+        /// `npm`) instead of the latest tag. A package missing from `[packages]` is the whole
+        /// repository.
+        #[arg(long, requires = "package", env = "SEMVER_REGISTRY")]
+        registry: Option<Registry>,
+        /// npm dist-tag the published version is read from, `latest` by default.
+        #[arg(long, requires = "registry", env = "SEMVER_DIST_TAG")]
+        dist_tag: Option<String>,
+    },
+    /// Promotes a prerelease to the version it announces, e.g. `v2.0.0-rc.3` to `v2.0.0`,
+    /// failing when a breaking change landed since the prerelease was cut.
+    Promote {
+        /// Prerelease tag to promote, defaults to the greatest one reachable from HEAD.
+        from: Option<String>,
+        /// Tags HEAD with the promoted version, otherwise only prints it.
+        #[arg(long, default_value_t = false, env = "SEMVER_TAG")]
+        tag: bool,
+        /// Pushes the promoted tag to the remote, implies `--tag`.
+        #[arg(long, num_args = 0..=1, default_missing_value = "origin", env = "SEMVER_PUSH")]
+        push: Option<String>,
+        /// Signs the promoted tag with the key of `git config user.signingkey`, implies `--tag`.
+        #[arg(long, default_value_t = false, env = "SEMVER_SIGN")]
+        sign: bool,
+    },
+    /// Compares two versions: prints `lt`, `eq` or `gt` and the component separating them
+    /// (`major`, `minor`, `patch` or `prerelease`).
+    ///
+    /// Exits with 0 when they are equal, 11 when the left one is lower and 12 when it is greater.
+    Compare {
+        #[arg(value_parser = parse_version)]
+        left: SemanticVersion,
+        #[arg(value_parser = parse_version)]
+        right: SemanticVersion,
+    },
+    /// Checks a version against a requirement such as `^1.2`, `~1.2.3`, `>=1.0, <2` or `1.*`:
+    /// prints `true` or `false`, and exits with 1 when it is not satisfied.
+    Satisfies {
+        #[arg(value_parser = VersionReq::parse)]
+        requirement: VersionReq,
+        #[arg(value_parser = parse_version)]
+        version: SemanticVersion,
+        /// Describes which comparator of each alternative the version fails.
+        #[arg(long, default_value_t = false, env = "SEMVER_EXPLAIN")]
+        explain: bool,
+    },
+    /// Prints the container image tags of a version, one per line: `1`, `1.4`, `1.4.2` and
+    /// `latest`, or only the full version for a prerelease.
+    DockerTags {
+        #[arg(value_parser = parse_version)]
+        version: SemanticVersion,
+        /// Template of each tag, `{tag}` standing for the tag, e.g. `ghcr.io/team/app:{tag}`.
+        #[arg(long, default_value = "{tag}", env = "SEMVER_TEMPLATE")]
+        template: String,
+    },
+    /// Reads versions from stdin, one per line, and prints them in precedence order, lowest
+    /// first; lines which are not versions are left out.
+    Sort {
+        /// Prints the highest version first.
+        #[arg(short, long, default_value_t = false, env = "SEMVER_REVERSE")]
+        reverse: bool,
+    },
+    /// Reads versions from stdin, one per line, and prints the highest one.
+    Max,
+    /// Reads versions from stdin, one per line, and prints the lowest one.
+    Min,
+    /// Reports on the commits of a revision range: counts by type and scope, breaking changes,
+    /// unparseable commits and the bump the range implies.
+    Analyze {
+        /// Git revision range, e.g. `main..feature`.
+        range: String,
+        /// Branch the policy is evaluated for, defaults to the checked out branch.
+        #[clap(short, long, value_parser, env = "SEMVER_BRANCH")]
+        branch: Option<String>,
+    },
+    /// Checks that the version files hold the version of the latest tag bumped by the commits
+    /// since, failing when they were not bumped or bumped inconsistently.
+    Check {
+        /// Files to check, defaults to the configured version files, or to `Cargo.toml`
+        /// and `package.json` when present.
+        #[arg(short, long, env = "SEMVER_FILE")]
+        file: Vec<PathBuf>,
+        /// Branch the policy is evaluated for, defaults to the checked out branch.
+        #[clap(short, long, value_parser, env = "SEMVER_BRANCH")]
+        branch: Option<String>,
+    },
+    /// Prints statistics on the history: commits by type, top scopes and release cadence.
+    Stats,
+    /// Infers the tag format from the tags of the repository and prints it as a `semver.toml`
+    /// line, the other formats in use commented out below it.
+    DetectFormat,
+    /// Prints the JSON Schema of `semver.toml`, e.g. for editors to validate and complete it.
+    Schema {
+        /// Prints the configured types, scopes and lint rules as json instead, e.g. for editor
+        /// extensions to complete commit messages.
+        #[arg(long, default_value_t = false, env = "SEMVER_TYPES")]
+        types: bool,
+    },
+    /// Checks commit messages: since the last version tag, in `--range` or the given `--message`.
+    Lint {
+        /// Message to check instead of the commits of the repository.
+        #[clap(
+            short,
+            long,
+            value_parser,
+            conflicts_with = "range",
+            env = "SEMVER_MESSAGE"
+        )]
+        message: Option<String>,
+        /// File holding the message to check, e.g. `$1` in a `commit-msg` hook; its `#` lines
+        /// are left out as git does.
+        #[arg(long, conflicts_with_all = ["message", "range"], env = "SEMVER_MESSAGE_FILE")]
+        message_file: Option<PathBuf>,
+        /// Rewrites the message file in place to fix the type casing and aliases, the separator
+        /// and, when configured, the subject case and trailing period before checking it.
+        #[arg(
+            long,
+            default_value_t = false,
+            requires = "message_file",
+            conflicts_with = "message",
+            env = "SEMVER_FIX"
+        )]
+        fix: bool,
+        /// Git revision range of the commits to check, e.g. `main..HEAD`.
+        #[arg(long, env = "SEMVER_RANGE")]
+        range: Option<String>,
+        /// Rejects commits without a ticket, as `[tickets] required = true` does.
+        #[arg(long, default_value_t = false, env = "SEMVER_REQUIRE_TICKET")]
+        require_ticket: bool,
+        /// Fails when the commits have more warnings than this, warnings never fail otherwise.
+        #[arg(long, env = "SEMVER_MAX_WARNINGS")]
+        max_warnings: Option<usize>,
+    },
+    /// Prints the scope of the staged changes, inferred from the `[scopes]` path globs, e.g.
+    /// to prefill commit messages from a `prepare-commit-msg` hook. Exits with 1 when no
+    /// changed path belongs to a scope.
+    Scope {
+        /// Infers the scope of this commit instead of the staged changes.
+        #[arg(long, env = "SEMVER_COMMIT")]
+        commit: Option<String>,
+    },
+    /// Installs and runs the git hooks of the repository.
+    Hook {
+        #[command(subcommand)]
+        command: HookCommand,
+    },
+    /// Works with the bump policy declared in the configuration.
+    Policy {
+        #[command(subcommand)]
+        command: PolicyCommand,
+    },
+    /// Serves `POST /parse`, `POST /next-version` and `POST /lint` over HTTP, with json bodies,
+    /// following the configuration loaded at startup.
+    #[cfg(feature = "server")]
+    Serve {
+        /// Port to listen on.
+        #[arg(long, default_value_t = 8080, env = "SEMVER_PORT")]
+        port: u16,
+        /// Address to listen on, e.g. `0.0.0.0` for every interface.
+        #[arg(long, default_value = "127.0.0.1", env = "SEMVER_BIND")]
+        bind: std::net::IpAddr,
+        /// Serves `POST /webhook/github`, checking the payloads against this secret. The
+        /// statuses are posted with `$GITHUB_TOKEN` to `$GITHUB_API_URL`.
+        #[arg(long, env = "SEMVER_GITHUB_WEBHOOK_SECRET", hide_env_values = true)]
+        github_webhook_secret: Option<String>,
+    },
+    /// Prints the completion script of the shell, e.g. `semver completions zsh > ~/.zfunc/_semver`.
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum HookCommand {
+    /// Installs the selected hooks in the repository, `core.hooksPath` included.
+    Install {
+        /// Pre-fills the commit editor with the accepted types and scopes as comments.
+        #[arg(
+            long,
+            default_value_t = false,
+            required = true,
+            env = "SEMVER_PREPARE_COMMIT_MSG"
+        )]
+        prepare_commit_msg: bool,
+        /// Replaces the hooks already installed by other tools.
+        #[arg(long, default_value_t = false, env = "SEMVER_FORCE")]
+        force: bool,
+    },
+    /// Runs as the `prepare-commit-msg` hook, with the arguments git passes to it.
+    PrepareCommitMsg {
+        /// File holding the commit message.
+        file: PathBuf,
+        /// Source of the message, e.g. `message` for `-m`; the template is only added without.
+        source: Option<String>,
+        /// Commit of the message, for amends.
+        sha: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PolicyCommand {
+    /// Runs the `[[policy.examples]]` against the policy, or prints the bump of the given commit.
+    Test {
+        /// Commit to evaluate instead of the configured examples.
+        #[clap(short, long, value_parser, env = "SEMVER_COMMIT")]
+        commit: Option<String>,
+        /// Branch the commit is evaluated on.
+        #[clap(short, long, value_parser, env = "SEMVER_BRANCH")]
+        branch: Option<String>,
+    },
+}
+
+fn parse_version(version: &str) -> Result<SemanticVersion, SemVerError> {
+    SemanticVersion::try_from(format!("v{}", version.trim_start_matches('v')).as_str())
+}
+
+fn load_config(path: Option<PathBuf>, strict_spec: bool) -> Result<Config, SemVerError> {
+    let mut config = match path {
+        Some(path) => Config::load(path),
+        None => Config::discover("."),
+    }?;
+    config.strict_spec |= strict_spec;
+
+    Ok(config)
+}
+
+/// Hands the `outputs` of a release to the CI systems asked for on the command line.
+fn write_outputs(
+    github: bool,
+    azure: bool,
+    teamcity: bool,
+    jenkins: Option<&Path>,
+    outputs: &[(&str, String)],
+) -> Result<(), SemVerError> {
+    if github {
+        write_github_output(outputs)?;
+    }
+    if azure {
+        write_azure_output(outputs);
+    }
+    if teamcity {
+        write_teamcity_output(outputs);
+    }
+    if let Some(path) = jenkins {
+        write_jenkins_output(path, outputs)?;
+    }
+
+    Ok(())
+}
+
+/// Opens the repository of the working directory, pushing with the authentication asked for on
+/// the command line.
+fn open_git(args: &Args) -> Git {
+    let auth = match (args.ssh_agent, &args.push_token, &args.credential_helper) {
+        (true, _, _) => PushAuth::SshAgent,
+        (_, Some(token), _) => PushAuth::Token(token.clone()),
+        (_, _, Some(helper)) => PushAuth::CredentialHelper(helper.clone()),
+        _ => PushAuth::Git,
+    };
+
+    Git::new(".").with_push_auth(auth)
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
+    init_tracing(args.verbose, args.log_format);
+    let style = Style::new(args.color);
+    let git = open_git(&args);
+    let remote = args.remote.clone();
+    if args.fetch {
+        open_vcs(".").fetch_history()?;
+    }
+
+    match args.command {
+        Some(Command::Bump {
+            manifest,
+            comment,
+            workspace_dependencies,
+            dry_run,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let semantic_comment = config.comment_parser().parse(comment.as_str())?;
+            let mut plan = plan_manifest_bump(manifest, &semantic_comment, workspace_dependencies)?;
+            plan.changes
+                .extend(plan_updates(&config.updaters()?, &plan.version)?);
+
+            if dry_run {
+                match args.output_json {
+                    true => println!("{}", plan.as_json_string()?),
+                    false => plan
+                        .steps()
+                        .iter()
+                        .for_each(|step| println!("[dry-run] {}", step)),
+                }
+                return Ok(());
+            }
+            plan.apply()?;
+
+            println!("{}", style.version(&String::from(plan.version)));
+        }
+        Some(Command::Release {
+            dry_run,
+            push,
+            branch,
+            package,
+            forge,
+            gitlab_url,
+            gitea_url,
+            allow_same_version,
+            initial_version,
+            resume,
+            sign,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let push = push.map(|push| remote.unwrap_or(push));
+            let forge = match (&gitlab_url, &gitea_url) {
+                (Some(_), _) => Some(ForgeKind::GitLab),
+                (_, Some(_)) => Some(ForgeKind::Gitea),
+                _ => forge,
+            };
+            let forge_url = gitlab_url.or(gitea_url);
+            let state_path = git.directory().join(RELEASE_STATE_FILE_NAME);
+            let state = ReleaseState::load(&state_path)?.filter(|state| !state.is_complete());
+
+            if resume {
+                let mut state = state.ok_or(SemVerError::NoReleaseToResume)?;
+                let forge = state
+                    .forge
+                    .as_deref()
+                    .map(|name| {
+                        forge_from_env(name.parse::<ForgeKind>()?, forge_url.as_deref(), &git)
+                    })
+                    .transpose()?;
+                resume_release(&git, forge.as_deref(), &mut state, Some(&state_path))?;
+                println!("{}", style.version(&state.tag));
+                return Ok(());
+            }
+            if let Some(state) = state.filter(|_| !dry_run) {
+                return Err(SemVerError::UnfinishedRelease(state.tag).into());
+            }
+            let options = ReleaseOptions {
+                branch,
+                push,
+                package,
+                forge,
+                allow_same_version,
+                initial_version,
+                cache: !args.no_cache,
+                registry: None,
+                sign,
+            };
+            let plan = plan_release(&git, &config, &options)?;
+            if args.verbose > 0 {
+                eprintln!("{}", plan.decision);
+            }
+
+            let released = !dry_run && !plan.is_empty();
+
+            if dry_run && args.output_json {
+                println!("{}", plan.as_json_string()?);
+            } else {
+                for step in plan.steps() {
+                    match dry_run {
+                        true => println!("[dry-run] {}", step),
+                        false => eprintln!("{}", step),
+                    }
+                }
+            }
+            if released {
+                // fails on a missing token before touching the repository
+                let forge = plan
+                    .forge
+                    .map(|kind| forge_from_env(kind, forge_url.as_deref(), &git))
+                    .transpose()?;
+
+                let mut state = ReleaseState::new(&plan, forge.as_deref());
+                resume_release(&git, forge.as_deref(), &mut state, Some(&state_path))?;
+            }
+            write_outputs(
+                args.github_output,
+                args.azure_output,
+                args.teamcity_output,
+                args.jenkins_output.as_deref(),
+                &release_outputs(&plan, released),
+            )?;
+
+            if plan.already_released {
+                eprintln!("already released");
+                if !(dry_run && args.output_json) {
+                    println!("{}", style.version(&plan.tag));
+                }
+            } else if plan.is_empty() {
+                eprintln!("nothing to release");
+            } else if !(dry_run && args.output_json) {
+                println!("{}", style.version_change(&plan.previous_tag, &plan.tag));
+            }
+        }
+        Some(Command::Rollback { push }) => {
+            let push = push.map(|push| remote.unwrap_or(push));
+            let state_path = git.directory().join(RELEASE_STATE_FILE_NAME);
+            let mut state =
+                ReleaseState::load(&state_path)?.ok_or(SemVerError::NoReleaseToRollback)?;
+
+            for step in rollback_release(&git, &mut state, push.as_deref(), Some(&state_path))? {
+                println!("{}", step);
+            }
+            fs::remove_file(&state_path)?;
+        }
+        Some(Command::Changelog {
+            all,
+            unreleased,
+            dry_run,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let vcs = open_vcs(".");
+            let path = config
+                .changelog
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("CHANGELOG.md"));
+            let mut changelog = match all {
+                true => regenerate_changelog(vcs.as_ref(), &config)?,
+                false if path.exists() => fs::read_to_string(&path)?,
+                false => String::new(),
+            };
+            if unreleased {
+                changelog = insert_release(&changelog, &render_unreleased(vcs.as_ref(), &config)?);
+            }
+
+            match dry_run {
+                true => print!("{}", changelog),
+                false => {
+                    fs::write(&path, changelog)?;
+                    println!("wrote {}", path.display());
+                }
+            }
+        }
+        Some(Command::Parse {
+            comment,
+            pr_title: true,
+            branch,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let title = config.analyze_pr_title(&comment, branch.as_deref())?;
+
+            match args.output_json {
+                true => println!("{}", title.as_json_string()?),
+                false => {
+                    println!("{}", style.comment(&title.comment, config.refactor_keyword));
+                    println!("bump: {}", title.bump);
+                }
+            }
+        }
+        Some(Command::Parse { comment, .. }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let semantic_comment = config.comment_parser().parse(comment.as_str())?;
+
+            match args.output_json {
+                true => println!("{}", semantic_comment.as_json_string()?),
+                false => println!(
+                    "{}",
+                    style.comment(&semantic_comment, config.refactor_keyword)
+                ),
+            }
+        }
+        Some(Command::Next {
+            package,
+            initial_version,
+            branch,
+            snapshot,
+            registry,
+            dist_tag,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let registry = registry
+                .zip(package.clone())
+                .map(|(registry, name)| match &dist_tag {
+                    Some(dist_tag) => (registry, format!("{}@{}", name, dist_tag)),
+                    None => (registry, name),
+                });
+            let package = package
+                .filter(|name| registry.is_none() || config.packages.contains_key(name.as_str()));
+            let options = ReleaseOptions {
+                branch,
+                package,
+                registry,
+                initial_version,
+                cache: !args.no_cache,
+                ..Default::default()
+            };
+            let vcs = open_vcs(".");
+            let mut plan = plan_release(vcs.as_ref(), &config, &options)?;
+            if args.verbose > 0 {
+                eprintln!("{}", plan.decision);
+            }
+            if snapshot {
+                let seconds = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default();
+                let sha = vcs.commit("HEAD")?.sha.chars().take(7).collect::<String>();
+                let mut format = config.tag_format()?;
+                if let Some(package) = &options.package {
+                    format = config.package(package)?.tag_format(&format)?;
+                }
+                plan.tag = plan.snapshot_tag(&format, config.snapshot_template(), seconds, &sha)?;
+            }
+
+            match args.output_json {
+                true => println!("{}", plan.as_json_string()?),
+                false => println!("{}", style.version_change(&plan.previous_tag, &plan.tag)),
+            }
+            write_outputs(
+                args.github_output,
+                args.azure_output,
+                args.teamcity_output,
+                args.jenkins_output.as_deref(),
+                &release_outputs(&plan, false),
+            )?;
+        }
+        Some(Command::Promote {
+            from,
+            tag,
+            push,
+            sign,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let push = push.map(|push| remote.unwrap_or(push));
+            let promotion = plan_promotion(&git, &config, from.as_deref())?;
+
+            if tag || sign || push.is_some() {
+                execute_promotion(&git, &promotion, push.as_deref(), sign)?;
+            }
+            match args.output_json {
+                true => println!("{}", promotion.as_json_string()?),
+                false => println!(
+                    "{}",
+                    style.version_change(&promotion.prerelease_tag, &promotion.tag)
+                ),
+            }
+        }
+        Some(Command::Compare { left, right }) => {
+            let diff = left.diff(&right);
+
+            match args.output_json {
+                true => println!("{}", diff.as_json_string()?),
+                false => println!("{}", diff),
+            }
+            match diff.ordering {
+                Ordering::Equal => {}
+                Ordering::Less => std::process::exit(11),
+                Ordering::Greater => std::process::exit(12),
+            }
+        }
+        #[cfg(feature = "server")]
+        Some(Command::Serve {
+            port,
+            bind,
+            github_webhook_secret,
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let github = github_webhook_secret.map(|secret| server::GitHubHook {
+                secret,
+                token: std::env::var("GITHUB_TOKEN").ok().filter(|t| !t.is_empty()),
+                api_url: std::env::var("GITHUB_API_URL")
+                    .unwrap_or_else(|_| "https://api.github.com".to_string()),
+            });
+            server::serve(config, github, std::net::SocketAddr::new(bind, port))?;
+        }
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(shell, &mut Args::command(), "semver", &mut io::stdout());
+        }
+        Some(Command::Satisfies {
+            requirement,
+            version,
+            explain,
+        }) => {
+            let explanation = requirement.explain(&version);
+
+            println!("{}", explanation.satisfied);
+            if explain {
+                println!("{}", explanation);
+            }
+            if !explanation.satisfied {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::DockerTags { version, template }) => {
+            let tags = docker_tags(&version, &template);
+
+            match args.output_json {
+                true => println!("{}", serde_json::to_string(&tags)?),
+                false => tags.iter().for_each(|tag| println!("{}", tag)),
+            }
+        }
+        Some(command @ (Command::Sort { .. } | Command::Max | Command::Min)) => {
+            let input = io::read_to_string(io::stdin())?;
+            let mut versions = sort_versions(input.lines())
+                .into_iter()
+                .map(|(line, _)| line)
+                .collect::<Vec<_>>();
+
+            match command {
+                Command::Sort { reverse: true } => versions.reverse(),
+                Command::Max => versions = versions.pop().into_iter().collect(),
+                Command::Min => versions.truncate(1),
+                _ => {}
+            }
+            if versions.is_empty() {
+                eprintln!("no version read");
+                std::process::exit(1);
+            }
+            for version in versions {
+                println!("{}", version);
+            }
+        }
+        Some(Command::Analyze { range, branch }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let vcs = open_vcs(".");
+            let branch = match branch {
+                Some(branch) => Some(branch),
+                None => vcs.current_branch()?,
+            };
+            let commits = vcs.commits(Some(&range))?;
+            let analysis = match args.no_cache {
+                true => config.analyze(&commits, branch.as_deref())?,
+                false => {
+                    let path = vcs.directory().join(CACHE_FILE_NAME);
+                    let mut cache = ParseCache::load(&path, &config);
+                    let analysis =
+                        config.analyze_cached(&commits, branch.as_deref(), &mut cache)?;
+                    cache.save(&path)?;
+                    analysis
+                }
+            };
+
+            match args.output_json {
+                true => println!("{}", analysis.as_json_string()?),
+                false => print!("{}", analysis.report()),
+            }
+        }
+        Some(Command::Check { file, branch }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let vcs = open_vcs(".");
+            let branch = match branch {
+                Some(branch) => Some(branch),
+                None => vcs.current_branch()?,
+            };
+            let mut files = file;
+            if files.is_empty() && config.files.is_empty() {
+                files = ["Cargo.toml", "package.json"]
+                    .into_iter()
+                    .map(PathBuf::from)
+                    .filter(|path| path.exists())
+                    .collect();
+            }
+            let updaters = match files.is_empty() {
+                true => config.updaters()?,
+                false => files
+                    .into_iter()
+                    .map(|path| {
+                        updater_for(&VersionFileConfig {
+                            path,
+                            pattern: None,
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            };
+
+            let checks = check_versions(vcs.as_ref(), &config, &updaters, branch.as_deref())?;
+            if args.output_json {
+                let checks = checks
+                    .iter()
+                    .map(VersionCheck::as_json_string)
+                    .collect::<Result<Vec<_>, _>>()?;
+                println!("[{}]", checks.join(","));
+            }
+            for check in &checks {
+                match (check.problem(), args.output_json) {
+                    (_, true) => {}
+                    (None, false) => println!(
+                        "{} {} {}",
+                        style.ok(),
+                        check.path.display(),
+                        String::from(check.version.clone())
+                    ),
+                    (Some(problem), false) => {
+                        println!("{} {} {}", style.fail(), check.path.display(), problem)
+                    }
+                }
+            }
+            if checks.iter().any(|check| !check.is_ok()) {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Stats) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let vcs = open_vcs(".");
+            let stats = config.stats(&vcs.commits(None)?, &vcs.tags()?)?;
+
+            match args.output_json {
+                true => println!("{}", stats.as_json_string()?),
+                false => print!("{}", stats.report()),
+            }
+        }
+        Some(Command::DetectFormat) => {
+            let detected = detect_tag_formats(&open_vcs(".").tag_names()?);
+            if detected.suggested().is_none() {
+                eprintln!("no version tag found");
+                std::process::exit(1);
+            }
+
+            match args.output_json {
+                true => println!("{}", detected.as_json_string()?),
+                false => print!("{}", detected.report()),
+            }
+        }
+        Some(Command::Schema { types }) => match types {
+            true => {
+                let config = load_config(args.config, args.strict_spec)?;
+                println!("{}", config.conventions().as_json_string()?);
+            }
+            false => println!("{:#}", config_schema()),
+        },
+        Some(Command::Lint {
+            message,
+            message_file,
+            fix,
+            range,
+            require_ticket,
+            max_warnings,
+        }) => {
+            let mut config = load_config(args.config, args.strict_spec)?;
+            config.tickets.required |= require_ticket;
+
+            let message = match message_file {
+                Some(path) => {
+                    let mut contents = fs::read_to_string(&path)?;
+                    if fix {
+                        let fixed = config.fix_message(&contents);
+                        if !fixed.fixes.is_empty() {
+                            fs::write(&path, &fixed.message)?;
+                            for fix in &fixed.fixes {
+                                eprintln!("fixed: {}", fix);
+                            }
+                            contents = fixed.message;
+                        }
+                    }
+                    let lines = contents.lines().filter(|line| !line.starts_with('#'));
+                    Some(lines.collect::<Vec<_>>().join("\n"))
+                }
+                None => message,
+            };
+            let commits = match message {
+                Some(message) => vec![Commit::new("-", message)],
+                None => {
+                    let vcs = open_vcs(".");
+                    let range = match range {
+                        Some(range) => Some(range),
+                        None => vcs
+                            .latest_version_tag_with_format(&config.tag_format()?)?
+                            .map(|(tag, _)| format!("{}..HEAD", tag.name)),
+                    };
+                    vcs.commits(range.as_deref())?
+                }
+            };
+            let report = config.lint_report(&commits);
+
+            match args.output_json {
+                true => println!("{}", report.as_json_string()?),
+                false => {
+                    for commit in &report.commits {
+                        let status = match (commit.errors.is_empty(), commit.warnings.is_empty()) {
+                            (false, _) => style.fail(),
+                            (true, false) => style.warn(),
+                            (true, true) => style.ok(),
+                        };
+                        let sha = commit.sha.chars().take(7).collect::<String>();
+                        println!("{} {:<7} {}", status, sha, commit.subject);
+                        // the first line of an error is enough in a table
+                        for problem in commit.errors.iter().chain(&commit.warnings) {
+                            println!(
+                                "     {:<7} {}",
+                                "",
+                                problem.lines().next().unwrap_or_default().trim_end()
+                            );
+                        }
+                    }
+                    println!(
+                        "{} commits, {} errors, {} warnings{}",
+                        report.commits.len(),
+                        report.errors,
+                        report.warnings,
+                        match max_warnings {
+                            Some(max) => format!(" (max {})", max),
+                            None => String::new(),
+                        }
+                    );
+                }
+            }
+            if !report.passed(max_warnings) {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Hook {
+            command:
+                HookCommand::Install {
+                    prepare_commit_msg,
+                    force,
+                },
+        }) => {
+            let git = Git::new(".");
+            if prepare_commit_msg {
+                let path = install_hook(
+                    &git,
+                    PREPARE_COMMIT_MSG_HOOK,
+                    PREPARE_COMMIT_MSG_SCRIPT,
+                    force,
+                )?;
+                println!("installed {}", path.display());
+            }
+        }
+        Some(Command::Hook {
+            command: HookCommand::PrepareCommitMsg { file, source, .. },
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            prepare_commit_message(&config, file, source.as_deref())?;
+        }
+        Some(Command::Scope { commit }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let vcs = open_vcs(".");
+            let paths = match commit {
+                Some(commit) => vcs.changed_paths(&commit)?,
+                None => vcs.staged_paths()?,
+            };
+
+            match config.scope_map()?.infer(&paths) {
+                Some(scope) => println!("{}", scope),
+                None => {
+                    eprintln!("no scope inferred");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Policy {
+            command: PolicyCommand::Test { commit, branch },
+        }) => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let parser = config.comment_parser();
+            let policy = config.bump_policy()?;
+
+            if let Some(commit) = commit {
+                let semantic_comment = parser.parse(commit.as_str())?;
+                println!("{}", policy.evaluate(&semantic_comment, branch.as_deref()));
+                return Ok(());
+            }
+
+            let mut failures = 0;
+            for example in &config.policy.examples {
+                let semantic_comment = parser.parse(example.commit.as_str())?;
+                let bump = policy.evaluate(&semantic_comment, example.branch.as_deref());
+                let branch = example.branch.as_deref().unwrap_or("-");
 
-    let semantic_comment = SemanticComment::try_from(args.comment.as_str())?;
+                if bump == example.expect {
+                    println!("{} [{}] {} => {}", style.ok(), branch, example.commit, bump);
+                } else {
+                    failures += 1;
+                    println!(
+                        "{} [{}] {} => {}, expected {}",
+                        style.fail(),
+                        branch,
+                        example.commit,
+                        bump,
+                        example.expect
+                    );
+                }
+            }
+            println!(
+                "{} passed, {} failed",
+                config.policy.examples.len() - failures,
+                failures
+            );
+            if failures > 0 {
+                std::process::exit(1);
+            }
+        }
+        None => {
+            let config = load_config(args.config, args.strict_spec)?;
+            let comment = args.comment.unwrap_or_default();
+            let semantic_comment = config.comment_parser().parse(comment.as_str())?;
 
-    if args.output_json {
-        println!("{}", semantic_comment.as_json_string()?);
-    } else {
-        println!("{:?}", semantic_comment)
+            if args.output_json {
+                println!("{}", semantic_comment.as_json_string()?);
+            } else {
+                println!(
+                    "{}",
+                    style.comment(&semantic_comment, config.refactor_keyword)
+                )
+            }
+        }
     }
 
     Ok(())
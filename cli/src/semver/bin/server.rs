@@ -0,0 +1,367 @@
+//! `semver serve`: the parser, versioner and linter over HTTP, with the configuration loaded at
+//! startup, so that every client follows the same policy.
+
+use std::{error::Error, net::SocketAddr, sync::Arc};
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use core::{
+    post_github_status, verify_github_signature, AggregateBump, BumpLevel, CommitCheck, Config,
+    SemVerError, SemanticComment, SemanticVersion, GITHUB_EVENT_HEADER, GITHUB_SIGNATURE_HEADER,
+};
+use serde::{Deserialize, Serialize};
+
+/// [`GitHubHook`] configures `POST /webhook/github`.
+pub struct GitHubHook {
+    /// Secret the payloads are signed with.
+    pub secret: String,
+    /// Token the commit statuses are posted with, they are only answered without one.
+    pub token: Option<String>,
+    /// Url of the API, e.g. `https://api.github.com`.
+    pub api_url: String,
+}
+
+/// Serves the routes on `address` until the process is stopped, with the GitHub webhook when
+/// `github` is configured.
+pub fn serve(
+    config: Config,
+    github: Option<GitHubHook>,
+    address: SocketAddr,
+) -> Result<(), Box<dyn Error>> {
+    let app = router(config, github);
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(async {
+            let listener = tokio::net::TcpListener::bind(address).await?;
+            eprintln!("listening on http://{}", listener.local_addr()?);
+            axum::serve(listener, app).await
+        })?;
+
+    Ok(())
+}
+
+fn router(config: Config, github: Option<GitHubHook>) -> Router {
+    let config = Arc::new(config);
+    let router = Router::new()
+        .route("/parse", post(parse))
+        .route("/next-version", post(next_version))
+        .route("/lint", post(lint))
+        .with_state(config.clone());
+
+    match github {
+        Some(github) => router.merge(
+            Router::new()
+                .route("/webhook/github", post(github_webhook))
+                .with_state(Arc::new((config, github))),
+        ),
+        None => router,
+    }
+}
+
+#[derive(Deserialize)]
+struct ParseRequest {
+    comment: String,
+}
+
+/// `POST /parse`: returns the comment as `semver --output-json` prints it.
+async fn parse(
+    State(config): State<Arc<Config>>,
+    Json(request): Json<ParseRequest>,
+) -> Result<Json<SemanticComment>, ApiError> {
+    Ok(Json(config.comment_parser().parse(&request.comment)?))
+}
+
+#[derive(Deserialize)]
+struct NextVersionRequest {
+    current_version: String,
+    /// Commit messages since `current_version`.
+    comments: Vec<String>,
+    /// Branch the policy is evaluated for.
+    #[serde(default)]
+    branch: Option<String>,
+}
+
+#[derive(Serialize)]
+struct NextVersionResponse {
+    version: String,
+    bump: BumpLevel,
+    /// Comments which do not parse, left out of the bump.
+    unparsed: Vec<String>,
+}
+
+/// `POST /next-version`: returns the version following `current_version` for the comments.
+async fn next_version(
+    State(config): State<Arc<Config>>,
+    Json(request): Json<NextVersionRequest>,
+) -> Result<Json<NextVersionResponse>, ApiError> {
+    let parser = config.comment_parser();
+    let policy = config.bump_policy()?;
+    let current = SemanticVersion::try_from(request.current_version.as_str())?;
+
+    let mut bump = AggregateBump::default();
+    let mut unparsed = vec![];
+    for message in request.comments {
+        match parser.parse_message(&message) {
+            Ok(comment) => bump.add_with_policy(&comment, &policy, request.branch.as_deref()),
+            Err(_) => unparsed.push(message),
+        }
+    }
+
+    Ok(Json(NextVersionResponse {
+        version: config.next_version(&current, bump.bump)?.into(),
+        bump: bump.bump,
+        unparsed,
+    }))
+}
+
+#[derive(Deserialize)]
+struct LintRequest {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct LintResponse {
+    valid: bool,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+/// `POST /lint`: checks a commit message like `semver lint --message`.
+async fn lint(
+    State(config): State<Arc<Config>>,
+    Json(request): Json<LintRequest>,
+) -> Json<LintResponse> {
+    let errors = config
+        .lint(&request.message)
+        .err()
+        .map(|error| error.to_string())
+        .into_iter()
+        .collect::<Vec<_>>();
+    let warnings = config
+        .lint_warnings(&request.message)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    Json(LintResponse {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    })
+}
+
+/// `POST /webhook/github`: lints the commits of a push or the title of a pull request, posts
+/// the result as a commit status and answers it, `204` for the other events.
+async fn github_webhook(
+    State(hook): State<Arc<(Arc<Config>, GitHubHook)>>,
+    headers: HeaderMap,
+    payload: Bytes,
+) -> Result<Response, ApiError> {
+    let (config, github) = hook.as_ref();
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let signature = header(GITHUB_SIGNATURE_HEADER).unwrap_or_default();
+    if !verify_github_signature(github.secret.as_bytes(), &payload, signature) {
+        return Err(ApiError(
+            StatusCode::UNAUTHORIZED,
+            "invalid signature".to_string(),
+        ));
+    }
+    let event = header(GITHUB_EVENT_HEADER)
+        .ok_or_else(|| ApiError(StatusCode::BAD_REQUEST, "missing event".to_string()))?;
+    let payload = serde_json::from_slice(&payload)
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let Some(check) = config.check_github_event(event, &payload)? else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    if let Some(token) = github.token.clone() {
+        let (status, api_url) = (check.clone(), github.api_url.clone());
+        tokio::task::spawn_blocking(move || post_github_status(&status, &api_url, &token))
+            .await
+            .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .map_err(|e| ApiError(StatusCode::BAD_GATEWAY, e.to_string()))?;
+    }
+
+    Ok(Json::<CommitCheck>(check).into_response())
+}
+
+/// A failed request, answered with its status and `{"error": "..."}`: `422` for inputs which
+/// do not parse.
+struct ApiError(StatusCode, String);
+
+impl From<SemVerError> for ApiError {
+    fn from(error: SemVerError) -> Self {
+        Self(StatusCode::UNPROCESSABLE_ENTITY, error.to_string())
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(ErrorBody { error: self.1 })).into_response()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Serves `router` on a free local port until the returned runtime is dropped.
+    fn spawn(router: Router) -> (tokio::runtime::Runtime, SocketAddr) {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        runtime.spawn(async move { axum::serve(listener, router).await });
+
+        (runtime, address)
+    }
+
+    /// Posts `body` to `path` with the `headers` and returns the status line and the body.
+    fn post(
+        address: SocketAddr,
+        path: &str,
+        headers: &[(&str, &str)],
+        body: &str,
+    ) -> (String, String) {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| format!("{}: {}\r\n", name, value))
+            .collect::<String>();
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path,
+            headers,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or_default();
+        (
+            head.lines().next().unwrap_or_default().to_string(),
+            body.to_string(),
+        )
+    }
+
+    /// Posts the json `request` to `path` and returns the status line and the json answered.
+    fn post_json(address: SocketAddr, path: &str, request: Value) -> (String, Value) {
+        let (status, body) = post(
+            address,
+            path,
+            &[("Content-Type", "application/json")],
+            &request.to_string(),
+        );
+
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[test]
+    fn test_parse_answers_the_comment_or_an_error() {
+        let (_runtime, address) = spawn(router(Config::default(), None));
+
+        let (status, comment) = post_json(
+            address,
+            "/parse",
+            json!({ "comment": "feat(api): export." }),
+        );
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(comment["scope"], "api");
+        assert_eq!(comment["comment"], "export.");
+
+        let (status, error) = post_json(address, "/parse", json!({ "comment": "wip" }));
+        assert_eq!(status, "HTTP/1.1 422 Unprocessable Entity");
+        assert!(error["error"]
+            .as_str()
+            .is_some_and(|error| !error.is_empty()));
+    }
+
+    #[test]
+    fn test_next_version_bumps_for_the_parsed_comments() {
+        let (_runtime, address) = spawn(router(Config::default(), None));
+
+        let (status, response) = post_json(
+            address,
+            "/next-version",
+            json!({
+                "current_version": "v1.2.3",
+                "comments": ["fix: a fix.", "feat: export.", "wip"],
+            }),
+        );
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(
+            response,
+            json!({ "version": "v1.3.0", "bump": "minor", "unparsed": ["wip"] })
+        );
+    }
+
+    #[test]
+    fn test_lint_answers_the_errors_and_warnings() {
+        let (_runtime, address) = spawn(router(Config::default(), None));
+
+        let (status, response) = post_json(address, "/lint", json!({ "message": "feat: export." }));
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert_eq!(
+            response,
+            json!({ "valid": true, "errors": [], "warnings": [] })
+        );
+
+        let (_, response) = post_json(address, "/lint", json!({ "message": "refact: simplify." }));
+        assert_eq!(response["valid"], true);
+        assert_eq!(response["warnings"].as_array().map(Vec::len), Some(1));
+
+        let (_, response) = post_json(address, "/lint", json!({ "message": "wip" }));
+        assert_eq!(response["valid"], false);
+        assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn test_github_webhook_rejects_missing_and_invalid_signatures() {
+        let github = GitHubHook {
+            secret: "secret".to_string(),
+            token: None,
+            api_url: "http://localhost".to_string(),
+        };
+        let (_runtime, address) = spawn(router(Config::default(), Some(github)));
+        let post_webhook = |signature: Option<&str>| {
+            let mut headers = vec![(GITHUB_EVENT_HEADER, "push")];
+            headers.extend(signature.map(|signature| (GITHUB_SIGNATURE_HEADER, signature)));
+            post(address, "/webhook/github", &headers, r#"{"commits": []}"#).0
+        };
+
+        assert_eq!(post_webhook(None), "HTTP/1.1 401 Unauthorized");
+        assert_eq!(post_webhook(Some("sha256=00")), "HTTP/1.1 401 Unauthorized");
+        assert_eq!(
+            post_webhook(Some("not-a-signature")),
+            "HTTP/1.1 401 Unauthorized"
+        );
+    }
+}
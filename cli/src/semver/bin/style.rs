@@ -0,0 +1,119 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use clap::ValueEnum;
+use core::{RefactorKeyword, SemanticComment, SemanticType};
+
+/// When to color the output, see [`Style::new`].
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Colors when stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+
+/// [`Style`] renders the human-friendly output: with colors when pretty, as the plain output,
+/// which scripts rely on, otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct Style {
+    pub pretty: bool,
+}
+
+impl Style {
+    /// [`new`] enables the colors as `choice` asks, `auto` leaving them out when stdout is not a
+    /// terminal or `NO_COLOR` is set to a non empty value.
+    pub fn new(choice: ColorChoice) -> Self {
+        let pretty = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && io::stdout().is_terminal()
+            }
+        };
+        Self { pretty }
+    }
+
+    fn paint(&self, codes: &str, text: &str) -> String {
+        match self.pretty {
+            true => format!("{}{}{}", codes, text, RESET),
+            false => text.to_string(),
+        }
+    }
+
+    /// Marker of a passed check, `ok  `.
+    pub fn ok(&self) -> String {
+        self.paint(GREEN, "ok  ")
+    }
+
+    /// Marker of a failed check, `FAIL`.
+    pub fn fail(&self) -> String {
+        self.paint(&format!("{}{}", BOLD, RED), "FAIL")
+    }
+
+    /// Marker of a warning, `WARN`.
+    pub fn warn(&self) -> String {
+        self.paint(YELLOW, "WARN")
+    }
+
+    /// Version, or tag, being released.
+    pub fn version(&self, version: &str) -> String {
+        self.paint(&format!("{}{}", BOLD, GREEN), version)
+    }
+
+    /// Diff-style change of version, `v1.2.3 → v1.3.0`, or the new version alone when plain or
+    /// without previous version.
+    pub fn version_change(&self, old: &str, new: &str) -> String {
+        match self.pretty && !old.is_empty() {
+            true => format!(
+                "{} → {}",
+                self.paint(&format!("{}{}", DIM, RED), old),
+                self.version(new)
+            ),
+            false => new.to_string(),
+        }
+    }
+
+    /// Semantic comment with a badge of its type, its scope and a highlighted breaking marker,
+    /// or its debug representation when plain.
+    pub fn comment(&self, comment: &SemanticComment, refactor_keyword: RefactorKeyword) -> String {
+        if !self.pretty {
+            return format!("{:?}", comment);
+        }
+        let keyword = comment.semantic_type.keyword(refactor_keyword);
+        let color = match comment.semantic_type {
+            SemanticType::Feature(_) => GREEN,
+            SemanticType::Fix(_) => BLUE,
+            SemanticType::Refactoring(_) => CYAN,
+            SemanticType::Security(_) => RED,
+            SemanticType::Revert(_, _) => MAGENTA,
+            SemanticType::Other(_, _) => DIM,
+        };
+        let mut line = self.paint(&format!("{}{}", BOLD, color), &format!("[{}]", keyword));
+        if let Some(scope) = &comment.scope {
+            line.push_str(&format!(" {}", self.paint(DIM, &format!("({})", scope))));
+        }
+        if comment.semantic_type.metadata().is_breaking {
+            line.push_str(&format!(
+                " {}",
+                self.paint(&format!("{}{}", BOLD, RED), "BREAKING")
+            ));
+        }
+        line.push_str(&format!(" {}", comment.comment));
+        line
+    }
+}
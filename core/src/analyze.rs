@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{cancel_reverts, BumpLevel, Commit, Config, ParseCache, SemVerError};
+
+/// [`Analysis`] reports on the commits of a revision range, see [`Config::analyze`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Analysis {
+    /// Number of commits walked, skipped and unparseable ones included.
+    pub commits: usize,
+    /// Number of parsed comments by type, e.g. `feat`.
+    pub types: BTreeMap<String, usize>,
+    /// Number of parsed comments by scope.
+    pub scopes: BTreeMap<String, usize>,
+    pub breaking: usize,
+    pub unparsed: Vec<Commit>,
+    /// Commits excluded by a skip marker, e.g. `[skip release]`.
+    pub skipped: Vec<Commit>,
+    /// Bump the range implies once released.
+    pub bump: BumpLevel,
+}
+
+impl Config {
+    /// [`analyze`] parses `commits` like a release would and counts them by type and scope,
+    /// along with the bump they imply on `branch`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let commits = vec![
+    ///     Commit::new("a1", "feat(api): new endpoint."),
+    ///     Commit::new("b2", "fix(api)! strict parsing."),
+    ///     Commit::new("c3", "wip"),
+    /// ];
+    ///
+    /// let analysis = Config::default().analyze(&commits, None).unwrap();
+    /// assert_eq!(analysis.types["feat"], 1);
+    /// assert_eq!(analysis.scopes["api"], 2);
+    /// assert_eq!(analysis.breaking, 1);
+    /// assert_eq!(analysis.unparsed.len(), 1);
+    /// assert_eq!(analysis.bump, BumpLevel::Major);
+    /// ```
+    pub fn analyze(
+        &self,
+        commits: &[Commit],
+        branch: Option<&str>,
+    ) -> Result<Analysis, SemVerError> {
+        self.analyze_cached(commits, branch, &mut ParseCache::default())
+    }
+
+    /// [`analyze_cached`] analyzes `commits` like [`Config::analyze`], parsing them with `cache`,
+    /// see [`Config::parse_commits_cached`].
+    pub fn analyze_cached(
+        &self,
+        commits: &[Commit],
+        branch: Option<&str>,
+        cache: &mut ParseCache,
+    ) -> Result<Analysis, SemVerError> {
+        let (skipped, kept): (Vec<_>, Vec<_>) = commits
+            .iter()
+            .cloned()
+            .partition(|commit| self.is_skipped(commit));
+        let (parsed, unparsed) = self.parse_commits_cached(&kept, cache);
+        let policy = self.bump_policy()?;
+
+        let mut analysis = Analysis {
+            commits: commits.len(),
+            unparsed: unparsed.into_iter().map(|(commit, _)| commit).collect(),
+            skipped,
+            ..Default::default()
+        };
+        for parsed in &parsed {
+            let comment = &parsed.comment;
+            let keyword = comment.semantic_type.keyword(self.refactor_keyword);
+
+            *analysis.types.entry(keyword.to_string()).or_default() += 1;
+            if let Some(scope) = &comment.scope {
+                *analysis.scopes.entry(scope.clone()).or_default() += 1;
+            }
+            if comment.semantic_type.metadata().is_breaking {
+                analysis.breaking += 1;
+            }
+        }
+        analysis.bump = policy.evaluate_commits(&cancel_reverts(parsed), branch);
+
+        Ok(analysis)
+    }
+}
+
+impl Analysis {
+    /// [`report`] renders the analysis as a plain text report.
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "commits: {} ({} unparseable, {} skipped)\nbreaking: {}\nbump: {}\n",
+            self.commits,
+            self.unparsed.len(),
+            self.skipped.len(),
+            self.breaking,
+            self.bump
+        );
+        for (title, counts) in [("types", &self.types), ("scopes", &self.scopes)] {
+            if !counts.is_empty() {
+                report.push_str(&format!("{}:\n", title));
+            }
+            for (name, count) in counts {
+                report.push_str(&format!("  {:<12} {}\n", name, count));
+            }
+        }
+        if !self.unparsed.is_empty() {
+            report.push_str("unparseable:\n");
+        }
+        for commit in &self.unparsed {
+            let sha = commit.sha.get(..7).unwrap_or(&commit.sha);
+            report.push_str(&format!("  {} {}\n", sha, commit.subject()));
+        }
+
+        report
+    }
+
+    /// [`as_json_string`] returns json representation of the analysis.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_analysis_report_lists_counts_and_unparseable_commits() {
+        let commits = vec![
+            Commit::new("aaaaaaaaaa", "feat(api): new endpoint."),
+            Commit::new("bbbbbbbbbb", "fix: a fix. [skip release]"),
+            Commit::new("cccccccccc", "wip"),
+        ];
+
+        let analysis = Config::default().analyze(&commits, None).unwrap();
+        assert_eq!(
+            analysis.report(),
+            "commits: 3 (1 unparseable, 1 skipped)\nbreaking: 0\nbump: minor\n\
+            types:\n  feat         1\nscopes:\n  api          1\nunparseable:\n  ccccccc wip\n"
+        );
+    }
+}
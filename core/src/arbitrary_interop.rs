@@ -0,0 +1,87 @@
+//! [`arbitrary::Arbitrary`] implementations for fuzzing, behind the `arbitrary` feature.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{SemanticComment, SemanticType, SemanticTypeMetadata, SemanticVersion};
+
+/// Characters of the prerelease and build identifiers accepted by the version grammar.
+const IDENTIFIER_CHARACTERS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-";
+
+/// Characters of the types and scopes rendered by [`SemanticComment::to_commit_string`].
+const WORD_CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+/// Generates a non empty word of `characters`.
+fn word(u: &mut Unstructured<'_>, characters: &[u8]) -> Result<String> {
+    let mut word = String::new();
+    for _ in 0..u.int_in_range(1..=12)? {
+        word.push(char::from(*u.choose(characters)?));
+    }
+    Ok(word)
+}
+
+/// Generates dot separated identifiers, e.g. `rc.1`, or `None`.
+fn identifiers(u: &mut Unstructured<'_>) -> Result<Option<String>> {
+    if !u.arbitrary::<bool>()? {
+        return Ok(None);
+    }
+
+    let mut identifiers = vec![];
+    for _ in 0..u.int_in_range(1..=4)? {
+        identifiers.push(word(u, IDENTIFIER_CHARACTERS)?);
+    }
+    Ok(Some(identifiers.join(".")))
+}
+
+/// Generates a type, reverting a comment without revert when `revert` is set.
+fn semantic_type(u: &mut Unstructured<'_>, revert: bool) -> Result<SemanticType> {
+    let metadata = SemanticTypeMetadata::new(u.arbitrary()?);
+    let last = match revert {
+        true => 5,
+        false => 4,
+    };
+
+    Ok(match u.int_in_range(0..=last)? {
+        0 => SemanticType::Feature(metadata),
+        1 => SemanticType::Fix(metadata),
+        2 => SemanticType::Refactoring(metadata),
+        3 => SemanticType::Security(metadata),
+        4 => SemanticType::Other(word(u, WORD_CHARACTERS)?, metadata),
+        _ => SemanticType::Revert(metadata, Box::new(comment(u, false)?)),
+    })
+}
+
+/// Generates a comment, a revert only when `revert` is set.
+fn comment(u: &mut Unstructured<'_>, revert: bool) -> Result<SemanticComment> {
+    let semantic_type = semantic_type(u, revert)?;
+    let description = match &semantic_type {
+        SemanticType::Revert(_, reverted) => reverted.to_commit_string(),
+        _ => u.arbitrary()?,
+    };
+    let comment = SemanticComment::new(description, semantic_type);
+
+    Ok(match u.arbitrary::<bool>()? {
+        true => comment.with_scope(word(u, WORD_CHARACTERS)?),
+        false => comment,
+    })
+}
+
+/// Generates versions rendering to a string the parser reads back.
+impl<'a> Arbitrary<'a> for SemanticVersion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SemanticVersion {
+            major: u.arbitrary()?,
+            minor: u.arbitrary()?,
+            patch: u.arbitrary()?,
+            pre: identifiers(u)?,
+            build: identifiers(u)?,
+        })
+    }
+}
+
+/// Generates comments of every type, scoped or not, reverts holding a comment of another type.
+impl<'a> Arbitrary<'a> for SemanticComment {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        comment(u, true)
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{history::parse_each, Commit, Config, ParsedCommit, SemVerError, SemanticComment};
+
+/// Cache of the parsed commits, written at the root of the repository.
+pub const CACHE_FILE_NAME: &str = ".semver-cache.json";
+
+/// [`ParseCache`] holds the comments parsed from every commit by sha, so that repeated runs only
+/// parse the new commits. It is tied to the configuration it was filled with: loading it with
+/// another one starts over.
+///
+/// Commits which do not parse are not cached, their error is computed again.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::default();
+/// let commits = vec![Commit::new("a1", "feat: export."), Commit::new("b2", "wip")];
+///
+/// let mut cache = ParseCache::new(&config);
+/// let (parsed, unparsed) = config.parse_commits_cached(&commits, &mut cache);
+/// assert_eq!((parsed.len(), unparsed.len()), (1, 1));
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParseCache {
+    fingerprint: String,
+    comments: BTreeMap<String, Vec<SemanticComment>>,
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl ParseCache {
+    /// [`new`] returns an empty cache for `config`.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            fingerprint: config.fingerprint(),
+            ..Default::default()
+        }
+    }
+
+    /// [`load`] reads the cache at `path`, starting over when it is missing, unreadable or was
+    /// filled with another configuration.
+    pub fn load(path: impl AsRef<Path>, config: &Config) -> Self {
+        let cache = fs::read_to_string(path.as_ref())
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Self>(&contents).ok());
+
+        match cache {
+            Some(cache) if cache.fingerprint == config.fingerprint() => {
+                debug!(path = %path.as_ref().display(), commits = cache.len(), "loaded parse cache");
+                cache
+            }
+            _ => {
+                debug!(path = %path.as_ref().display(), "starting a new parse cache");
+                Self::new(config)
+            }
+        }
+    }
+
+    /// [`save`] writes the cache to `path` when commits were added since it was loaded.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SemVerError> {
+        if !self.changed {
+            return Ok(());
+        }
+        Ok(fs::write(path, serde_json::to_string(self)?)?)
+    }
+
+    /// Number of cached commits.
+    pub fn len(&self) -> usize {
+        self.comments.len()
+    }
+
+    /// Whether no commit is cached.
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+    }
+}
+
+impl Config {
+    /// [`parse_commits_cached`] parses `commits` like [`Config::parse_commits`], taking the
+    /// comments of the commits found in `cache` and adding the others to it.
+    pub fn parse_commits_cached(
+        &self,
+        commits: &[Commit],
+        cache: &mut ParseCache,
+    ) -> (Vec<ParsedCommit>, Vec<(Commit, SemVerError)>) {
+        let parser = self.comment_parser();
+        let (parsed, unparsed) = parse_each(self.kept_commits(commits), |commit| {
+            match cache.comments.get(&commit.sha) {
+                Some(comments) => Ok(comments
+                    .iter()
+                    .map(|comment| ParsedCommit {
+                        commit: commit.clone(),
+                        comment: comment.clone(),
+                    })
+                    .collect()),
+                None => self.parse_kept_commit(commit, &parser),
+            }
+        });
+
+        let mut added = BTreeMap::<String, Vec<SemanticComment>>::new();
+        for parsed in &parsed {
+            if !cache.comments.contains_key(&parsed.commit.sha) {
+                added
+                    .entry(parsed.commit.sha.clone())
+                    .or_default()
+                    .push(parsed.comment.clone());
+            }
+        }
+        cache.changed |= !added.is_empty();
+        cache.comments.extend(added);
+
+        (parsed, unparsed)
+    }
+
+    /// Hash of the configuration and of the version of the parser, tying a cache to them.
+    fn fingerprint(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        format!("{:?}", self).hash(&mut hasher);
+
+        format!("{:016x}", hasher.finish())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cache_is_reused_until_the_configuration_changes() {
+        let path = std::env::temp_dir().join(format!("semver-cache-{}.json", std::process::id()));
+        let config = Config::default();
+        let commits = vec![
+            Commit::new("a1", "feat: export."),
+            Commit::new("b2", "fix: crash."),
+        ];
+
+        let mut cache = ParseCache::load(&path, &config);
+        config.parse_commits_cached(&commits[..1], &mut cache);
+        cache.save(&path).unwrap();
+
+        let mut cache = ParseCache::load(&path, &config);
+        assert_eq!(cache.len(), 1);
+        let (parsed, _) = config.parse_commits_cached(&commits, &mut cache);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(cache.len(), 2);
+
+        let config = Config::from_toml_str("types = [\"feat\"]\n").unwrap();
+        assert!(ParseCache::load(&path, &config).is_empty());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
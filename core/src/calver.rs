@@ -0,0 +1,143 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::{civil_from_days, BumpLevel, Config, SemVerError, SemanticVersion};
+
+/// Format of the calendar versions unless configured, see [`Config::calver_format`].
+pub const DEFAULT_CALVER_FORMAT: &str = "YYYY.MM.MICRO";
+
+/// [`Scheme`] tells how the next version is computed from the commits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    /// The commits bump the major, minor or patch component.
+    #[default]
+    SemVer,
+    /// The version is the release date followed by a micro counter, e.g. `v2024.6.2`:
+    /// any releasable commit increments the counter, which resets on a new period.
+    CalVer,
+}
+
+/// [`calver_version`] returns the calendar version following `previous` (`None` for the first
+/// release) when released in `year` and `month`, in `format`: `YYYY` or `YY`, then `MM`, then
+/// `MICRO`, separated by dots.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let previous = SemanticVersion::new(2024, 6, 1);
+///
+/// assert_eq!(
+///     calver_version("YYYY.MM.MICRO", Some(&previous), (2024, 6)).unwrap(),
+///     SemanticVersion::new(2024, 6, 2)
+/// );
+/// assert_eq!(
+///     calver_version("YY.MM.MICRO", Some(&previous), (2024, 7)).unwrap(),
+///     SemanticVersion::new(24, 7, 0)
+/// );
+/// ```
+pub fn calver_version(
+    format: &str,
+    previous: Option<&SemanticVersion>,
+    (year, month): (i64, u32),
+) -> Result<SemanticVersion, SemVerError> {
+    let year = match format.split('.').collect::<Vec<_>>()[..] {
+        ["YYYY", "MM", "MICRO"] => year as u64,
+        ["YY", "MM", "MICRO"] => (year % 100) as u64,
+        _ => {
+            return Err(SemVerError::InvalidConfig(format!(
+                "unsupported calver format `{}`, expected YYYY.MM.MICRO or YY.MM.MICRO",
+                format
+            )))
+        }
+    };
+
+    let month = u64::from(month);
+    let micro = match previous {
+        Some(previous) if previous.major == year && previous.minor == month => previous
+            .patch
+            .checked_add(1)
+            .ok_or_else(|| SemVerError::VersionOverflow(String::from(previous.clone())))?,
+        _ => 0,
+    };
+
+    Ok(SemanticVersion::new(year, month, micro))
+}
+
+/// Returns the current year and month (UTC).
+fn current_month() -> (i64, u32) {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (year, month, _) = civil_from_days((seconds / 86_400) as i64);
+
+    (year, month)
+}
+
+impl Config {
+    /// [`calver_format`] returns the configured calendar version format, or the default one.
+    pub fn calver_format(&self) -> &str {
+        self.calver_format
+            .as_deref()
+            .unwrap_or(DEFAULT_CALVER_FORMAT)
+    }
+
+    /// [`next_version`] returns the version released after `previous` for `bump`, following
+    /// the configured [`Scheme`]. Nothing is released without bump.
+    pub fn next_version(
+        &self,
+        previous: &SemanticVersion,
+        bump: BumpLevel,
+    ) -> Result<SemanticVersion, SemVerError> {
+        self.next_version_in(previous, bump, current_month())
+    }
+
+    /// [`next_version_in`] returns the version released after `previous` for `bump` when
+    /// released in `(year, month)`, for the callers without system clock, e.g. WebAssembly.
+    pub fn next_version_in(
+        &self,
+        previous: &SemanticVersion,
+        bump: BumpLevel,
+        month: (i64, u32),
+    ) -> Result<SemanticVersion, SemVerError> {
+        if bump == BumpLevel::None {
+            return Ok(previous.clone());
+        }
+
+        match self.scheme {
+            Scheme::SemVer => {
+                let mut version = previous.clone();
+                version.apply_bump(bump)?;
+                Ok(version)
+            }
+            Scheme::CalVer => calver_version(self.calver_format(), Some(previous), month),
+        }
+    }
+
+    /// [`first_version`] returns the version of the first release: the initial version, or
+    /// the current period with the calendar scheme.
+    pub fn first_version(&self) -> Result<SemanticVersion, SemVerError> {
+        match self.scheme {
+            Scheme::SemVer => Ok(self.initial_version()),
+            Scheme::CalVer => calver_version(self.calver_format(), None, current_month()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_calver_version_resets_the_micro_counter_on_a_new_period() {
+        let previous = SemanticVersion::new(2024, 12, 4);
+
+        assert_eq!(
+            calver_version(DEFAULT_CALVER_FORMAT, Some(&previous), (2025, 1)).unwrap(),
+            SemanticVersion::new(2025, 1, 0)
+        );
+        assert!(calver_version("YYYY.MICRO", None, (2025, 1)).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::fmt;
+
+use crate::{SemVerError, SemanticVersion};
+
+/// [`CanonicalizeOptions`] selects the fixes [`SemanticVersion::canonicalize`] may apply; an
+/// input needing a disabled one is rejected. Every fix is enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalizeOptions {
+    /// Drops the text before the first digit, e.g. `release-` or `V`, `v` being canonical.
+    pub strip_prefix: bool,
+    /// Completes the missing minor and patch components with zeros, e.g. `1.2` as `1.2.0`.
+    pub pad_components: bool,
+    /// Moves the components past the patch to the build metadata, e.g. `1.2.3.4` as
+    /// `1.2.3+4`, preserving them without affecting precedence.
+    pub fold_extra_components: bool,
+}
+
+impl Default for CanonicalizeOptions {
+    fn default() -> Self {
+        CanonicalizeOptions {
+            strip_prefix: true,
+            pad_components: true,
+            fold_extra_components: true,
+        }
+    }
+}
+
+/// [`VersionFix`] is a change [`SemanticVersion::canonicalize`] made to its input.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionFix {
+    /// The text before the version was dropped.
+    StrippedPrefix(String),
+    /// Leading zeros were dropped from the numeric components, e.g. `02` as `2`.
+    StrippedLeadingZeros,
+    /// Zero components were appended to the given number of components.
+    PaddedComponents(usize),
+    /// The components past the patch were moved to the build metadata.
+    FoldedComponents(String),
+}
+
+impl fmt::Display for VersionFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionFix::StrippedPrefix(prefix) => write!(f, "stripped the prefix `{}`", prefix),
+            VersionFix::StrippedLeadingZeros => write!(f, "stripped leading zeros"),
+            VersionFix::PaddedComponents(count) => {
+                write!(f, "padded {} component(s) with zeros", count)
+            }
+            VersionFix::FoldedComponents(extra) => {
+                write!(
+                    f,
+                    "moved the extra components `{}` to the build metadata",
+                    extra
+                )
+            }
+        }
+    }
+}
+
+/// [`Canonicalized`] is the result of [`SemanticVersion::canonicalize`]: the version along
+/// with the fixes it took, empty when the input was canonical.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canonicalized {
+    pub version: SemanticVersion,
+    pub fixes: Vec<VersionFix>,
+}
+
+impl SemanticVersion {
+    /// [`canonicalize`] reads the messy versions of legacy tag sets, e.g. `V1.02.3`,
+    /// `release-1.2` or `1.2.3.4`, and reports what it fixed to read them as
+    /// `v<major>.<minor>.<patch>[-<pre>][+<build>]`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let canonical = SemanticVersion::canonicalize("release-1.02", CanonicalizeOptions::default()).unwrap();
+    ///
+    /// assert_eq!(String::from(canonical.version), "v1.2.0");
+    /// assert_eq!(
+    ///     canonical.fixes,
+    ///     vec![
+    ///         VersionFix::StrippedPrefix("release-".to_string()),
+    ///         VersionFix::StrippedLeadingZeros,
+    ///         VersionFix::PaddedComponents(2),
+    ///     ]
+    /// );
+    ///
+    /// let strict = CanonicalizeOptions { strip_prefix: false, ..CanonicalizeOptions::default() };
+    /// assert!(SemanticVersion::canonicalize("release-1.2.0", strict).is_err());
+    /// ```
+    pub fn canonicalize(
+        input: &str,
+        options: CanonicalizeOptions,
+    ) -> Result<Canonicalized, SemVerError> {
+        let invalid = || SemVerError::InvalidVersionFormat(input.to_string());
+        let mut fixes = vec![];
+
+        let start = input
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (prefix, version) = input.split_at(start);
+        if !prefix.is_empty() && prefix != "v" {
+            if !options.strip_prefix {
+                return Err(invalid());
+            }
+            fixes.push(VersionFix::StrippedPrefix(prefix.to_string()));
+        }
+
+        let (version, mut build) = match version.split_once('+') {
+            Some((version, build)) => (version, Some(build.to_string())),
+            None => (version, None),
+        };
+        let (version, pre) = match version.split_once('-') {
+            Some((version, pre)) => (version, Some(pre.to_string())),
+            None => (version, None),
+        };
+
+        let mut numbers = vec![];
+        for number in version.split('.') {
+            if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let zero_padded = number.len() > 1 && number.starts_with('0') && numbers.len() < 3;
+            if zero_padded && !fixes.contains(&VersionFix::StrippedLeadingZeros) {
+                fixes.push(VersionFix::StrippedLeadingZeros);
+            }
+            numbers.push(number);
+        }
+
+        if numbers.len() < 3 {
+            if !options.pad_components {
+                return Err(invalid());
+            }
+            fixes.push(VersionFix::PaddedComponents(numbers.len()));
+        }
+        if let Some(extra) = numbers.get(3..).filter(|extra| !extra.is_empty()) {
+            if !options.fold_extra_components {
+                return Err(invalid());
+            }
+            let extra = extra.join(".");
+            fixes.push(VersionFix::FoldedComponents(extra.clone()));
+            build = Some(match build {
+                Some(build) => format!("{}.{}", extra, build),
+                None => extra,
+            });
+        }
+
+        let component = |index: usize| -> Result<u64, SemVerError> {
+            match numbers.get(index) {
+                Some(number) => number.parse().map_err(|_| invalid()),
+                None => Ok(0),
+            }
+        };
+        let identifiers = |value: &Option<String>| {
+            value
+                .iter()
+                .flat_map(|value| value.split('.'))
+                .all(|identifier| {
+                    !identifier.is_empty()
+                        && identifier
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+        };
+        if !identifiers(&pre) || !identifiers(&build) {
+            return Err(invalid());
+        }
+
+        Ok(Canonicalized {
+            version: SemanticVersion {
+                major: component(0)?,
+                minor: component(1)?,
+                patch: component(2)?,
+                pre,
+                build,
+            },
+            fixes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_reports_the_fixes_of_legacy_tags() {
+        let cases = [
+            ("v1.2.3", "v1.2.3", vec![]),
+            (
+                "V1.02.3",
+                "v1.2.3",
+                vec![
+                    VersionFix::StrippedPrefix("V".to_string()),
+                    VersionFix::StrippedLeadingZeros,
+                ],
+            ),
+            (
+                "1.2.3.4-rc.1+sha.abc",
+                "v1.2.3-rc.1+4.sha.abc",
+                vec![VersionFix::FoldedComponents("4".to_string())],
+            ),
+            ("v7", "v7.0.0", vec![VersionFix::PaddedComponents(1)]),
+        ];
+
+        for (input, expected, fixes) in cases {
+            let canonical =
+                SemanticVersion::canonicalize(input, CanonicalizeOptions::default()).unwrap();
+            assert_eq!(String::from(canonical.version), expected, "{}", input);
+            assert_eq!(canonical.fixes, fixes, "{}", input);
+        }
+        for invalid in [
+            "",
+            "release",
+            "1..2",
+            "1.2.x",
+            "1.2.3-",
+            "1.2.3-rc_1",
+            "99999999999999999999",
+        ] {
+            assert!(
+                SemanticVersion::canonicalize(invalid, CanonicalizeOptions::default()).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,683 @@
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    collect_contributors, Contributor, Forge, ParsedCommit, Reference, RepositoryLinks,
+    SemanticType, SemanticVersion,
+};
+
+/// Title of the changelog document.
+pub const CHANGELOG_HEADER: &str = "# Changelog";
+
+/// [`render_release`] renders the changelog section of a release in Markdown.
+///
+/// A `⚠ Breaking Changes` section comes first, listing the `BREAKING CHANGE` footers of the
+/// breaking commits, e.g. their migration notes, or their description without one. Entries are
+/// then grouped by type; types that do not affect the version (e.g. `docs`) are left out. The
+/// `Deprecated` footers of every commit follow, see [`crate::SemanticComment::deprecations`].
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let commits = vec![ParsedCommit {
+///     commit: Commit::new("0123456789", "feat(api): new endpoint."),
+///     comment: "feat(api): new endpoint.".try_into().unwrap(),
+/// }];
+/// let version = SemanticVersion::new(1, 3, 0);
+///
+/// assert_eq!(
+///     render_release(&version, "2024-06-01", &commits),
+///     "## v1.3.0 (2024-06-01)\n\n### Features\n\n- **api:** new endpoint. (0123456)\n"
+/// );
+/// ```
+pub fn render_release(version: &SemanticVersion, date: &str, commits: &[ParsedCommit]) -> String {
+    render_release_with_links(version, date, commits, None)
+}
+
+/// [`render_release_with_links`] renders the section like [`render_release`], linking the short shas
+/// and the references of the commits to their page on `forge`.
+pub fn render_release_with_links(
+    version: &SemanticVersion,
+    date: &str,
+    commits: &[ParsedCommit],
+    forge: Option<&dyn Forge>,
+) -> String {
+    let heading = format!("## {} ({})\n", String::from(version.clone()), date);
+
+    render_section(heading, commits, forge)
+}
+
+/// [`render_linked_release`] renders the section like [`render_release`], linking the short shas
+/// and the references of the commits to the pages of the repository, and the version to the
+/// comparison of the `(previous, new)` tags when given.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let message = "fix: crash.\n\nCloses #12";
+/// let commits = vec![ParsedCommit {
+///     commit: Commit::new("0123456789", message),
+///     comment: CommentParser::default().parse_message(message).unwrap(),
+/// }];
+/// let links = RepositoryLinks::new(HostKind::GitHub, "https://github.com/team/app");
+/// let version = SemanticVersion::new(1, 2, 1);
+///
+/// let section = render_linked_release(&version, "2024-06-01", &commits, &links, Some(("v1.2.0", "v1.2.1")));
+/// assert!(section.starts_with("## [v1.2.1](https://github.com/team/app/compare/v1.2.0...v1.2.1) (2024-06-01)\n"));
+/// assert!(section.ends_with(
+///     "- crash. ([#12](https://github.com/team/app/issues/12)) ([0123456](https://github.com/team/app/commit/0123456789))\n"
+/// ));
+/// ```
+pub fn render_linked_release(
+    version: &SemanticVersion,
+    date: &str,
+    commits: &[ParsedCommit],
+    links: &RepositoryLinks,
+    tags: Option<(&str, &str)>,
+) -> String {
+    let heading = render_heading(
+        &String::from(version.clone()),
+        Some(date),
+        Some(links),
+        tags,
+    );
+
+    render_section(heading, commits, Some(links))
+}
+
+/// Urls the entries of a section are linked to.
+pub(crate) trait Links {
+    fn commit_url(&self, sha: &str) -> String;
+    fn reference_url(&self, reference: &Reference) -> Option<String>;
+}
+
+impl Links for dyn Forge + '_ {
+    fn commit_url(&self, sha: &str) -> String {
+        Forge::commit_url(self, sha)
+    }
+
+    fn reference_url(&self, reference: &Reference) -> Option<String> {
+        Forge::reference_url(self, reference)
+    }
+}
+
+impl Links for RepositoryLinks {
+    fn commit_url(&self, sha: &str) -> String {
+        RepositoryLinks::commit_url(self, sha)
+    }
+
+    fn reference_url(&self, reference: &Reference) -> Option<String> {
+        RepositoryLinks::reference_url(self, reference)
+    }
+}
+
+/// Renders the entries of `commits` below the `section` heading.
+pub(crate) fn render_section<L: Links + ?Sized>(
+    mut section: String,
+    commits: &[ParsedCommit],
+    links: Option<&L>,
+) -> String {
+    let commits = commits.iter().collect::<Vec<_>>();
+    section.push_str(&render_breaking_changes(&commits, links));
+    section.push_str(&render_group(&commits, links, "###", None));
+
+    section
+}
+
+/// Renders the `⚠ Breaking Changes` section, one entry per `BREAKING CHANGE` footer, the lines
+/// of the footer indented under its entry.
+fn render_breaking_changes<L: Links + ?Sized>(
+    commits: &[&ParsedCommit],
+    links: Option<&L>,
+) -> String {
+    let entries = commits
+        .iter()
+        .filter(|parsed| parsed.comment.semantic_type.metadata().is_breaking)
+        .flat_map(|parsed| {
+            let scope = render_scope(parsed, None);
+            let sha = render_sha(parsed, links);
+            let notes = match parsed.comment.breaking_changes.is_empty() {
+                true => vec![parsed.comment.comment.as_str()],
+                false => parsed
+                    .comment
+                    .breaking_changes
+                    .iter()
+                    .map(String::as_str)
+                    .collect(),
+            };
+            notes
+                .into_iter()
+                .map(move |note| format!("- {}{} ({})\n", scope, note.replace('\n', "\n  "), sha))
+        })
+        .collect::<Vec<_>>();
+
+    match entries.is_empty() {
+        true => String::new(),
+        false => format!("\n### {}\n\n{}", BREAKING_CHANGES_TITLE, entries.concat()),
+    }
+}
+
+/// Renders the type sections of `commits` and their deprecations with `level` headings, leaving
+/// out the scope of the entries when it is the `group` they are listed in.
+fn render_group<L: Links + ?Sized>(
+    commits: &[&ParsedCommit],
+    links: Option<&L>,
+    level: &str,
+    group: Option<&str>,
+) -> String {
+    let mut rendered = String::new();
+    for (title, belongs) in SECTIONS {
+        let entries = commits
+            .iter()
+            .filter(|parsed| belongs(&parsed.comment.semantic_type))
+            .map(|parsed| render_entry(parsed, links, group))
+            .collect::<Vec<_>>();
+
+        if !entries.is_empty() {
+            rendered.push_str(&format!("\n{} {}\n\n{}", level, title, entries.concat()));
+        }
+    }
+
+    let deprecations = commits
+        .iter()
+        .flat_map(|parsed| {
+            let scope = render_scope(parsed, group);
+            parsed
+                .comment
+                .deprecations()
+                .into_iter()
+                .map(move |deprecation| format!("- {}{}\n", scope, deprecation))
+        })
+        .collect::<Vec<_>>();
+    if !deprecations.is_empty() {
+        rendered.push_str(&format!(
+            "\n{} Deprecations\n\n{}",
+            level,
+            deprecations.concat()
+        ));
+    }
+
+    rendered
+}
+
+/// Name of the group of the entries without scope or package.
+pub const OTHER_GROUP: &str = "Other";
+
+/// [`ChangelogGroup`] is a named group of entries of a release, e.g. a scope and its commits.
+pub type ChangelogGroup = (String, Vec<ParsedCommit>);
+
+/// [`ChangelogLayout`] tells how the entries of a release are grouped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogLayout {
+    /// One section per type, e.g. `Features`.
+    #[default]
+    Type,
+    /// One section per scope, then per type, see [`group_by_scope`].
+    Scope,
+    /// One section per package of the `[packages]` section, then per type.
+    Package,
+}
+
+/// [`group_by_scope`] groups `commits` by scope, in alphabetical order, the unscoped ones last
+/// under [`OTHER_GROUP`].
+pub fn group_by_scope(commits: &[ParsedCommit]) -> Vec<ChangelogGroup> {
+    let mut groups = BTreeMap::<&str, Vec<ParsedCommit>>::new();
+    let mut other = vec![];
+    for parsed in commits {
+        match &parsed.comment.scope {
+            Some(scope) => groups.entry(scope).or_default().push(parsed.clone()),
+            None => other.push(parsed.clone()),
+        }
+    }
+
+    let mut groups = groups
+        .into_iter()
+        .map(|(scope, commits)| (scope.to_string(), commits))
+        .collect::<Vec<_>>();
+    if !other.is_empty() {
+        groups.push((OTHER_GROUP.to_string(), other));
+    }
+
+    groups
+}
+
+/// [`render_grouped_release`] renders the section like [`render_linked_release`], with one
+/// section per group of `groups`, e.g. per scope or package, then per type. The scope of the
+/// entries is left out when it names their group.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let parsed = |sha: &str, message: &str| ParsedCommit {
+///     commit: Commit::new(sha, message),
+///     comment: message.try_into().unwrap(),
+/// };
+/// let commits = vec![parsed("a1", "feat(web): dark mode."), parsed("b2", "fix(api): escape ids."), parsed("c3", "fix: typo.")];
+/// let version = SemanticVersion::new(1, 3, 0);
+///
+/// assert_eq!(
+///     render_grouped_release(&version, "2024-06-01", &group_by_scope(&commits), None, None),
+///     [
+///         "## v1.3.0 (2024-06-01)\n",
+///         "\n### api\n\n#### Bug Fixes\n\n- escape ids. (b2)\n",
+///         "\n### web\n\n#### Features\n\n- dark mode. (a1)\n",
+///         "\n### Other\n\n#### Bug Fixes\n\n- typo. (c3)\n",
+///     ]
+///     .concat()
+/// );
+/// ```
+pub fn render_grouped_release(
+    version: &SemanticVersion,
+    date: &str,
+    groups: &[ChangelogGroup],
+    links: Option<&RepositoryLinks>,
+    tags: Option<(&str, &str)>,
+) -> String {
+    let heading = render_heading(&String::from(version.clone()), Some(date), links, tags);
+
+    render_grouped_section(heading, groups, links)
+}
+
+/// Renders the entries of `groups` below the `section` heading, see [`render_grouped_release`].
+pub(crate) fn render_grouped_section(
+    mut section: String,
+    groups: &[ChangelogGroup],
+    links: Option<&RepositoryLinks>,
+) -> String {
+    // a commit of several packages is listed once among the breaking changes, every comment
+    // of a squash merge sharing its sha
+    let mut breaking: Vec<&ParsedCommit> = vec![];
+    for parsed in groups.iter().flat_map(|(_, commits)| commits) {
+        if !breaking
+            .iter()
+            .any(|b| b.commit.sha == parsed.commit.sha && b.comment == parsed.comment)
+        {
+            breaking.push(parsed);
+        }
+    }
+    section.push_str(&render_breaking_changes(&breaking, links));
+
+    for (group, commits) in groups {
+        let commits = commits.iter().collect::<Vec<_>>();
+        let rendered = render_group(&commits, links, "####", Some(group));
+
+        if !rendered.is_empty() {
+            section.push_str(&format!("\n### {}\n{}", group, rendered));
+        }
+    }
+
+    section
+}
+
+/// Renders the heading of a section, e.g. `## [v1.2.1](compare url) (2024-06-01)`, `title`
+/// linked to the comparison of the `(previous, new)` tags when given.
+pub(crate) fn render_heading(
+    title: &str,
+    date: Option<&str>,
+    links: Option<&RepositoryLinks>,
+    tags: Option<(&str, &str)>,
+) -> String {
+    let title = match links.zip(tags) {
+        Some((links, (previous, tag))) => {
+            format!("[{}]({})", title, links.compare_url(previous, tag))
+        }
+        None => title.to_string(),
+    };
+
+    match date {
+        Some(date) => format!("## {} ({})\n", title, date),
+        None => format!("## {}\n", title),
+    }
+}
+
+fn render_sha<L: Links + ?Sized>(parsed: &ParsedCommit, links: Option<&L>) -> String {
+    let short_sha = parsed.commit.sha.get(..7).unwrap_or(&parsed.commit.sha);
+
+    match links {
+        Some(links) => format!("[{}]({})", short_sha, links.commit_url(&parsed.commit.sha)),
+        None => short_sha.to_string(),
+    }
+}
+
+fn render_scope(parsed: &ParsedCommit, group: Option<&str>) -> String {
+    match parsed.comment.scope.as_deref() {
+        Some(scope) if Some(scope) != group => format!("**{}:** ", scope),
+        _ => String::new(),
+    }
+}
+
+/// [`render_contributors`] renders the Contributors section of a release: the names of the
+/// authors and `Co-authored-by` trailers of the commits, without duplicates, in order of
+/// appearance. It is empty when no commit has an author.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let message = "feat: pairing.\n\nCo-authored-by: Jane Doe <jane@example.com>";
+/// let commits = vec![ParsedCommit {
+///     commit: Commit::new("0123456789", message).with_author("Joe <joe@example.com>"),
+///     comment: CommentParser::default().parse_message(message).unwrap(),
+/// }];
+///
+/// assert_eq!(render_contributors(&commits), "\n### Contributors\n\n- Joe\n- Jane Doe\n");
+/// ```
+pub fn render_contributors(commits: &[ParsedCommit]) -> String {
+    render_contributor_list(&collect_contributors(commits))
+}
+
+/// [`render_contributor_list`] renders the Contributors section of `contributors`, with their
+/// handle when known, e.g. `- Mona (@octocat)`. It is empty when there is none.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let contributors = vec![Contributor::parse("Mona <1+octocat@users.noreply.github.com>", None)];
+///
+/// assert_eq!(render_contributor_list(&contributors), "\n### Contributors\n\n- Mona (@octocat)\n");
+/// ```
+pub fn render_contributor_list(contributors: &[Contributor]) -> String {
+    if contributors.is_empty() {
+        return String::new();
+    }
+
+    let lines = contributors
+        .iter()
+        .map(|contributor| match &contributor.handle {
+            Some(handle) => format!("- {} (@{})\n", contributor.name, handle),
+            None => format!("- {}\n", contributor.name),
+        })
+        .collect::<String>();
+
+    format!("\n### Contributors\n\n{}", lines)
+}
+
+/// Title of the section of the commits since the latest release, see
+/// [`crate::render_unreleased`].
+pub const UNRELEASED_TITLE: &str = "Unreleased";
+
+/// [`insert_release`] inserts a release section at the top of an existing changelog,
+/// right below its title, creating the title when the changelog is empty.
+///
+/// The `Unreleased` section opening the changelog is replaced, so that a release converts it
+/// into its version and a new `Unreleased` section refreshes it.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let changelog = "# Changelog\n\n## Unreleased\n\n- a fix.\n\n## v1.0.0 (2024-05-01)\n";
+///
+/// assert_eq!(
+///     insert_release(changelog, "## v1.0.1 (2024-06-01)\n\n- a fix.\n"),
+///     "# Changelog\n\n## v1.0.1 (2024-06-01)\n\n- a fix.\n\n## v1.0.0 (2024-05-01)\n"
+/// );
+/// ```
+pub fn insert_release(changelog: &str, section: &str) -> String {
+    let body = changelog
+        .trim_start()
+        .strip_prefix(CHANGELOG_HEADER)
+        .unwrap_or(changelog)
+        .trim();
+    let body = strip_unreleased(body);
+
+    match body.is_empty() {
+        true => format!("{}\n\n{}", CHANGELOG_HEADER, section),
+        false => format!("{}\n\n{}\n{}\n", CHANGELOG_HEADER, section, body),
+    }
+}
+
+/// Drops the `## Unreleased` (or `## [Unreleased](...)`) section opening `body`.
+fn strip_unreleased(body: &str) -> &str {
+    let Some(title) = body.strip_prefix("## ") else {
+        return body;
+    };
+    let title = title.strip_prefix('[').unwrap_or(title);
+    if !title.starts_with(UNRELEASED_TITLE) {
+        return body;
+    }
+
+    match body.find("\n## ") {
+        Some(end) => body[end..].trim_start(),
+        None => "",
+    }
+}
+
+/// Returns today's date as `YYYY-MM-DD` (UTC).
+pub fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+type SectionFilter = fn(&SemanticType) -> bool;
+
+/// Title of the section of the breaking changes, first of a release.
+pub const BREAKING_CHANGES_TITLE: &str = "⚠ Breaking Changes";
+
+/// Changelog sections in rendering order.
+const SECTIONS: [(&str, SectionFilter); 5] = [
+    ("Security", |t| matches!(t, SemanticType::Security(_))),
+    ("Features", |t| matches!(t, SemanticType::Feature(_))),
+    ("Bug Fixes", |t| matches!(t, SemanticType::Fix(_))),
+    ("Refactorings", |t| {
+        matches!(t, SemanticType::Refactoring(_))
+    }),
+    ("Reverts", |t| matches!(t, SemanticType::Revert(_, _))),
+];
+
+fn render_entry<L: Links + ?Sized>(
+    parsed: &ParsedCommit,
+    links: Option<&L>,
+    group: Option<&str>,
+) -> String {
+    let scope = render_scope(parsed, group);
+    let breaking = match parsed.comment.semantic_type.metadata().is_breaking {
+        true => "**BREAKING** ",
+        false => "",
+    };
+    let sha = render_sha(parsed, links);
+    // references already mentioned by the description are not repeated
+    let references = parsed
+        .comment
+        .references
+        .iter()
+        .filter(|reference| !parsed.comment.comment.contains(&reference.to_string()))
+        .map(
+            |reference| match links.and_then(|links| links.reference_url(reference)) {
+                Some(url) => format!("[{}]({})", reference, url),
+                None => reference.to_string(),
+            },
+        )
+        .collect::<Vec<_>>();
+    let references = match references.is_empty() {
+        true => String::new(),
+        false => format!(" ({})", references.join(", ")),
+    };
+
+    format!(
+        "- {}{}{}{} ({})\n",
+        breaking, scope, parsed.comment.comment, references, sha
+    )
+}
+
+/// Converts days since the unix epoch to a `(year, month, day)` civil date.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CommentParser, Commit};
+
+    use super::*;
+
+    fn parsed(sha: &str, message: &str) -> ParsedCommit {
+        ParsedCommit {
+            commit: Commit::new(sha, message),
+            comment: message.try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_render_release_groups_entries_by_type() {
+        let commits = vec![
+            parsed("aaaaaaaaaa", "fix: a fix."),
+            parsed("bbbbbbbbbb", "feat! a breaking feature."),
+            parsed("cccccccccc", "refactor(core): a refactor."),
+            parsed("dddddddddd", "feat: a feature."),
+        ];
+        let version = SemanticVersion::new(2, 0, 0);
+
+        assert_eq!(
+            render_release(&version, "2024-06-01", &commits),
+            "## v2.0.0 (2024-06-01)\n\
+            \n### ⚠ Breaking Changes\n\n- a breaking feature. (bbbbbbb)\n\
+            \n### Features\n\n- **BREAKING** a breaking feature. (bbbbbbb)\n- a feature. (ddddddd)\n\
+            \n### Bug Fixes\n\n- a fix. (aaaaaaa)\n\
+            \n### Refactorings\n\n- **core:** a refactor. (ccccccc)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_release_lists_breaking_change_footers_first() {
+        let parser = CommentParser::builder()
+            .types(["feat", "fix", "docs"])
+            .build();
+        let parsed = |sha: &str, message: &str| ParsedCommit {
+            commit: Commit::new(sha, message),
+            comment: parser.parse_message(message).unwrap(),
+        };
+        let commits = vec![
+            parsed("aaaaaaaaaa", "fix: a fix."),
+            parsed(
+                "bbbbbbbbbb",
+                "feat(config): v2 format.\n\nBREAKING CHANGE: `files` is renamed `updaters`.\nRun `semver migrate` to update it.\nReviewed-by: Joe",
+            ),
+            parsed("cccccccccc", "docs! drop the v1 guide."),
+        ];
+        let version = SemanticVersion::new(2, 0, 0);
+
+        assert_eq!(
+            render_release(&version, "2024-06-01", &commits),
+            "## v2.0.0 (2024-06-01)\n\
+            \n### ⚠ Breaking Changes\n\n\
+            - **config:** `files` is renamed `updaters`.\n  Run `semver migrate` to update it. (bbbbbbb)\n\
+            - drop the v1 guide. (ccccccc)\n\
+            \n### Features\n\n- **BREAKING** **config:** v2 format. (bbbbbbb)\n\
+            \n### Bug Fixes\n\n- a fix. (aaaaaaa)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_grouped_release_lists_every_breaking_change_of_a_squash_merge() {
+        let commits = vec![
+            parsed("aaaaaaaaaa", "feat(api)! drop v1."),
+            parsed("aaaaaaaaaa", "fix(web)! new urls."),
+            parsed("bbbbbbbbbb", "fix(web): a fix."),
+        ];
+        let mut groups = group_by_scope(&commits);
+        groups.push(("all".to_string(), vec![commits[0].clone()]));
+        let version = SemanticVersion::new(2, 0, 0);
+
+        let release = render_grouped_release(&version, "2024-06-01", &groups, None, None);
+        assert!(release.starts_with(
+            "## v2.0.0 (2024-06-01)\n\
+            \n### ⚠ Breaking Changes\n\n\
+            - **api:** drop v1. (aaaaaaa)\n\
+            - **web:** new urls. (aaaaaaa)\n\n###"
+        ));
+    }
+
+    #[test]
+    fn test_render_release_puts_security_fixes_first() {
+        let commits = vec![
+            parsed("aaaaaaaaaa", "feat: a feature."),
+            parsed("bbbbbbbbbb", "security: escape input."),
+        ];
+        let version = SemanticVersion::new(1, 1, 0);
+
+        assert_eq!(
+            render_release(&version, "2024-06-01", &commits),
+            "## v1.1.0 (2024-06-01)\n\
+            \n### Security\n\n- escape input. (bbbbbbb)\n\
+            \n### Features\n\n- a feature. (aaaaaaa)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_release_lists_deprecations_of_every_commit() {
+        let message = "docs(api): document v2.\n\nDeprecated: the v1 endpoints, use v2.";
+        let commits = vec![
+            parsed("aaaaaaaaaa", "fix: a fix."),
+            ParsedCommit {
+                commit: Commit::new("bbbbbbbbbb", message),
+                comment: crate::CommentParser::builder()
+                    .types(["fix", "docs"])
+                    .build()
+                    .parse_message(message)
+                    .unwrap(),
+            },
+        ];
+        let version = SemanticVersion::new(1, 0, 1);
+
+        assert_eq!(
+            render_release(&version, "2024-06-01", &commits),
+            "## v1.0.1 (2024-06-01)\n\
+            \n### Bug Fixes\n\n- a fix. (aaaaaaa)\n\
+            \n### Deprecations\n\n- **api:** the v1 endpoints, use v2.\n"
+        );
+    }
+
+    #[test]
+    fn test_render_release_lists_references_not_in_the_description() {
+        let message = "fix: crash on start (#12).\n\nCloses #10";
+        let commits = vec![ParsedCommit {
+            commit: Commit::new("aaaaaaaaaa", message),
+            comment: crate::CommentParser::default()
+                .parse_message(message)
+                .unwrap(),
+        }];
+        let version = SemanticVersion::new(0, 1, 1);
+
+        assert_eq!(
+            render_release(&version, "2024-06-01", &commits),
+            "## v0.1.1 (2024-06-01)\n\n### Bug Fixes\n\n- crash on start (#12). (#10) (aaaaaaa)\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_release_keeps_previous_releases_below_the_header() {
+        let changelog = insert_release("", "## v0.1.0 (2024-01-01)\n");
+        assert_eq!(changelog, "# Changelog\n\n## v0.1.0 (2024-01-01)\n");
+
+        assert_eq!(
+            insert_release(&changelog, "## v0.2.0 (2024-02-01)\n"),
+            "# Changelog\n\n## v0.2.0 (2024-02-01)\n\n## v0.1.0 (2024-01-01)\n"
+        );
+    }
+
+    #[test]
+    fn test_civil_from_days_converts_known_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_875), (2024, 6, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+}
@@ -0,0 +1,141 @@
+use std::{fs, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{Config, SemVerError, SemanticVersion, Vcs, VersionFileUpdater};
+
+/// [`VersionCheck`] compares the version held by a file with the one the history requires.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VersionCheck {
+    pub path: PathBuf,
+    /// Version held by the file.
+    pub version: SemanticVersion,
+    /// Version of the latest tag.
+    pub released: SemanticVersion,
+    /// Version implied by the commits since the latest tag.
+    pub expected: SemanticVersion,
+}
+
+impl VersionCheck {
+    /// Returns `true` when the file holds the expected version.
+    pub fn is_ok(&self) -> bool {
+        self.version == self.expected
+    }
+
+    /// [`as_json_string`] returns json representation of the check.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+
+    /// Describes why the check fails, if it does.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let check = VersionCheck {
+    ///     path: "Cargo.toml".into(),
+    ///     version: SemanticVersion::new(1, 2, 0),
+    ///     released: SemanticVersion::new(1, 2, 0),
+    ///     expected: SemanticVersion::new(1, 3, 0),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     check.problem().unwrap(),
+    ///     "not bumped, the commits since v1.2.0 require v1.3.0"
+    /// );
+    /// ```
+    pub fn problem(&self) -> Option<String> {
+        if self.is_ok() {
+            return None;
+        }
+
+        Some(match self.version == self.released {
+            true => format!(
+                "not bumped, the commits since {} require {}",
+                String::from(self.released.clone()),
+                String::from(self.expected.clone())
+            ),
+            false => format!(
+                "holds {}, the commits since {} require {}",
+                String::from(self.version.clone()),
+                String::from(self.released.clone()),
+                String::from(self.expected.clone())
+            ),
+        })
+    }
+}
+
+/// [`check_versions`] compares the version held by the files of `updaters` with the version
+/// of the latest tag bumped by the commits since, on `branch`.
+pub fn check_versions(
+    vcs: &dyn Vcs,
+    config: &Config,
+    updaters: &[Box<dyn VersionFileUpdater>],
+    branch: Option<&str>,
+) -> Result<Vec<VersionCheck>, SemVerError> {
+    let (tag, released) = vcs
+        .latest_version_tag_with_format(&config.tag_format()?)?
+        .ok_or(SemVerError::NoVersionTag)?;
+    let commits = vcs.commits(Some(&format!("{}..HEAD", tag.name)))?;
+    let expected = config.next_version(&released, config.analyze(&commits, branch)?.bump)?;
+
+    updaters
+        .iter()
+        .map(|updater| {
+            let contents = fs::read_to_string(updater.path())?;
+
+            Ok(VersionCheck {
+                path: updater.path().to_path_buf(),
+                version: updater.version(&contents)?,
+                released: released.clone(),
+                expected: expected.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        git::test::{commit, init_repository},
+        CargoUpdater, Git,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_check_versions_expects_the_bump_of_the_commits_since_the_latest_tag() {
+        let git = init_repository("check_versions");
+        let manifest = git.directory().join("Cargo.toml");
+        commit(&git, "a.txt", "feat: initial.");
+        git.create_tag("v0.1.0", "v0.1.0").unwrap();
+        commit(&git, "a.txt", "feat: new endpoint.");
+        fs::write(
+            &manifest,
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let updaters: Vec<Box<dyn VersionFileUpdater>> = vec![Box::new(CargoUpdater {
+            path: manifest.clone(),
+            lockstep: vec![],
+        })];
+        let check = |git: &Git| {
+            check_versions(git, &Config::default(), &updaters, None)
+                .unwrap()
+                .remove(0)
+        };
+
+        assert_eq!(
+            check(&git).problem().unwrap(),
+            "not bumped, the commits since v0.1.0 require v0.2.0"
+        );
+
+        fs::write(
+            &manifest,
+            "[package]\nname = \"app\"\nversion = \"0.2.0\"\n",
+        )
+        .unwrap();
+        assert!(check(&git).is_ok());
+    }
+}
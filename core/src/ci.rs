@@ -0,0 +1,196 @@
+use std::{fs, path::Path};
+
+use crate::SemVerError;
+
+/// Properties file `--jenkins-output` writes by default, read with `readProperties`.
+pub const JENKINS_OUTPUT_FILE_NAME: &str = "semver.properties";
+
+/// [`render_azure_output`] renders `outputs` as Azure Pipelines logging commands setting output
+/// variables, readable by later jobs as `dependencies.<job>.outputs['<step>.<key>']`.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let outputs = vec![("version", "1.3.0".to_string()), ("released", "true".to_string())];
+///
+/// let commands = render_azure_output(&outputs);
+///
+/// assert_eq!(commands.lines().count(), 2);
+/// assert_eq!(commands.lines().next(), Some("##vso[task.setvariable variable=version;isOutput=true]1.3.0"));
+/// assert_eq!(render_azure_output(&[("notes", "a;b".to_string())]).trim_end(), "##vso[task.setvariable variable=notes;isOutput=true]a%3Bb");
+/// ```
+pub fn render_azure_output(outputs: &[(&str, String)]) -> String {
+    outputs
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "##vso[task.setvariable variable={};isOutput=true]{}\n",
+                key,
+                escape_azure(value)
+            )
+        })
+        .collect()
+}
+
+/// [`write_azure_output`] prints `outputs` as Azure Pipelines logging commands, see
+/// [`render_azure_output`].
+pub fn write_azure_output(outputs: &[(&str, String)]) {
+    print!("{}", render_azure_output(outputs));
+}
+
+/// [`render_jenkins_output`] renders `outputs` as a Java properties file, e.g. for the
+/// `readProperties` step of Jenkins pipelines.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let outputs = vec![("version", "1.3.0".to_string()), ("tag", "v1.3.0".to_string())];
+///
+/// assert_eq!(render_jenkins_output(&outputs), "version=1.3.0\ntag=v1.3.0\n");
+/// ```
+pub fn render_jenkins_output(outputs: &[(&str, String)]) -> String {
+    outputs
+        .iter()
+        .map(|(key, value)| format!("{}={}\n", key, escape_properties(value)))
+        .collect()
+}
+
+/// [`write_jenkins_output`] writes `outputs` to the properties file at `path`, replacing the
+/// outputs of a previous run.
+pub fn write_jenkins_output(path: &Path, outputs: &[(&str, String)]) -> Result<(), SemVerError> {
+    fs::write(path, render_jenkins_output(outputs))?;
+
+    Ok(())
+}
+
+/// [`render_teamcity_output`] renders `outputs` as TeamCity service messages: the `version`
+/// becomes the build number, and each output a `semver.<key>` parameter, e.g.
+/// `%semver.bump%`.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let outputs = vec![("version", "1.3.0".to_string()), ("bump", "minor".to_string())];
+/// let messages = render_teamcity_output(&outputs);
+///
+/// assert_eq!(
+///     messages.lines().collect::<Vec<_>>(),
+///     vec![
+///         "##teamcity[buildNumber '1.3.0']",
+///         "##teamcity[setParameter name='semver.version' value='1.3.0']",
+///         "##teamcity[setParameter name='semver.bump' value='minor']",
+///     ]
+/// );
+/// ```
+pub fn render_teamcity_output(outputs: &[(&str, String)]) -> String {
+    let build_number = outputs
+        .iter()
+        .find(|(key, _)| *key == "version")
+        .map(|(_, version)| format!("##teamcity[buildNumber '{}']\n", escape_teamcity(version)));
+    let parameters = outputs.iter().map(|(key, value)| {
+        format!(
+            "##teamcity[setParameter name='semver.{}' value='{}']\n",
+            key,
+            escape_teamcity(value)
+        )
+    });
+
+    build_number.into_iter().chain(parameters).collect()
+}
+
+/// [`write_teamcity_output`] prints `outputs` as TeamCity service messages, see
+/// [`render_teamcity_output`].
+pub fn write_teamcity_output(outputs: &[(&str, String)]) {
+    print!("{}", render_teamcity_output(outputs));
+}
+
+/// Escapes the characters ending or breaking a logging command the way the Azure agent
+/// unescapes them.
+fn escape_azure(value: &str) -> String {
+    value
+        .replace('%', "%AZP25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+        .replace(']', "%5D")
+        .replace(';', "%3B")
+}
+
+/// Escapes the values of service messages with `|`, the way TeamCity reads them.
+fn escape_teamcity(value: &str) -> String {
+    value
+        .replace('|', "||")
+        .replace('\'', "|'")
+        .replace('\n', "|n")
+        .replace('\r', "|r")
+        .replace('[', "|[")
+        .replace(']', "|]")
+}
+
+fn escape_properties(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_azure_output_sets_output_variables_escaping_values() {
+        let outputs = vec![
+            ("version", "1.3.0".to_string()),
+            ("notes", "50%; fixed [a]\r\nb".to_string()),
+        ];
+
+        assert_eq!(
+            render_azure_output(&outputs),
+            "##vso[task.setvariable variable=version;isOutput=true]1.3.0\n\
+             ##vso[task.setvariable variable=notes;isOutput=true]50%AZP25%3B fixed [a%5D%0D%0Ab\n"
+        );
+        assert_eq!(render_azure_output(&[]), "");
+    }
+
+    #[test]
+    fn test_jenkins_output_writes_a_properties_file() {
+        let outputs = vec![
+            ("version", "1.3.0".to_string()),
+            ("notes", "C:\\path\nsecond line".to_string()),
+        ];
+        let path =
+            std::env::temp_dir().join(format!("semver-jenkins-{}.properties", std::process::id()));
+        fs::write(&path, "stale=true\n").unwrap();
+
+        write_jenkins_output(&path, &outputs).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "version=1.3.0\nnotes=C:\\\\path\\nsecond line\n"
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_teamcity_output_sets_the_build_number_and_parameters() {
+        let outputs = vec![
+            ("bump", "minor".to_string()),
+            ("version", "1.3.0".to_string()),
+            ("notes", "it's [done] | ok\r\n".to_string()),
+        ];
+
+        assert_eq!(
+            render_teamcity_output(&outputs).lines().collect::<Vec<_>>(),
+            vec![
+                "##teamcity[buildNumber '1.3.0']",
+                "##teamcity[setParameter name='semver.bump' value='minor']",
+                "##teamcity[setParameter name='semver.version' value='1.3.0']",
+                "##teamcity[setParameter name='semver.notes' value='it|'s |[done|] || ok|r|n']",
+            ]
+        );
+        assert_eq!(
+            render_teamcity_output(&[("released", "false".to_string())]),
+            "##teamcity[setParameter name='semver.released' value='false']\n"
+        );
+    }
+}
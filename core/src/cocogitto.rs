@@ -0,0 +1,127 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{comment_parser::CONVENTIONAL_TYPES, BumpLevel, Config, PolicyRule, SemVerError};
+
+/// Cocogitto configuration file read by [`Config::discover`] when there is no `semver.toml`.
+pub const COG_FILE_NAME: &str = "cog.toml";
+
+/// The subset of `cog.toml` mapped by [`Config::from_cog_toml_str`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CogConfig {
+    tag_prefix: Option<String>,
+    ignore_merge_commits: bool,
+    commit_types: BTreeMap<String, CogCommitType>,
+    changelog: CogChangelog,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CogCommitType {
+    bump_minor: bool,
+    bump_patch: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CogChangelog {
+    path: Option<PathBuf>,
+}
+
+impl Config {
+    /// [`from_cog_toml_str`] maps a cocogitto configuration onto a configuration: the
+    /// `[commit_types]` extend the accepted types, `bump_minor` and `bump_patch` becoming policy
+    /// rules, `tag_prefix` sets the tag format, `ignore_merge_commits` skips merges and the
+    /// changelog `path` is kept. Other settings, e.g. the changelog templates, are left out.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_cog_toml_str(r#"
+    /// tag_prefix = "v"
+    ///
+    /// [commit_types]
+    /// hotfix = { changelog_title = "Hotfixes", bump_patch = true }
+    ///
+    /// [changelog]
+    /// path = "CHANGELOG.md"
+    /// "#).unwrap();
+    ///
+    /// assert!(config.types.contains(&"hotfix".to_string()));
+    /// assert_eq!(config.tag_format.as_deref(), Some("v{major}.{minor}.{patch}{-pre}{+build}"));
+    /// assert_eq!(config.changelog, Some("CHANGELOG.md".into()));
+    /// ```
+    pub fn from_cog_toml_str(contents: &str) -> Result<Self, SemVerError> {
+        let cog: CogConfig = toml::from_str(contents)
+            .map_err(|e| SemVerError::InvalidConfig(format!("cog.toml: {}", e)))?;
+        let mut config = Config {
+            types: CONVENTIONAL_TYPES.iter().map(|t| t.to_string()).collect(),
+            // cocogitto tags have no prefix unless configured, e.g. `1.2.3`
+            tag_format: Some(format!(
+                "{}{{major}}.{{minor}}.{{patch}}{{-pre}}{{+build}}",
+                cog.tag_prefix.unwrap_or_default()
+            )),
+            changelog: cog.changelog.path,
+            ..Default::default()
+        };
+        config.history.skip_merges = cog.ignore_merge_commits;
+
+        for (name, commit_type) in cog.commit_types {
+            let bump = match (commit_type.bump_minor, commit_type.bump_patch) {
+                (true, _) => Some(BumpLevel::Minor),
+                (false, true) => Some(BumpLevel::Patch),
+                (false, false) => None,
+            };
+            if let Some(bump) = bump {
+                config.policy.rules.push(PolicyRule {
+                    when: format!("type == {} && !breaking", name),
+                    bump,
+                });
+            }
+            if !config.types.contains(&name) {
+                config.types.push(name);
+            }
+        }
+        config.bump_policy()?;
+
+        Ok(config)
+    }
+
+    /// [`load_cog`] reads the cocogitto configuration file at `path`,
+    /// see [`Config::from_cog_toml_str`].
+    pub fn load_cog(path: impl AsRef<Path>) -> Result<Self, SemVerError> {
+        Self::from_cog_toml_str(&fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Commit, SemanticVersion};
+
+    use super::*;
+
+    #[test]
+    fn test_config_from_cog_toml_bumps_the_custom_types() {
+        let config = Config::from_cog_toml_str(
+            "ignore_merge_commits = true\n\n[commit_types]\nrelease = { changelog_title = \"Releases\", bump_minor = true }\n",
+        )
+        .unwrap();
+        let commits = vec![Commit::new("a1", "release: ship it.")];
+
+        assert!(config.history.skip_merges);
+        assert_eq!(
+            config.analyze(&commits, None).unwrap().bump,
+            BumpLevel::Minor
+        );
+        assert_eq!(
+            config.tag_format().unwrap().parse("1.2.0"),
+            Some(SemanticVersion::new(1, 2, 0))
+        );
+    }
+}
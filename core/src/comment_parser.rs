@@ -1,15 +1,737 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::{borrow::Cow, sync::LazyLock};
+
 use regex::Regex;
-use serde::{Deserialize, Serialize};
-use thiserror::Error;
 
-use crate::{SemVerError, SemanticComment, SemanticType, SemanticTypeMetadata};
+use crate::models::{group, literal_regex};
+use crate::{
+    RefactorKeyword, Reference, ReferenceKind, SemVerError, SemanticComment, SemanticType,
+    SemanticTypeMetadata,
+};
+
+/// Semantic types understood by the parser when no explicit list is configured.
+pub(crate) const DEFAULT_TYPES: [&str; 3] = ["feat", "fix", "refactor"];
+
+/// Types of the conventional commits presets, accepted by cocogitto and semantic-release.
+pub(crate) const CONVENTIONAL_TYPES: [&str; 11] = [
+    "feat", "fix", "chore", "revert", "perf", "docs", "style", "refactor", "test", "build", "ci",
+];
+
+/// git's own revert subject, `Revert "<reverted comment>"`.
+static GIT_REVERT_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| literal_regex(r#"^Revert "(?P<reverted>.*)"\s*$"#));
+
+/// Issue and merge request references, optionally preceded by a closing keyword.
+static REFERENCE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    literal_regex(
+        r"(?i)(?:^|[\s(\[,])(?:(?P<keyword>close[sd]?|fix(?:e[sd])?|resolve[sd]?):?\s+)?(?P<kind>#|GH-|!)(?P<number>\d+)\b",
+    )
+});
+
+/// `Key: value` trailers of the message body.
+static TRAILER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    literal_regex(r"^(?P<key>[A-Za-z0-9][A-Za-z0-9-]*|BREAKING CHANGE):\s+(?P<value>.*)$")
+});
+
+/// Legacy spelling accepted as an alias of `refactor`.
+const LEGACY_REFACTOR_TYPE: &str = "refact";
+
+/// Type of reverts, accepted whatever the configured types.
+pub(crate) const REVERT_TYPE: &str = "revert";
+
+/// Type of security fixes, accepted whatever the configured types.
+pub(crate) const SECURITY_TYPE: &str = "security";
+
+/// Gitmoji recognized by [`CommentParserBuilder::gitmoji`] with the type they stand for,
+/// a trailing `!` marking a breaking change.
+pub const DEFAULT_GITMOJI: [(&str, &str); 14] = [
+    (":sparkles:", "feat"),
+    ("\u{2728}", "feat"),
+    (":boom:", "feat!"),
+    ("\u{1f4a5}", "feat!"),
+    (":bug:", "fix"),
+    ("\u{1f41b}", "fix"),
+    (":ambulance:", "fix"),
+    ("\u{1f691}", "fix"),
+    (":lock:", "fix"),
+    ("\u{1f512}", "fix"),
+    (":recycle:", "refactor"),
+    ("\u{267b}\u{fe0f}", "refactor"),
+    (":art:", "refactor"),
+    ("\u{1f3a8}", "refactor"),
+];
+
+/// Reverts of reverts parsed before giving up on a comment, keeping the recursion of
+/// [`CommentParser::parse`] bounded whatever the input.
+const MAX_REVERT_DEPTH: usize = 8;
+
+/// Keys of the footers announcing a breaking change, see [`CommentParser::parse_message`].
+const BREAKING_CHANGE_KEYS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// Byte order mark some editors write at the start of a file, and so of a commit message.
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Variation selector some emoji are written with, e.g. `♻️`.
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+
+/// [`CommentParser`] parses semantic comments according to its configuration.
+///
+/// It owns its compiled patterns, so a parser should be built once and reused.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let parser = CommentParser::builder()
+///     .strict(true)
+///     .allow_scope(true)
+///     .types(["feat", "fix"])
+///     .build();
+///
+/// let parsed_comment = parser.parse("feat(api): new endpoint.").unwrap();
+/// assert_eq!(parsed_comment.scope, Some("api".to_string()));
+///
+/// assert!(parser.parse("refactor: not allowed here.").is_err());
+/// assert!(parser.parse("fix:").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommentParser {
+    strict: bool,
+    normalize_whitespace: bool,
+    types: Vec<String>,
+    pattern: Regex,
+    git_revert_pattern: Regex,
+    reference_pattern: Regex,
+    trailer_pattern: Regex,
+    ticket_pattern: Option<Regex>,
+    gitmoji: Vec<(String, String)>,
+    aliases: Vec<(String, String)>,
+    refactor_keyword: RefactorKeyword,
+}
+
+/// Builds a [`CommentParser`], see [`CommentParser::builder`].
+#[derive(Debug, Clone)]
+pub struct CommentParserBuilder {
+    strict: bool,
+    normalize_whitespace: bool,
+    strict_spec: bool,
+    allow_scope: bool,
+    types: Vec<String>,
+    ticket_pattern: Option<Regex>,
+    gitmoji: Vec<(String, String)>,
+    aliases: Vec<(String, String)>,
+    refactor_keyword: RefactorKeyword,
+}
+
+impl Default for CommentParserBuilder {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            normalize_whitespace: true,
+            strict_spec: false,
+            allow_scope: true,
+            types: DEFAULT_TYPES.iter().map(|t| t.to_string()).collect(),
+            ticket_pattern: None,
+            gitmoji: vec![],
+            aliases: vec![],
+            refactor_keyword: RefactorKeyword::default(),
+        }
+    }
+}
+
+impl CommentParserBuilder {
+    /// When `strict` is enabled comments without a description are rejected.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// When `normalize_whitespace` is enabled (default), messages are read as written on any
+    /// platform: a leading byte order mark is dropped, `\r\n` and `\r` line endings become `\n`
+    /// and the trailing whitespace of every line is trimmed.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let message = "\u{feff}feat(api): export. \r\n\r\nReviewed-by: Jane \r\n";
+    ///
+    /// let comment = CommentParser::default().parse_message(message).unwrap();
+    /// assert_eq!(comment.to_commit_string(), "feat(api): export.");
+    /// assert_eq!(comment.trailers, vec![("Reviewed-by".to_string(), "Jane".to_string())]);
+    ///
+    /// let parser = CommentParser::builder().normalize_whitespace(false).build();
+    /// assert!(parser.parse_message(message).is_err());
+    /// ```
+    pub fn normalize_whitespace(mut self, normalize_whitespace: bool) -> Self {
+        self.normalize_whitespace = normalize_whitespace;
+        self
+    }
+
+    /// When `strict_spec` is enabled comments must follow Conventional Commits 1.0.0 exactly:
+    /// `type(scope)!: description`, with a lower case type, a colon even after `!`, a space
+    /// after the colon and a description.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let parser = CommentParser::builder().strict_spec(true).build();
+    ///
+    /// assert!(parser.parse("feat(api)!: drop v1.").unwrap().semantic_type.metadata().is_breaking);
+    /// assert!(parser.parse("feat! drop v1.").is_err());
+    /// assert!(parser.parse("feat:drop v1.").is_err());
+    /// assert!(parser.parse("Feat: drop v1.").is_err());
+    /// ```
+    pub fn strict_spec(mut self, strict_spec: bool) -> Self {
+        self.strict_spec = strict_spec;
+        self
+    }
+
+    /// Controls if a scope is accepted, e.g. `feat(api): new endpoint.`.
+    pub fn allow_scope(mut self, allow_scope: bool) -> Self {
+        self.allow_scope = allow_scope;
+        self
+    }
+
+    /// Sets the accepted semantic types, e.g. `["feat", "fix", "docs"]`.
+    ///
+    /// Types other than `feat`, `fix` and `refactor` are parsed as [`SemanticType::Other`].
+    pub fn types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.types = types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the pattern of the tickets collected in [`SemanticComment::tickets`],
+    /// e.g. `PROJ-\d+`.
+    pub fn ticket_pattern(mut self, ticket_pattern: Regex) -> Self {
+        self.ticket_pattern = Some(ticket_pattern);
+        self
+    }
+
+    /// Accepts other spellings of the types, e.g. `("feature", "feat")` or `("bugfix", "fix")`.
+    ///
+    /// Types are matched case insensitively, aliases included.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let parser = CommentParser::builder().aliases([("feature", "feat")]).build();
+    ///
+    /// assert_eq!(parser.parse("Feature: export.").unwrap(), parser.parse("feat: export.").unwrap());
+    /// assert_eq!(parser.parse("FIX: crash.").unwrap().to_commit_string(), "fix: crash.");
+    /// ```
+    pub fn aliases<I, K, V>(mut self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.aliases = aliases
+            .into_iter()
+            .map(|(alias, semantic_type)| {
+                let alias: String = alias.into();
+                let semantic_type: String = semantic_type.into();
+                (alias.to_lowercase(), semantic_type.to_lowercase())
+            })
+            .collect();
+        self
+    }
+
+    /// Spells refactorings as `refactor_keyword` when the parsed comments are rendered back,
+    /// see [`SemanticComment::to_commit_string`]. Both spellings are parsed either way.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let parser = CommentParser::builder().refactor_keyword(RefactorKeyword::Refact).build();
+    ///
+    /// assert_eq!(parser.parse("refactor: simplify.").unwrap().to_commit_string(), "refact: simplify.");
+    /// ```
+    pub fn refactor_keyword(mut self, refactor_keyword: RefactorKeyword) -> Self {
+        self.refactor_keyword = refactor_keyword;
+        self
+    }
+
+    /// Recognizes commits prefixed by one of the `gitmoji`, e.g. `:sparkles:` or `✨`,
+    /// mapped to the type they stand for, see [`DEFAULT_GITMOJI`].
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let parser = CommentParser::builder().gitmoji(DEFAULT_GITMOJI).build();
+    ///
+    /// assert_eq!(parser.parse(":sparkles: add export.").unwrap().to_commit_string(), "feat: add export.");
+    /// assert_eq!(parser.parse("🐛 fix(api): crash.").unwrap().to_commit_string(), "fix(api): crash.");
+    /// assert!(parser.parse("💥 drop v1.").unwrap().semantic_type.metadata().is_breaking);
+    /// ```
+    pub fn gitmoji<I, K, V>(mut self, gitmoji: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.gitmoji = gitmoji
+            .into_iter()
+            .map(|(emoji, semantic_type)| {
+                let emoji: String = emoji.into();
+                (emoji.replace(EMOJI_PRESENTATION, ""), semantic_type.into())
+            })
+            .collect();
+        // longest first, so that an emoji is not shadowed by one of its prefixes
+        self.gitmoji
+            .sort_by_key(|(emoji, _)| std::cmp::Reverse(emoji.len()));
+        self
+    }
+
+    pub fn build(self) -> CommentParser {
+        let (type_pattern, separator) = match self.strict_spec {
+            true => (r"[a-z0-9_]+", r"(?P<breaking>!)?: "),
+            false => (r"[a-zA-Z0-9_]+", r"(?:(?P<breaking>!):?|:)"),
+        };
+        let scope = match self.allow_scope {
+            true => r"(\((?P<scope>[^()]*)\))?",
+            false => "",
+        };
+        let pattern = format!("^(?P<type>{}){}{}", type_pattern, scope, separator);
+
+        CommentParser {
+            strict: self.strict || self.strict_spec,
+            normalize_whitespace: self.normalize_whitespace,
+            types: self.types,
+            pattern: literal_regex(&pattern),
+            git_revert_pattern: GIT_REVERT_PATTERN.clone(),
+            reference_pattern: REFERENCE_PATTERN.clone(),
+            trailer_pattern: TRAILER_PATTERN.clone(),
+            ticket_pattern: self.ticket_pattern,
+            gitmoji: self.gitmoji,
+            aliases: self.aliases,
+            refactor_keyword: self.refactor_keyword,
+        }
+    }
+}
+
+impl Default for CommentParser {
+    fn default() -> Self {
+        CommentParserBuilder::default().build()
+    }
+}
+
+impl CommentParser {
+    pub fn builder() -> CommentParserBuilder {
+        CommentParserBuilder::default()
+    }
+
+    /// Parses a comment and returns a [`Result<SemanticComment, SemVerError>`]
+    /// # Expected format:
+    /// - <semantic_type>: this is a <semantic_type>.
+    /// - <semantic_type>! this is a <semantic_type>, or <semantic_type>!: this is a <semantic_type>.
+    /// - <semantic_type>(<scope>): this is a scoped <semantic_type>.
+    /// - revert: <reverted comment>, or git's `Revert "<reverted comment>"`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let parser = CommentParser::default();
+    /// let revert = parser.parse(r#"Revert "feat(api): new endpoint.""#).unwrap();
+    ///
+    /// assert_eq!(revert, parser.parse("revert: feat(api): new endpoint.").unwrap());
+    /// assert_eq!(revert.semantic_type.default_bump(), BumpLevel::Patch);
+    /// ```
+    pub fn parse(&self, comment: &str) -> Result<SemanticComment, SemVerError> {
+        self.parse_nested(comment, 0)
+    }
+
+    /// Parses `comment`, reverted by `depth` enclosing reverts.
+    fn parse_nested(&self, comment: &str, depth: usize) -> Result<SemanticComment, SemVerError> {
+        let comment = self.normalize(comment);
+        let comment = comment.as_ref();
+        let expanded;
+        let comment = match self.expand_gitmoji(comment) {
+            Some(comment) => {
+                expanded = comment;
+                expanded.as_str()
+            }
+            None => comment,
+        };
+
+        if let Some(captures) = self.git_revert_pattern.captures(comment) {
+            return self.parse_revert(group(&captures, "reverted"), false, None, depth);
+        }
+
+        let captures = self
+            .pattern
+            .captures(comment)
+            .ok_or(SemVerError::InvalidCommentFormat)?;
+
+        let prefix = self.canonical_type(group(&captures, "type"));
+        let is_breaking = captures.name("breaking").is_some();
+        let description = comment
+            .get(captures.get(0).map_or(0, |m| m.end())..)
+            .unwrap_or_default()
+            .trim();
+
+        if self.strict && description.is_empty() {
+            return Err(SemVerError::InvalidCommentFormat);
+        }
+        if prefix == REVERT_TYPE {
+            let scope = captures.name("scope").map(|scope| scope.as_str());
+            return self.parse_revert(description, is_breaking, scope, depth);
+        }
+        if !self.knows_type(&prefix) {
+            return Err(SemVerError::UnexpectedSemanticType(prefix.to_string()));
+        }
+
+        let metadata = SemanticTypeMetadata::new(is_breaking);
+        let semantic_type = match prefix.as_str() {
+            "feat" => SemanticType::Feature(metadata),
+            "fix" => SemanticType::Fix(metadata),
+            "refactor" => SemanticType::Refactoring(metadata),
+            SECURITY_TYPE => SemanticType::Security(metadata),
+            _ => SemanticType::Other(prefix, metadata),
+        };
+
+        let mut semantic_comment = SemanticComment::new(description.to_string(), semantic_type);
+        semantic_comment.refactor_keyword = self.refactor_keyword;
+        semantic_comment.references = self.references(description);
+        semantic_comment.tickets = self.tickets(description);
+
+        Ok(match captures.name("scope") {
+            Some(scope) => semantic_comment.with_scope(scope.as_str()),
+            None => semantic_comment,
+        })
+    }
+
+    /// [`parse_message`] parses the subject of a whole commit message, collecting the
+    /// references, tickets and trailers of its body and footers as well.
+    ///
+    /// A `BREAKING CHANGE:` (or `BREAKING-CHANGE:`) footer makes the comment breaking, its text
+    /// running until the next footer, see [`SemanticComment::breaking_changes`].
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let comment = CommentParser::default()
+    ///     .parse_message("fix: crash on start (#12).\n\nSee GH-3 and !4.\n\nCloses #10")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     comment.references.iter().map(|r| (r.to_string(), r.closes)).collect::<Vec<_>>(),
+    ///     vec![
+    ///         ("#12".to_string(), false),
+    ///         ("GH-3".to_string(), false),
+    ///         ("!4".to_string(), false),
+    ///         ("#10".to_string(), true),
+    ///     ]
+    /// );
+    ///
+    /// let comment = CommentParser::default()
+    ///     .parse_message("feat: v2 config.\n\nBREAKING CHANGE: the config moved to semver.toml.\nRun `semver migrate`.")
+    ///     .unwrap();
+    /// assert!(comment.semantic_type.metadata().is_breaking);
+    /// assert_eq!(comment.breaking_changes, vec!["the config moved to semver.toml.\nRun `semver migrate`."]);
+    /// ```
+    pub fn parse_message(&self, message: &str) -> Result<SemanticComment, SemVerError> {
+        let message = self.normalize(message);
+        let (subject, body) = message.split_once('\n').unwrap_or((&message, ""));
+        let mut semantic_comment = self.parse(subject)?;
+
+        for reference in self.references(body) {
+            add_reference(&mut semantic_comment.references, reference);
+        }
+        for ticket in self.tickets(body) {
+            if !semantic_comment.tickets.contains(&ticket) {
+                semantic_comment.tickets.push(ticket);
+            }
+        }
+        semantic_comment.trailers = trailers(body);
+        semantic_comment.breaking_changes = self.breaking_changes(body);
+        if !semantic_comment.breaking_changes.is_empty() {
+            semantic_comment.semantic_type.metadata_mut().is_breaking = true;
+        }
+
+        Ok(semantic_comment)
+    }
+
+    /// Drops the byte order mark, `\r` line endings and trailing whitespace of `text` when
+    /// normalizing whitespace, see [`CommentParserBuilder::normalize_whitespace`].
+    fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.normalize_whitespace {
+            return Cow::Borrowed(text);
+        }
+        let text = text.strip_prefix(BYTE_ORDER_MARK).unwrap_or(text);
+        if !text.contains('\r') && !text.lines().any(|line| line.ends_with(char::is_whitespace)) {
+            return Cow::Borrowed(text.trim_end());
+        }
+
+        Cow::Owned(
+            text.replace("\r\n", "\n")
+                .replace('\r', "\n")
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim_end()
+                .to_string(),
+        )
+    }
+
+    /// Extracts the references of `text`, e.g. `#123` or `closes #123`.
+    fn references(&self, text: &str) -> Vec<Reference> {
+        let mut references = vec![];
+        for captures in self.reference_pattern.captures_iter(text) {
+            let Ok(number) = group(&captures, "number").parse() else {
+                continue;
+            };
+            let kind = match group(&captures, "kind").to_uppercase().as_str() {
+                "#" => ReferenceKind::Issue,
+                "!" => ReferenceKind::MergeRequest,
+                _ => ReferenceKind::GitHub,
+            };
+
+            add_reference(
+                &mut references,
+                Reference {
+                    kind,
+                    number,
+                    closes: captures.name("keyword").is_some(),
+                },
+            );
+        }
+
+        references
+    }
+
+    /// Whether `prefix`, in its canonical form, is an accepted type.
+    pub(crate) fn knows_type(&self, prefix: &str) -> bool {
+        prefix == REVERT_TYPE
+            || prefix == SECURITY_TYPE
+            || self.types.iter().any(|t| self.canonical_type(t) == prefix)
+    }
+
+    /// Maps a type to its canonical form: lower case, with aliases and the legacy `refact`
+    /// spelling resolved.
+    pub(crate) fn canonical_type(&self, prefix: &str) -> String {
+        let prefix = prefix.to_lowercase();
+        let prefix = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| *alias == prefix)
+            .map(|(_, semantic_type)| semantic_type.clone())
+            .unwrap_or(prefix);
+
+        match prefix.as_str() {
+            LEGACY_REFACTOR_TYPE => "refactor".to_string(),
+            _ => prefix,
+        }
+    }
+
+    /// Rewrites a comment prefixed by a gitmoji as a conventional one, keeping the conventional
+    /// prefix written after the emoji if any.
+    fn expand_gitmoji(&self, comment: &str) -> Option<String> {
+        let (semantic_type, rest) = self.gitmoji.iter().find_map(|(emoji, semantic_type)| {
+            comment
+                .strip_prefix(emoji.as_str())
+                .map(|rest| (semantic_type, rest.trim_start_matches(EMOJI_PRESENTATION)))
+        })?;
+        let rest = rest.trim_start();
+
+        if self.pattern.is_match(rest) {
+            return Some(rest.to_string());
+        }
+        Some(match semantic_type.strip_suffix('!') {
+            Some(semantic_type) => format!("{}!: {}", semantic_type, rest),
+            None => format!("{}: {}", semantic_type, rest),
+        })
+    }
+
+    /// Extracts the text of the `BREAKING CHANGE` footers of `body`, each running until the next
+    /// footer or the end of the message, whatever its indentation.
+    fn breaking_changes(&self, body: &str) -> Vec<String> {
+        let mut breaking_changes = vec![];
+        let mut current: Option<Vec<&str>> = None;
+        for line in body.lines() {
+            match self.trailer_pattern.captures(line.trim_end()) {
+                Some(captures) => {
+                    if let Some(lines) = current.take() {
+                        breaking_changes.push(lines.join("\n").trim().to_string());
+                    }
+                    if BREAKING_CHANGE_KEYS.contains(&group(&captures, "key")) {
+                        current = Some(vec![group(&captures, "value")]);
+                    }
+                }
+                None => {
+                    if let Some(lines) = &mut current {
+                        lines.push(line.trim());
+                    }
+                }
+            }
+        }
+        if let Some(lines) = current {
+            breaking_changes.push(lines.join("\n").trim().to_string());
+        }
+
+        breaking_changes.retain(|text| !text.is_empty());
+        breaking_changes
+    }
+
+    /// Extracts the tickets of `text` matching the ticket pattern, without duplicates.
+    fn tickets(&self, text: &str) -> Vec<String> {
+        let mut tickets: Vec<String> = vec![];
+        let Some(pattern) = &self.ticket_pattern else {
+            return tickets;
+        };
+
+        for ticket in pattern.find_iter(text) {
+            if !tickets.iter().any(|t| t == ticket.as_str()) {
+                tickets.push(ticket.as_str().to_string());
+            }
+        }
+
+        tickets
+    }
+
+    fn parse_revert(
+        &self,
+        reverted: &str,
+        is_breaking: bool,
+        scope: Option<&str>,
+        depth: usize,
+    ) -> Result<SemanticComment, SemVerError> {
+        if depth >= MAX_REVERT_DEPTH {
+            return Err(SemVerError::InvalidCommentFormat);
+        }
+        let reverted = self.parse_nested(reverted.trim(), depth + 1)?;
+        let mut semantic_comment = SemanticComment::new(
+            reverted.to_commit_string(),
+            SemanticType::Revert(SemanticTypeMetadata::new(is_breaking), Box::new(reverted)),
+        );
+        semantic_comment.refactor_keyword = self.refactor_keyword;
+
+        Ok(match scope {
+            Some(scope) => semantic_comment.with_scope(scope),
+            None => semantic_comment,
+        })
+    }
+
+    /// [`deprecated_type`] returns the deprecated spelling of the type of `comment`, if it uses
+    /// one, with its canonical spelling, e.g. `("refact", "refactor")`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let parser = CommentParser::default();
+    ///
+    /// assert_eq!(parser.deprecated_type("Refact: simplify."), Some(("refact", "refactor")));
+    /// assert_eq!(parser.deprecated_type("refactor: simplify."), None);
+    /// ```
+    pub fn deprecated_type(&self, comment: &str) -> Option<(&'static str, &'static str)> {
+        let expanded = self.expand_gitmoji(comment);
+        let captures = self
+            .pattern
+            .captures(expanded.as_deref().unwrap_or(comment))?;
+
+        match group(&captures, "type").eq_ignore_ascii_case(LEGACY_REFACTOR_TYPE) {
+            true => Some((LEGACY_REFACTOR_TYPE, "refactor")),
+            false => None,
+        }
+    }
+
+    /// Returns `true` when the comment can be parsed with this configuration.
+    pub fn is_valid(&self, comment: &str) -> bool {
+        self.parse(comment).is_ok()
+    }
+
+    /// [`parse_comments`] lazily parses every comment of `comments`, see [`parse_comments`].
+    pub fn parse_comments<'a, I>(
+        &'a self,
+        comments: I,
+    ) -> impl Iterator<Item = Result<SemanticComment, SemVerError>> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        comments.into_iter().map(move |comment| self.parse(comment))
+    }
+}
+
+/// [`parse_comments`] lazily parses every comment of `comments` with the default parser, e.g. the
+/// lines of `git log --format=%s`, without collecting them first.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let log = "feat: export.\nwip\nfix! drop v1.";
+///
+/// let bump: AggregateBump = parse_comments(log.lines()).filter_map(Result::ok).collect();
+/// assert_eq!(bump.bump, BumpLevel::Major);
+/// assert_eq!(parse_comments(log.lines()).filter(Result::is_err).count(), 1);
+/// ```
+pub fn parse_comments<'a, I>(
+    comments: I,
+) -> impl Iterator<Item = Result<SemanticComment, SemVerError>> + 'a
+where
+    I: IntoIterator<Item = &'a str>,
+    I::IntoIter: 'a,
+{
+    let parser = CommentParser::default();
+    comments
+        .into_iter()
+        .map(move |comment| parser.parse(comment))
+}
+
+/// Extracts the trailers of the last paragraph of `body`, when every line of it is a
+/// `Key: value` trailer or the indented continuation of one.
+pub(crate) fn trailers(body: &str) -> Vec<(String, String)> {
+    let Some(paragraph) = body.trim().rsplit("\n\n").next() else {
+        return vec![];
+    };
+
+    let mut trailers: Vec<(String, String)> = vec![];
+    for line in paragraph.lines() {
+        if let Some(captures) = TRAILER_PATTERN.captures(line.trim_end()) {
+            trailers.push((
+                group(&captures, "key").to_string(),
+                group(&captures, "value").to_string(),
+            ));
+            continue;
+        }
+        match trailers.last_mut() {
+            Some((_, value)) if line.starts_with(char::is_whitespace) => {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            _ => return vec![],
+        }
+    }
+
+    trailers
+}
+
+/// Adds `reference` unless already present, keeping the closing flag of either.
+fn add_reference(references: &mut Vec<Reference>, reference: Reference) {
+    match references
+        .iter_mut()
+        .find(|r| r.kind == reference.kind && r.number == reference.number)
+    {
+        Some(existing) => existing.closes |= reference.closes,
+        None => references.push(reference),
+    }
+}
 
-/// Parses a comment and returns a [`Result<SemanticComment, SemVerError>`]
+/// Parses a comment with the default [`CommentParser`] and returns a [`Result<SemanticComment, SemVerError>`]
 /// # Expected format:
 /// - <semantic_type>: this is a <semantic_type>.
-/// - <semantic_type>! this is a <semantic_type>.
+/// - <semantic_type>! this is a <semantic_type>, or <semantic_type>!: this is a <semantic_type>.
 ///
-/// Where <semantic_type> is [`fix`, `feat`, `refact`] and [`:`, `!`] means [`non_breaking`, `breaking`] respectively.
+/// Where <semantic_type> is [`fix`, `feat`, `refactor`] (`refact` is accepted as an alias) and [`:`, `!`] means [`non_breaking`, `breaking`] respectively.
 ///
 /// Example
 /// ```
@@ -24,36 +746,7 @@ impl TryFrom<&str> for SemanticComment {
     type Error = SemVerError;
 
     fn try_from(comment: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(r"^[a-zA-Z0-9_]+(:|!)").unwrap();
-
-        if let Some(mat) = re.find(comment) {
-            let prefix_delimiter = mat.end();
-
-            let left_side = &comment[0..prefix_delimiter];
-            let right_side = &comment[(prefix_delimiter)..comment.len()];
-
-            let is_breaking = left_side.ends_with('!');
-
-            let prefix = &left_side[0..left_side.len() - 1];
-
-            match prefix.trim() {
-                "feat" => Ok(SemanticComment::new(
-                    right_side.trim().to_string(),
-                    SemanticType::Feature(SemanticTypeMetadata::new(is_breaking)),
-                )),
-                "fix" => Ok(SemanticComment::new(
-                    right_side.trim().to_string(),
-                    SemanticType::Fix(SemanticTypeMetadata::new(is_breaking)),
-                )),
-                "refact" => Ok(SemanticComment::new(
-                    right_side.trim().to_string(),
-                    SemanticType::Refactoring(SemanticTypeMetadata::new(is_breaking)),
-                )),
-                _ => Err(SemVerError::UnexpectedSemanticType(prefix.to_string())),
-            }
-        } else {
-            Err(SemVerError::InvalidCommentFormat)
-        }
+        CommentParser::default().parse(comment)
     }
 }
 
@@ -101,6 +794,13 @@ mod test {
                     SemanticType::Fix(SemanticTypeMetadata::new(true)),
                 ),
             ),
+            (
+                "refactor: refactoring here",
+                SemanticComment::new(
+                    "refactoring here".to_string(),
+                    SemanticType::Refactoring(SemanticTypeMetadata::new(false)),
+                ),
+            ),
             (
                 "refact: refactoring here",
                 SemanticComment::new(
@@ -145,4 +845,249 @@ mod test {
             SemVerError::UnexpectedSemanticType("wop".to_string())
         )
     }
+
+    #[test]
+    fn test_parse_rejects_multibyte_types_without_panicking() {
+        let parser = CommentParser::default();
+
+        assert!(parser.parse("fé: ünïcödé").is_err());
+        assert!(parser.parse("ü").is_err());
+        assert!(parser.parse("feat(é)ü").is_err());
+    }
+
+    #[test]
+    fn test_comment_parser_applies_builder_configuration() {
+        let parser = CommentParser::builder()
+            .strict(true)
+            .allow_scope(false)
+            .types(["feat"])
+            .build();
+
+        assert_eq!(
+            parser.parse("feat: feature here").unwrap(),
+            SemanticComment::new(
+                "feature here".to_string(),
+                SemanticType::Feature(SemanticTypeMetadata::new(false)),
+            )
+        );
+        assert_eq!(
+            parser.parse("feat(api): feature here").unwrap_err(),
+            SemVerError::InvalidCommentFormat
+        );
+        assert_eq!(
+            parser.parse("feat: ").unwrap_err(),
+            SemVerError::InvalidCommentFormat
+        );
+        assert_eq!(
+            parser.parse("fix: fix here").unwrap_err(),
+            SemVerError::UnexpectedSemanticType("fix".to_string())
+        );
+    }
+
+    #[test]
+    fn test_default_comment_parser_retrieves_scope() {
+        let sem_comment = CommentParser::default()
+            .parse("fix(parser)! fix here")
+            .unwrap();
+
+        assert_eq!(
+            sem_comment,
+            SemanticComment::new(
+                "fix here".to_string(),
+                SemanticType::Fix(SemanticTypeMetadata::new(true)),
+            )
+            .with_scope("parser")
+        );
+    }
+
+    #[test]
+    fn test_parse_message_keeps_trailers_of_the_last_paragraph_in_order() {
+        let parser = CommentParser::default();
+
+        let comment = parser
+            .parse_message(
+                "feat: pairing.\n\nNote: not a trailer paragraph\nsince this line is prose.\n\n\
+                Signed-off-by: Joe <joe@example.com>\nCo-authored-by: Jane\n  Doe <jane@example.com>",
+            )
+            .unwrap();
+        assert_eq!(
+            comment.trailers,
+            vec![
+                (
+                    "Signed-off-by".to_string(),
+                    "Joe <joe@example.com>".to_string()
+                ),
+                (
+                    "Co-authored-by".to_string(),
+                    "Jane Doe <jane@example.com>".to_string()
+                ),
+            ]
+        );
+
+        let comment = parser
+            .parse_message("feat: pairing.\n\nNote: prose\nsince this line is prose.")
+            .unwrap();
+        assert!(comment.trailers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_message_reads_windows_and_mixed_line_endings_alike() {
+        let parser = CommentParser::default();
+        let expected = parser
+            .parse_message("fix(api): crash (#3).\n\nDetails.\n\nCloses #4\nReviewed-by: Jane")
+            .unwrap();
+
+        for message in [
+            "fix(api): crash (#3).\r\n\r\nDetails.\r\n\r\nCloses #4\r\nReviewed-by: Jane\r\n",
+            "\u{feff}fix(api): crash (#3).  \n\nDetails.\r\n\r\nCloses #4 \nReviewed-by: Jane\t\n",
+            "fix(api): crash (#3).\r\rDetails.\r\rCloses #4\rReviewed-by: Jane",
+        ] {
+            assert_eq!(parser.parse_message(message).unwrap(), expected);
+        }
+        assert_eq!(
+            CommentParser::builder()
+                .normalize_whitespace(false)
+                .build()
+                .parse("\u{feff}fix: crash.")
+                .unwrap_err(),
+            SemVerError::InvalidCommentFormat
+        );
+    }
+
+    #[test]
+    fn test_parse_message_preserves_multibyte_content() {
+        let parser = CommentParser::builder()
+            .types(["feat", "fix", "docs"])
+            .gitmoji(DEFAULT_GITMOJI)
+            .build();
+
+        let comment = parser
+            .parse_message(
+                "feat(日本語)!: ajoute l’export 🎉\n\nCo-authored-by: Zoë <zoe@example.com>",
+            )
+            .unwrap();
+        assert_eq!(comment.scope.as_deref(), Some("日本語"));
+        assert_eq!(comment.comment, "ajoute l’export 🎉");
+        assert_eq!(
+            comment.trailers,
+            vec![(
+                "Co-authored-by".to_string(),
+                "Zoë <zoe@example.com>".to_string()
+            )]
+        );
+
+        for invalid in ["é: x.", "👨‍👩‍👧 fix", "fé(ü): x.", "Revert \"日本\"", "feat(é"]
+        {
+            assert!(parser.parse(invalid).is_err(), "{}", invalid);
+        }
+        assert_eq!(parser.parse("✨ 🎉").unwrap().comment, "🎉");
+    }
+
+    #[test]
+    fn test_gitmoji_are_matched_with_or_without_variation_selector() {
+        let parser = CommentParser::builder().gitmoji(DEFAULT_GITMOJI).build();
+        let expected = SemanticComment::new(
+            "simplify.".to_string(),
+            SemanticType::Refactoring(SemanticTypeMetadata::new(false)),
+        );
+
+        assert_eq!(
+            parser.parse("\u{267b}\u{fe0f} simplify.").unwrap(),
+            expected
+        );
+        assert_eq!(parser.parse("\u{267b} simplify.").unwrap(), expected);
+        assert_eq!(parser.parse(":recycle: simplify.").unwrap(), expected);
+        assert_eq!(
+            CommentParser::default().parse(":recycle: simplify."),
+            Err(SemVerError::InvalidCommentFormat)
+        );
+    }
+
+    #[test]
+    fn test_breaking_marker_is_accepted_with_or_without_colon() {
+        let expected = SemanticComment::new(
+            "drop v1.".to_string(),
+            SemanticType::Feature(SemanticTypeMetadata::new(true)),
+        );
+
+        assert_eq!(
+            SemanticComment::try_from("feat!: drop v1.").unwrap(),
+            expected
+        );
+        assert_eq!(
+            SemanticComment::try_from("feat! drop v1.").unwrap(),
+            expected
+        );
+        assert_eq!(
+            CommentParser::builder()
+                .strict_spec(true)
+                .build()
+                .parse("feat!:"),
+            Err(SemVerError::InvalidCommentFormat)
+        );
+    }
+
+    #[test]
+    fn test_legacy_refactor_spelling_serializes_as_canonical_type() {
+        let parser = CommentParser::default();
+
+        assert_eq!(
+            parser.parse("refact: simplify.").unwrap().as_json_string(),
+            parser
+                .parse("refactor: simplify.")
+                .unwrap()
+                .as_json_string()
+        );
+    }
+
+    #[test]
+    fn test_comment_parser_collects_tickets_of_subject_and_body() {
+        let parser = CommentParser::builder()
+            .ticket_pattern(Regex::new(r"PROJ-\d+").unwrap())
+            .build();
+
+        let comment = parser
+            .parse_message("fix: PROJ-12 crash on start.\n\nRefs: PROJ-12, PROJ-7")
+            .unwrap();
+        assert_eq!(comment.tickets, vec!["PROJ-12", "PROJ-7"]);
+        assert!(CommentParser::default()
+            .parse("fix: PROJ-12 crash.")
+            .unwrap()
+            .tickets
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_deeply_nested_reverts_without_overflowing() {
+        let parser = CommentParser::default();
+
+        let nested = format!("{}feat: search.", "revert: ".repeat(MAX_REVERT_DEPTH));
+        assert!(matches!(
+            parser.parse(&nested).unwrap().semantic_type,
+            SemanticType::Revert(..)
+        ));
+
+        let too_deep = format!("{}feat: search.", "revert: ".repeat(20_000));
+        assert_eq!(
+            parser.parse(&too_deep),
+            Err(SemVerError::InvalidCommentFormat)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_deeply_nested_git_reverts_without_overflowing() {
+        let parser = CommentParser::default();
+        let nested = (0..20_000).fold("feat: search.".to_string(), |comment, _| {
+            format!("Revert \"{}\"", comment)
+        });
+
+        assert_eq!(
+            parser.parse(&nested),
+            Err(SemVerError::InvalidCommentFormat)
+        );
+        assert_eq!(
+            parser.parse_message(&format!("{}\n\nThis reverts commit aaaaaaaa11.", nested)),
+            Err(SemVerError::InvalidCommentFormat)
+        );
+    }
 }
@@ -0,0 +1,135 @@
+use std::{fs, path::Path};
+
+use serde_json::Value;
+
+use crate::{Config, SemVerError, SubjectCase};
+
+/// Commitlint configuration files read by [`Config::discover`] when there is no `semver.toml`.
+/// `commitlint.config.js` is left out: it is code rather than data.
+pub const COMMITLINT_FILE_NAMES: [&str; 4] = [
+    ".commitlintrc.json",
+    ".commitlintrc",
+    ".commitlintrc.yml",
+    ".commitlintrc.yaml",
+];
+
+impl Config {
+    /// [`from_commitlint_str`] maps the rules of a commitlint configuration, in its JSON or YAML
+    /// form, onto a configuration: `type-enum` to the accepted types, `scope-enum`,
+    /// `subject-max-length`, `header-max-length`, `body-max-line-length` and `subject-case`,
+    /// with `lower-case` or `sentence-case`, to the `[lint]` section. Rules of level 1 become warnings, disabled and other rules are left out.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_commitlint_str(r#"
+    /// rules:
+    ///   type-enum: [2, always, [feat, fix, docs]]
+    ///   scope-enum: [2, always, [api, cli]]
+    ///   subject-max-length: [1, always, 72]
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(config.types, vec!["feat", "fix", "docs"]);
+    /// assert_eq!(config.lint.scopes, vec!["api", "cli"]);
+    /// assert_eq!(config.lint.subject_max_length, Some(72));
+    /// assert_eq!(config.lint.warnings, vec!["subject-max-length"]);
+    /// ```
+    pub fn from_commitlint_str(contents: &str) -> Result<Self, SemVerError> {
+        // YAML is a superset of JSON
+        let commitlint: Value = serde_yaml::from_str(contents)
+            .map_err(|e| SemVerError::InvalidConfig(format!("commitlint: {}", e)))?;
+        let mut config = Config::default();
+
+        let rules = commitlint.get("rules").and_then(Value::as_object);
+        for (name, rule) in rules.into_iter().flatten() {
+            let Some([level, applicable, value]) = rule.as_array().map(Vec::as_slice) else {
+                continue;
+            };
+            let (Some(level @ 1..=2), Some("always")) = (level.as_u64(), applicable.as_str())
+            else {
+                continue;
+            };
+            let strings = || -> Vec<String> {
+                value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            };
+            let length = || value.as_u64().map(|max| max as usize);
+
+            let mapped = match name.as_str() {
+                "type-enum" => {
+                    config.types = strings();
+                    true
+                }
+                "scope-enum" => {
+                    config.lint.scopes = strings();
+                    true
+                }
+                "subject-max-length" => {
+                    config.lint.subject_max_length = length();
+                    true
+                }
+                "header-max-length" => {
+                    config.lint.header_max_length = length();
+                    true
+                }
+                "body-max-line-length" => {
+                    config.lint.body_max_line_length = length();
+                    true
+                }
+                "subject-case" => {
+                    config.lint.subject_case = match value.as_str() {
+                        Some("lower-case") => Some(SubjectCase::Lower),
+                        Some("sentence-case") => Some(SubjectCase::Sentence),
+                        _ => None,
+                    };
+                    config.lint.subject_case.is_some()
+                }
+                _ => false,
+            };
+            if mapped && level == 1 && name != "type-enum" {
+                config.lint.warnings.push(name.clone());
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// [`load_commitlint`] reads the commitlint configuration file at `path`,
+    /// see [`Config::from_commitlint_str`].
+    pub fn load_commitlint(path: impl AsRef<Path>) -> Result<Self, SemVerError> {
+        Self::from_commitlint_str(&fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_from_commitlint_json_leaves_out_disabled_and_unknown_rules() {
+        let config = Config::from_commitlint_str(
+            r#"{
+                "extends": ["@commitlint/config-conventional"],
+                "rules": {
+                    "header-max-length": [2, "always", 100],
+                    "body-max-line-length": [0, "always", 100],
+                    "scope-case": [2, "always", "lower-case"],
+                    "subject-max-length": [2, "never", 10],
+                    "subject-case": [1, "always", "lower-case"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.lint.header_max_length, Some(100));
+        assert_eq!(config.lint.body_max_line_length, None);
+        assert_eq!(config.lint.subject_max_length, None);
+        assert_eq!(config.lint.subject_case, Some(SubjectCase::Lower));
+        assert_eq!(config.lint.warnings, vec!["subject-case"]);
+        assert!(config.lint("feat: a feature.").is_ok());
+    }
+}
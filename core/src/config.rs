@@ -0,0 +1,819 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calver_version,
+    comment_parser::trailers,
+    history::{parse_commit, parse_each, parse_squashed_commit},
+    BumpPolicy, ChangelogLayout, CommentParser, Commit, LinksConfig, ParsedCommit, PolicyExample,
+    PolicyRule, RefactorKeyword, Scheme, SemVerError, SemanticVersion, TagFormat,
+    VersionComponents, COG_FILE_NAME, COMMITLINT_FILE_NAMES, DEFAULT_GITMOJI,
+    DEFAULT_RELEASE_COMMIT_TEMPLATE, DEFAULT_SNAPSHOT_TEMPLATE, DEFAULT_TAG_FORMAT,
+    SEMANTIC_RELEASE_FILE_NAMES,
+};
+
+/// Channel of the branches releasing without prerelease, see [`Config::channels`].
+pub const STABLE_CHANNEL: &str = "stable";
+
+/// [`Config`] holds the project configuration read from `semver.toml`.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// [[files]]
+/// path = "package.json"
+///
+/// [[files]]
+/// path = "src/version.rs"
+/// pattern = 'VERSION: &str = "(?P<version>[^"]+)"'
+/// "#).unwrap();
+///
+/// assert_eq!(config.files.len(), 2);
+/// assert_eq!(config.files[1].pattern.as_deref(), Some(r#"VERSION: &str = "(?P<version>[^"]+)""#));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Version files updated by `semver bump`.
+    pub files: Vec<VersionFileConfig>,
+    /// Spelling of refactorings when rendering comments, `refactor` (default) or `refact`.
+    pub refactor_keyword: RefactorKeyword,
+    /// Accepted semantic types, defaults to `feat`, `fix` and `refactor`.
+    pub types: Vec<String>,
+    /// Bump policy, see [`BumpPolicy`].
+    pub policy: PolicyConfig,
+    /// Changelog updated by `semver release`, e.g. `CHANGELOG.md`.
+    pub changelog: Option<PathBuf>,
+    /// Ends the changelog sections with the authors and co-authors of the release,
+    /// see [`crate::render_contributors`].
+    pub changelog_contributors: bool,
+    /// Resolves the GitHub handles of the contributors through the API, with `$GITHUB_TOKEN`,
+    /// see [`crate::GitHubUsers`].
+    pub contributor_handles: bool,
+    /// Grouping of the changelog entries, by type (default), scope or package,
+    /// see [`crate::ChangelogLayout`].
+    pub changelog_layout: ChangelogLayout,
+    /// Packages of a monorepo, see [`crate::Package`].
+    pub packages: BTreeMap<String, PackageConfig>,
+    /// Whether the packages are versioned independently (default) or share one version.
+    pub versioning: Versioning,
+    /// Parses the bullet lines of squash merge bodies as separate comments,
+    /// see [`crate::parse_squashed_commits`].
+    pub expand_squash_merges: bool,
+    /// Commits left out of the history, see [`HistoryConfig`].
+    pub history: HistoryConfig,
+    /// Ticket references, see [`TicketsConfig`].
+    pub tickets: TicketsConfig,
+    /// Gitmoji prefixed commits, see [`GitmojiConfig`].
+    pub gitmoji: GitmojiConfig,
+    /// Version of the first release, when no version is tagged yet, `v0.1.0` by default.
+    pub initial_version: Option<SemanticVersion>,
+    /// Enforces Conventional Commits 1.0.0 exactly, see [`crate::CommentParserBuilder::strict_spec`].
+    pub strict_spec: bool,
+    /// Other spellings of the types, e.g. `feature = "feat"`, matched case insensitively.
+    pub aliases: BTreeMap<String, String>,
+    /// Prerelease channels of the branches, e.g. `next = "rc"` tags `v2.0.0-rc.1` on `next`.
+    /// Branches left out, or mapped to `stable`, release without prerelease.
+    pub channels: BTreeMap<String, String>,
+    /// Template of `semver next --snapshot`, see [`crate::ReleasePlan::snapshot_tag`].
+    pub snapshot_template: Option<String>,
+    /// Template of the release commit messages, see [`Config::release_commit_message`].
+    pub release_commit_template: Option<String>,
+    /// Versioning scheme, semantic (default) or calendar, see [`crate::Scheme`].
+    pub scheme: Scheme,
+    /// Format of the calendar versions, `YYYY.MM.MICRO` by default, see [`crate::calver_version`].
+    pub calver_format: Option<String>,
+    /// Template of the release tags, `v{major}.{minor}.{patch}{-pre}{+build}` by default,
+    /// see [`crate::TagFormat`].
+    pub tag_format: Option<String>,
+    /// Reads the tags missing their patch, or minor and patch components, e.g. `v1.2` or `v1`,
+    /// see [`TagFormat::lenient`].
+    pub lenient_versions: bool,
+    /// Component count of the release tags, `normalize` (default) or `preserve`, see
+    /// [`crate::VersionComponents`].
+    pub version_components: VersionComponents,
+    /// Rules checked by `semver lint`, see [`LintConfig`].
+    pub lint: LintConfig,
+    /// Scopes and the path globs they are inferred from, e.g. `api = ["services/api/**"]`,
+    /// also accepted by `scope-enum`, see [`crate::ScopeMap`].
+    pub scopes: BTreeMap<String, Vec<String>>,
+    /// Links of the changelog, see [`LinksConfig`].
+    pub links: LinksConfig,
+}
+
+/// [`GitmojiConfig`] holds the `[gitmoji]` section, parsing commits such as `✨ add export.`
+/// as the type their emoji stands for.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// types = ["feat", "fix", "refactor", "docs"]
+///
+/// [gitmoji]
+/// enabled = true
+/// types = { ":memo:" = "docs", "📝" = "docs" }
+/// "#).unwrap();
+/// let parser = config.comment_parser();
+///
+/// assert_eq!(parser.parse("📝 usage.").unwrap().to_commit_string(), "docs: usage.");
+/// assert_eq!(parser.parse(":sparkles: export.").unwrap().to_commit_string(), "feat: export.");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitmojiConfig {
+    pub enabled: bool,
+    /// Emoji, as shortcode or character, and the type they stand for, e.g. `":memo:" = "docs"`,
+    /// added to [`crate::DEFAULT_GITMOJI`]. A trailing `!` marks a breaking change.
+    pub types: BTreeMap<String, String>,
+}
+
+/// [`TicketsConfig`] holds the `[tickets]` section, extracting issue tracker keys such as
+/// `PROJ-123` into [`crate::SemanticComment::tickets`].
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// [tickets]
+/// pattern = "PROJ-\\d+"
+/// required = true
+/// "#).unwrap();
+///
+/// let comment = config.comment_parser().parse_message("fix: crash.\n\nRefs: PROJ-42").unwrap();
+/// assert_eq!(comment.tickets, vec!["PROJ-42"]);
+/// assert_eq!(config.lint("fix: crash."), Err(SemVerError::MissingTicket));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TicketsConfig {
+    /// Regex matching a ticket, e.g. `PROJ-\d+` or `[A-Z]+-\d+`.
+    pub pattern: Option<String>,
+    /// Makes `semver lint` reject commits without any ticket.
+    pub required: bool,
+}
+
+impl TicketsConfig {
+    /// [`regex`] compiles the ticket `pattern`, if any.
+    pub fn regex(&self) -> Result<Option<Regex>, SemVerError> {
+        self.pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| SemVerError::InvalidConfig(format!("ticket pattern: {}", e)))
+    }
+}
+
+/// [`ScopePresence`] tells whether the comments of a type carry a scope, see
+/// [`LintConfig::scope`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScopePresence {
+    /// The scope may be given or not.
+    #[default]
+    Optional,
+    /// The scope must be given, e.g. `feat(api): export.`.
+    Required,
+    /// The scope must not be given, e.g. `chore: bump deps.`.
+    Forbidden,
+}
+
+/// [`SubjectCase`] is the case the subjects start with, see [`LintConfig::subject_case`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubjectCase {
+    /// A lower case letter, e.g. `feat: add export.`.
+    Lower,
+    /// An upper case letter, e.g. `feat: Add export.`.
+    Sentence,
+}
+
+/// [`LintConfig`] holds the `[lint]` section, rules checked by `semver lint` on top of the
+/// comment format. Rules are named after their commitlint equivalent, e.g. `scope-enum`.
+/// Lengths are counted in graphemes, so that `é` or `👨‍👩‍👧` count as one character.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// [lint]
+/// scopes = ["api", "cli"]
+/// subject_max_length = 20
+/// warnings = ["subject-max-length"]
+/// "#).unwrap();
+///
+/// assert!(config.lint("feat(web): new page.").is_err());
+/// assert!(config.lint("feat(api): a rather long subject.").is_ok());
+/// assert_eq!(config.lint_warnings("feat(api): a rather long subject.").len(), 1);
+/// ```
+///
+/// Scopes can be required, or forbidden, for every type and then per type:
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// types = ["feat", "fix", "chore"]
+///
+/// [lint]
+/// scope = "required"
+/// scope_by_type = { chore = "forbidden" }
+/// "#).unwrap();
+///
+/// assert!(config.lint("feat: export.").is_err());
+/// assert!(config.lint("feat(api): export.").is_ok());
+/// assert!(config.lint("chore(deps): bump.").is_err());
+/// assert!(config.lint("chore: bump.").is_ok());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Accepted scopes, along with those of the `[scopes]` section, any when both are empty
+    /// (`scope-enum`).
+    pub scopes: Vec<String>,
+    /// Maximum number of characters of the subject, after the type and scope
+    /// (`subject-max-length`).
+    pub subject_max_length: Option<usize>,
+    /// Maximum number of characters of the first line (`header-max-length`).
+    pub header_max_length: Option<usize>,
+    /// Maximum number of characters of the body lines (`body-max-line-length`).
+    pub body_max_line_length: Option<usize>,
+    /// Rules reported as warnings rather than failures, e.g. `["subject-max-length"]`.
+    pub warnings: Vec<String>,
+    /// Whether the comments carry a scope, `optional` (default), `required` or `forbidden`
+    /// (`scope-empty`).
+    pub scope: ScopePresence,
+    /// Overrides of `scope` for some types, e.g. `{ feat = "required", chore = "forbidden" }`.
+    pub scope_by_type: BTreeMap<String, ScopePresence>,
+    /// Case of the first letter of the subject, subjects starting otherwise, e.g. with a
+    /// digit, being accepted (`subject-case`).
+    pub subject_case: Option<SubjectCase>,
+    /// Rejects the subjects separated from the type by more than one space, or by a tab
+    /// (`subject-leading-whitespace`).
+    pub subject_no_leading_whitespace: bool,
+    /// Rejects the subjects starting with a past tense, gerund or third person verb, e.g.
+    /// `added`, `adding` or `adds` rather than `add` (`subject-imperative`).
+    pub subject_imperative: bool,
+    /// Words the subjects must not hold, matched case insensitively, e.g. `["wip", "oops"]`
+    /// (`subject-blocklist`).
+    pub subject_blocklist: Vec<String>,
+    /// Whether the subjects end with a period, `true` requiring it and `false` forbidding it
+    /// (`subject-full-stop`).
+    pub subject_full_stop: Option<bool>,
+}
+
+impl LintConfig {
+    /// [`scope_presence`] returns whether the comments of `semantic_type` carry a scope, its
+    /// override first.
+    pub fn scope_presence(&self, semantic_type: &str) -> ScopePresence {
+        self.scope_by_type
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(semantic_type))
+            .map_or(self.scope, |(_, presence)| *presence)
+    }
+}
+
+/// [`HistoryConfig`] holds the `[history]` section, filtering the commits before parsing.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// types = ["feat", "fix", "chore"]
+///
+/// [history]
+/// bots = ["dependabot", "renovate"]
+/// bot_type = "chore"
+/// "#).unwrap();
+/// let commits = vec![
+///     Commit::new("a1", "Merge branch 'main' into feature"),
+///     Commit::new("b2", "Bump serde from 1.0.1 to 1.0.2").with_author("dependabot[bot] <support@github.com>"),
+///     Commit::new("c3", "not conventional"),
+/// ];
+///
+/// let (parsed, unparsed) = config.parse_commits(&commits);
+/// assert_eq!(parsed[0].comment.to_commit_string(), "chore: Bump serde from 1.0.1 to 1.0.2");
+/// assert_eq!(unparsed.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    /// Skips the merge commits, `true` by default.
+    pub skip_merges: bool,
+    /// Authors of bot commits, matched against the author name and email, e.g. `dependabot`.
+    pub bots: Vec<String>,
+    /// Type given to bot commits that are not conventional, they are skipped when `None`.
+    pub bot_type: Option<String>,
+    /// Markers excluding a commit from releases, case insensitively: a `Key: value` marker
+    /// matches a trailer of the message, any other one is searched in the whole message.
+    /// Defaults to `[skip release]` and the `Release-Note: none` trailer.
+    pub skip_markers: Vec<String>,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            skip_merges: true,
+            bots: vec![],
+            bot_type: None,
+            skip_markers: vec![
+                "[skip release]".to_string(),
+                "Release-Note: none".to_string(),
+            ],
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// Returns `true` when the commit message holds one of the `skip_markers`.
+    pub fn is_skipped(&self, commit: &Commit) -> bool {
+        let message = commit.message.to_lowercase();
+        let body = commit.message.split_once('\n').map_or("", |(_, body)| body);
+        let commit_trailers = trailers(body);
+
+        self.skip_markers
+            .iter()
+            .any(|marker| match trailers(marker).as_slice() {
+                [(key, value)] => commit_trailers.iter().any(|(k, v)| {
+                    k.eq_ignore_ascii_case(key) && v.trim().eq_ignore_ascii_case(value.trim())
+                }),
+                _ => message.contains(&marker.to_lowercase()),
+            })
+    }
+
+    /// Returns `true` when the commit was authored by one of the configured bots.
+    pub fn is_bot(&self, commit: &Commit) -> bool {
+        let author = commit.author.to_lowercase();
+
+        self.bots
+            .iter()
+            .any(|bot| author.contains(&bot.to_lowercase()))
+    }
+}
+
+/// [`Versioning`] tells how the packages of a workspace are versioned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Versioning {
+    /// Every package is released on its own, see [`crate::Package`].
+    #[default]
+    Independent,
+    /// Every package shares the repository version: releases apply the highest bump across
+    /// the workspace and update all the configured manifests, and the requirements they declare
+    /// on each other, together.
+    Lockstep,
+}
+
+/// [`PolicyConfig`] holds the `[policy]` section.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyConfig {
+    pub rules: Vec<PolicyRule>,
+    pub examples: Vec<PolicyExample>,
+}
+
+/// [`PackageConfig`] declares a package of the `[packages]` section, either by its path globs
+/// (`api = ["services/api/**"]`) or as a table also listing the packages it depends on and the
+/// prefix of its tags (`web = { paths = ["web"], depends_on = ["api"], tag_prefix = "web/" }`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum PackageConfig {
+    Paths(Vec<String>),
+    Table(PackageTable),
+}
+
+/// [`PackageTable`] holds the table form of a [`PackageConfig`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageTable {
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub tag_prefix: Option<String>,
+}
+
+impl PackageConfig {
+    pub fn paths(&self) -> &[String] {
+        match self {
+            Self::Paths(paths) => paths,
+            Self::Table(table) => &table.paths,
+        }
+    }
+
+    pub fn depends_on(&self) -> &[String] {
+        match self {
+            Self::Paths(_) => &[],
+            Self::Table(table) => &table.depends_on,
+        }
+    }
+
+    pub fn tag_prefix(&self) -> Option<&str> {
+        match self {
+            Self::Paths(_) => None,
+            Self::Table(table) => table.tag_prefix.as_deref(),
+        }
+    }
+}
+
+/// [`VersionFileConfig`] declares a file holding the project version.
+///
+/// The updater is chosen from the file name unless a `pattern` is given, in which case
+/// the `version` named group (or the first group) of the regex is replaced.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionFileConfig {
+    pub path: PathBuf,
+    pub pattern: Option<String>,
+}
+
+impl Config {
+    /// Names looked up by [`Config::discover`], in order.
+    pub const FILE_NAMES: [&'static str; 2] = ["semver.toml", ".semver.toml"];
+
+    /// [`from_toml_str`] parses a configuration from its toml representation.
+    pub fn from_toml_str(contents: &str) -> Result<Self, SemVerError> {
+        let config: Self =
+            toml::from_str(contents).map_err(|e| SemVerError::InvalidConfig(e.to_string()))?;
+        config.tickets.regex()?;
+        config.tag_format()?;
+        if config.scheme == Scheme::CalVer {
+            calver_version(config.calver_format(), None, (1970, 1))?;
+        }
+
+        Ok(config)
+    }
+
+    /// [`load`] reads the configuration file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SemVerError> {
+        Self::from_toml_str(&fs::read_to_string(path)?)
+    }
+
+    /// [`comment_parser`] builds the [`CommentParser`] accepting the configured types and
+    /// collecting the configured tickets.
+    pub fn comment_parser(&self) -> CommentParser {
+        let mut builder = CommentParser::builder()
+            .strict_spec(self.strict_spec)
+            .refactor_keyword(self.refactor_keyword);
+        if !self.types.is_empty() {
+            builder = builder.types(&self.types);
+        }
+        if !self.aliases.is_empty() {
+            builder = builder.aliases(self.aliases.clone());
+        }
+        if self.gitmoji.enabled {
+            let mut gitmoji = DEFAULT_GITMOJI
+                .iter()
+                .filter(|(emoji, _)| !self.gitmoji.types.contains_key(*emoji))
+                .map(|(emoji, semantic_type)| (emoji.to_string(), semantic_type.to_string()))
+                .collect::<Vec<_>>();
+            gitmoji.extend(self.gitmoji.types.clone());
+            builder = builder.gitmoji(gitmoji);
+        }
+        if let Ok(Some(ticket_pattern)) = self.tickets.regex() {
+            builder = builder.ticket_pattern(ticket_pattern);
+        }
+
+        builder.build()
+    }
+
+    /// [`parse_commits`] parses `commits` with the configured parser, expanding squash merges
+    /// when enabled and leaving out the commits filtered by the `[history]` section,
+    /// skip markers included.
+    pub fn parse_commits(
+        &self,
+        commits: &[Commit],
+    ) -> (Vec<ParsedCommit>, Vec<(Commit, SemVerError)>) {
+        let parser = self.comment_parser();
+
+        parse_each(self.kept_commits(commits), |commit| {
+            self.parse_kept_commit(commit, &parser)
+        })
+    }
+
+    /// Leaves out the commits filtered by the `[history]` section.
+    pub(crate) fn kept_commits<'a>(
+        &'a self,
+        commits: &'a [Commit],
+    ) -> impl Iterator<Item = &'a Commit> + 'a {
+        commits.iter().filter(|commit| {
+            !(self.history.skip_merges && commit.is_merge() || self.is_skipped(commit))
+        })
+    }
+
+    /// Parses a commit kept by [`Config::kept_commits`], bots and squash merges included.
+    pub(crate) fn parse_kept_commit(
+        &self,
+        commit: &Commit,
+        parser: &CommentParser,
+    ) -> Result<Vec<ParsedCommit>, SemVerError> {
+        if self.history.is_bot(commit) && !parser.is_valid(commit.subject()) {
+            return match &self.history.bot_type {
+                Some(bot_type) => Ok(vec![ParsedCommit {
+                    commit: commit.clone(),
+                    comment: parser.parse(&format!("{}: {}", bot_type, commit.subject()))?,
+                }]),
+                None => Ok(vec![]),
+            };
+        }
+        match self.expand_squash_merges {
+            true => parse_squashed_commit(commit, parser),
+            false => parse_commit(commit, parser),
+        }
+    }
+
+    /// [`initial_version`] returns the version of the first release, `v0.1.0` unless configured.
+    pub fn initial_version(&self) -> SemanticVersion {
+        self.initial_version
+            .clone()
+            .unwrap_or(SemanticVersion::new(0, 1, 0))
+    }
+
+    /// [`channel`] returns the prerelease channel of `branch`, `None` for stable releases.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str("[channels]\nmain = \"stable\"\nnext = \"rc\"\n").unwrap();
+    ///
+    /// assert_eq!(config.channel(Some("next")), Some("rc"));
+    /// assert_eq!(config.channel(Some("main")), None);
+    /// assert_eq!(config.channel(None), None);
+    /// ```
+    pub fn channel(&self, branch: Option<&str>) -> Option<&str> {
+        self.channels
+            .get(branch?)
+            .map(String::as_str)
+            .filter(|channel| *channel != STABLE_CHANNEL)
+    }
+
+    /// [`snapshot_template`] returns the configured snapshot template, or the default one.
+    pub fn snapshot_template(&self) -> &str {
+        self.snapshot_template
+            .as_deref()
+            .unwrap_or(DEFAULT_SNAPSHOT_TEMPLATE)
+    }
+
+    /// [`release_commit_template`] returns the configured release commit template, or the
+    /// default one.
+    pub fn release_commit_template(&self) -> &str {
+        self.release_commit_template
+            .as_deref()
+            .unwrap_or(DEFAULT_RELEASE_COMMIT_TEMPLATE)
+    }
+
+    /// [`release_commit_message`] renders the message of the commit releasing `version` as
+    /// `tag`: `{version}` stands for the version without its `v`, and `{tag}` for the tag.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::default();
+    /// let version = SemanticVersion::try_from("v2.0.0-rc.1").unwrap();
+    ///
+    /// assert_eq!(config.release_commit_message("api-v2.0.0-rc.1", &version), "chore(release): v2.0.0-rc.1 [skip ci]");
+    /// assert!(config.is_release_commit(&Commit::new("a1", "chore(release): v1.4.0 [skip ci]")));
+    /// assert!(!config.is_release_commit(&Commit::new("a1", "chore(release): prepare [skip ci]")));
+    /// ```
+    pub fn release_commit_message(&self, tag: &str, version: &SemanticVersion) -> String {
+        let version = String::from(version.clone());
+
+        self.release_commit_template()
+            .replace("{version}", version.trim_start_matches('v'))
+            .replace("{tag}", tag)
+    }
+
+    /// [`is_release_commit`] returns `true` when the subject of `commit` follows the release
+    /// commit template, whatever its version and tag.
+    pub fn is_release_commit(&self, commit: &Commit) -> bool {
+        let template = self.release_commit_template();
+        let subject = commit.subject();
+        // compiles the template only for the few subjects starting like it
+        let start = ["{version}", "{tag}"]
+            .iter()
+            .filter_map(|placeholder| template.find(placeholder))
+            .min()
+            .unwrap_or(template.len());
+        if !template
+            .get(..start)
+            .is_some_and(|start| subject.starts_with(start))
+        {
+            return false;
+        }
+
+        let pattern = regex::escape(template)
+            .replace(r"\{version\}", r"[0-9][0-9A-Za-z.+-]*")
+            .replace(r"\{tag\}", r"\S+");
+        Regex::new(&format!("^{}$", pattern)).is_ok_and(|pattern| pattern.is_match(subject))
+    }
+
+    /// [`is_skipped`] returns `true` when the commit is left out of releases: it holds one of
+    /// the `skip_markers` or is a release commit, see [`Config::is_release_commit`].
+    pub fn is_skipped(&self, commit: &Commit) -> bool {
+        self.history.is_skipped(commit) || self.is_release_commit(commit)
+    }
+
+    /// [`tag_format`] compiles the configured tag format, or the default one, lenient when
+    /// [`Config::lenient_versions`] is set.
+    pub fn tag_format(&self) -> Result<TagFormat, SemVerError> {
+        let format = TagFormat::new(self.tag_format.as_deref().unwrap_or(DEFAULT_TAG_FORMAT))?;
+
+        match self.lenient_versions {
+            true => format.lenient(),
+            false => Ok(format),
+        }
+    }
+
+    /// [`bump_policy`] compiles the configured policy rules.
+    pub fn bump_policy(&self) -> Result<BumpPolicy, SemVerError> {
+        BumpPolicy::new(&self.policy.rules)
+    }
+
+    /// [`discover`] loads the configuration file found in `directory`, then the cocogitto,
+    /// semantic-release or commitlint one, falling back to the default configuration when there is none.
+    pub fn discover(directory: impl AsRef<Path>) -> Result<Self, SemVerError> {
+        let find = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| directory.as_ref().join(name))
+                .find(|path| path.is_file())
+        };
+
+        if let Some(path) = find(&Self::FILE_NAMES) {
+            Self::load(path)
+        } else if let Some(path) = find(&[COG_FILE_NAME]) {
+            Self::load_cog(path)
+        } else if let Some(path) = find(&SEMANTIC_RELEASE_FILE_NAMES) {
+            Self::load_semantic_release(path)
+        } else if let Some(path) = find(&COMMITLINT_FILE_NAMES) {
+            Self::load_commitlint(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_from_toml_str_rejects_unknown_fields() {
+        let error =
+            Config::from_toml_str("[[files]]\npath = \"VERSION\"\nkind = \"plain\"\n").unwrap_err();
+
+        assert!(matches!(error, SemVerError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_config_builds_parser_and_policy() {
+        let config = Config::from_toml_str(
+            "types = [\"feat\", \"docs\"]\n\n[[policy.rules]]\nwhen = \"type == docs\"\nbump = \"patch\"\n",
+        )
+        .unwrap();
+
+        let comment = config.comment_parser().parse("docs: readme.").unwrap();
+        assert_eq!(
+            config.bump_policy().unwrap().evaluate(&comment, None),
+            crate::BumpLevel::Patch
+        );
+        assert!(config.comment_parser().parse("fix: a fix.").is_err());
+    }
+
+    #[test]
+    fn test_config_parser_resolves_aliases_case_insensitively() {
+        let config = Config::from_toml_str(
+            "types = [\"feat\", \"fix\", \"docs\"]\n\n[aliases]\nbugfix = \"fix\"\nDoc = \"docs\"\n",
+        )
+        .unwrap();
+        let parser = config.comment_parser();
+
+        assert_eq!(
+            parser.parse("BugFix: crash.").unwrap().to_commit_string(),
+            "fix: crash."
+        );
+        assert_eq!(
+            parser.parse("doc: usage.").unwrap().to_commit_string(),
+            "docs: usage."
+        );
+        assert_eq!(
+            parser.parse("Chore: deps."),
+            Err(SemVerError::UnexpectedSemanticType("chore".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_config_from_empty_toml_str_is_default() {
+        assert_eq!(Config::from_toml_str("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_history_skips_bracket_markers_anywhere_in_the_message() {
+        let history = HistoryConfig::default();
+
+        assert!(history.is_skipped(&Commit::new("a1", "feat: search [Skip Release].")));
+        assert!(history.is_skipped(&Commit::new(
+            "b2",
+            "feat: search.\n\nNot ready yet [skip release]."
+        )));
+        assert!(!history.is_skipped(&Commit::new("c3", "feat: search.")));
+    }
+
+    #[test]
+    fn test_history_skips_trailer_markers_only_as_trailers() {
+        let history = HistoryConfig::default();
+
+        assert!(history.is_skipped(&Commit::new("a1", "feat!: search.\n\nRelease-Note: none")));
+        assert!(history.is_skipped(&Commit::new(
+            "b2",
+            "feat!: search.\n\nSome details.\n\nrelease-note: None\nRefs: #12"
+        )));
+        assert!(!history.is_skipped(&Commit::new(
+            "c3",
+            "feat!: search.\n\nRelease-Note: none of the callers need changes."
+        )));
+        assert!(!history.is_skipped(&Commit::new(
+            "d4",
+            "feat!: search.\n\nRelease-Note: none\n\nThe callers need changes."
+        )));
+    }
+
+    #[test]
+    fn test_config_refactor_keyword_selects_the_rendered_spelling() {
+        let default = Config::default().comment_parser();
+        let refact = Config::from_toml_str("refactor_keyword = \"refact\"\n")
+            .unwrap()
+            .comment_parser();
+
+        let comment = "refactor(core): simplify.";
+        assert_eq!(
+            default.parse(comment).unwrap().to_commit_string(),
+            "refactor(core): simplify."
+        );
+        assert_eq!(
+            refact.parse(comment).unwrap().to_commit_string(),
+            "refact(core): simplify."
+        );
+        assert_eq!(
+            refact.parse("revert: refactor: simplify.").unwrap().comment,
+            "refact: simplify."
+        );
+    }
+
+    #[test]
+    fn test_parse_commits_skips_merges_and_bot_commits() {
+        let commits = vec![
+            Commit::new("a1", "Merge branch 'main' into feature"),
+            Commit::new("b2", "Bump serde from 1.0.1 to 1.0.2")
+                .with_author("dependabot[bot] <support@github.com>"),
+            Commit::new("c3", "fix(deps): bump ureq.")
+                .with_author("Renovate Bot <bot@renovateapp.com>"),
+            Commit::new("d4", "feat: search."),
+        ];
+        let subjects = |config: &Config| {
+            let (parsed, unparsed) = config.parse_commits(&commits);
+            (
+                parsed
+                    .iter()
+                    .map(|parsed| parsed.comment.to_commit_string())
+                    .collect::<Vec<_>>(),
+                unparsed.len(),
+            )
+        };
+
+        let config = Config::from_toml_str(
+            "types = [\"feat\", \"fix\", \"chore\"]\n\n[history]\nbots = [\"dependabot\", \"renovate\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            subjects(&config),
+            (
+                vec![
+                    "fix(deps): bump ureq.".to_string(),
+                    "feat: search.".to_string()
+                ],
+                0
+            )
+        );
+
+        let config = Config::from_toml_str(
+            "types = [\"feat\", \"fix\", \"chore\"]\n\n[history]\nskip_merges = false\nbots = [\"dependabot\"]\nbot_type = \"chore\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            subjects(&config),
+            (
+                vec![
+                    "chore: Bump serde from 1.0.1 to 1.0.2".to_string(),
+                    "fix(deps): bump ureq.".to_string(),
+                    "feat: search.".to_string()
+                ],
+                1
+            )
+        );
+    }
+}
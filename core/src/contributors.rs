@@ -0,0 +1,117 @@
+use serde::Serialize;
+
+use crate::ParsedCommit;
+
+/// Domain of the private commit emails of GitHub, `<id>+<login>@users.noreply.github.com`.
+const GITHUB_NOREPLY_DOMAIN: &str = "@users.noreply.github.com";
+
+/// [`Contributor`] is an author or co-author of the commits of a release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Contributor {
+    pub name: String,
+    pub email: Option<String>,
+    /// GitHub login, e.g. `octocat`, rendered as `@octocat`.
+    pub handle: Option<String>,
+    /// Commit authored by the contributor, `None` when they only co-authored commits.
+    pub sha: Option<String>,
+}
+
+impl Contributor {
+    /// [`parse`] reads a contributor written `Name <email>`, its handle taken from GitHub
+    /// noreply emails.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let contributor = Contributor::parse("Mona <1+octocat@users.noreply.github.com>", None);
+    ///
+    /// assert_eq!(contributor.name, "Mona");
+    /// assert_eq!(contributor.handle.as_deref(), Some("octocat"));
+    /// assert_eq!(Contributor::parse("Joe", None).email, None);
+    /// ```
+    pub fn parse(contributor: &str, sha: Option<&str>) -> Self {
+        let (name, email) = match contributor.split_once(" <") {
+            Some((name, email)) => (name.trim(), Some(email.trim_end_matches('>').trim())),
+            None => (contributor.trim(), None),
+        };
+        let handle = email
+            .and_then(|email| email.strip_suffix(GITHUB_NOREPLY_DOMAIN))
+            .map(|user| {
+                user.split_once('+')
+                    .map_or(user, |(_, login)| login)
+                    .to_string()
+            });
+
+        Self {
+            name: name.to_string(),
+            email: email.filter(|email| !email.is_empty()).map(str::to_string),
+            handle,
+            sha: sha.map(str::to_string),
+        }
+    }
+}
+
+/// [`collect_contributors`] returns the authors and `Co-authored-by` trailers of the commits,
+/// without duplicates, in order of appearance.
+pub fn collect_contributors(commits: &[ParsedCommit]) -> Vec<Contributor> {
+    let mut contributors: Vec<Contributor> = vec![];
+    for parsed in commits {
+        let author = Contributor::parse(&parsed.commit.author, Some(&parsed.commit.sha));
+        let co_authors = parsed
+            .comment
+            .co_authors()
+            .into_iter()
+            .map(|co_author| Contributor::parse(co_author, None));
+
+        for contributor in std::iter::once(author).chain(co_authors) {
+            if contributor.name.is_empty() {
+                continue;
+            }
+            match contributors.iter_mut().find(|c| c.name == contributor.name) {
+                Some(known) => {
+                    known.email = known.email.take().or(contributor.email);
+                    known.handle = known.handle.take().or(contributor.handle);
+                    known.sha = known.sha.take().or(contributor.sha);
+                }
+                None => contributors.push(contributor),
+            }
+        }
+    }
+
+    contributors
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CommentParser, Commit};
+
+    use super::*;
+
+    #[test]
+    fn test_collect_contributors_merges_authors_and_co_authors() {
+        let parsed = |sha: &str, author: &str, message: &str| ParsedCommit {
+            commit: Commit::new(sha, message).with_author(author),
+            comment: CommentParser::default().parse_message(message).unwrap(),
+        };
+        let commits = vec![
+            parsed(
+                "a1",
+                "Joe <joe@example.com>",
+                "feat: pairing.\n\nCo-authored-by: Mona <7+octocat@users.noreply.github.com>",
+            ),
+            parsed("b2", "Mona <mona@example.com>", "fix: a fix."),
+            parsed("c3", "Joe <joe@example.com>", "fix: another fix."),
+        ];
+
+        let contributors = collect_contributors(&commits);
+        assert_eq!(contributors.len(), 2);
+        assert_eq!(contributors[0].sha.as_deref(), Some("a1"));
+        assert_eq!(contributors[1].name, "Mona");
+        assert_eq!(contributors[1].handle.as_deref(), Some("octocat"));
+        assert_eq!(
+            contributors[1].email.as_deref(),
+            Some("7+octocat@users.noreply.github.com")
+        );
+        assert_eq!(contributors[1].sha.as_deref(), Some("b2"));
+    }
+}
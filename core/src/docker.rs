@@ -0,0 +1,64 @@
+use crate::SemanticVersion;
+
+/// [`docker_tags`] returns the container image tags of `version`, each rendered in `template`
+/// where `{tag}` stands for the tag: `1`, `1.4`, `1.4.2` and `latest` for a release, only the
+/// full version for a prerelease. Build metadata is joined with `-`, `+` being invalid in tags.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let version = SemanticVersion::try_from("v1.4.2").unwrap();
+/// assert_eq!(docker_tags(&version, "{tag}"), vec!["1", "1.4", "1.4.2", "latest"]);
+///
+/// let version = SemanticVersion::try_from("v2.0.0-rc.1").unwrap();
+/// assert_eq!(docker_tags(&version, "ghcr.io/team/app:{tag}"), vec!["ghcr.io/team/app:2.0.0-rc.1"]);
+/// ```
+pub fn docker_tags(version: &SemanticVersion, template: &str) -> Vec<String> {
+    let full = String::from(version.clone());
+    let full = full.trim_start_matches('v').replace('+', "-");
+    let tags = match version.is_prerelease() {
+        true => vec![full],
+        false => vec![
+            version.major.to_string(),
+            format!("{}.{}", version.major, version.minor),
+            full,
+            "latest".to_string(),
+        ],
+    };
+
+    tags.into_iter()
+        .map(|tag| template.replace("{tag}", &tag))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_docker_tags_of_releases_prereleases_and_build_metadata() {
+        let tags = |version: &str, template: &str| {
+            docker_tags(&SemanticVersion::try_from(version).unwrap(), template)
+        };
+
+        assert_eq!(tags("v1.4.2", "{tag}"), vec!["1", "1.4", "1.4.2", "latest"]);
+        assert_eq!(
+            tags("v0.1.0", "ghcr.io/team/app:{tag}"),
+            vec![
+                "ghcr.io/team/app:0",
+                "ghcr.io/team/app:0.1",
+                "ghcr.io/team/app:0.1.0",
+                "ghcr.io/team/app:latest"
+            ]
+        );
+        assert_eq!(tags("v2.0.0-rc.1", "{tag}"), vec!["2.0.0-rc.1"]);
+        assert_eq!(
+            tags("v2.0.0-rc.1+sha.abc1234", "app:{tag}"),
+            vec!["app:2.0.0-rc.1-sha.abc1234"]
+        );
+        assert_eq!(
+            tags("v1.4.2+build.5", "{tag}"),
+            vec!["1", "1.4", "1.4.2-build.5", "latest"]
+        );
+    }
+}
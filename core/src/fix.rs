@@ -0,0 +1,213 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::{fmt, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::{group, literal_regex, Config, SubjectCase};
+
+/// Header of a conventional comment as written by hand, loose enough to read the mistakes
+/// [`Config::fix_message`] repairs.
+static LOOSE_HEADER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    literal_regex(
+        r"^(?P<type>[a-zA-Z0-9_]+)(?P<scope>\([^()]*\))?(?P<breaking>!)?(?P<colon>:)?(?P<space>[ \t]*)(?P<subject>.*)$",
+    )
+});
+
+/// [`MessageFix`] is a change [`Config::fix_message`] made to a commit message.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageFix {
+    /// The type was lower cased or resolved from an alias, e.g. `Feature` as `feat`.
+    Type { from: String, to: String },
+    /// The subject was separated from the type by exactly `: `.
+    Separator,
+    /// The first letter of the subject was cased as `subject-case` requires.
+    SubjectCase(SubjectCase),
+    /// The trailing period of the subject was added, or removed, as `subject-full-stop`
+    /// requires.
+    FullStop(bool),
+}
+
+impl fmt::Display for MessageFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageFix::Type { from, to } => write!(f, "replaced the type `{}` by `{}`", from, to),
+            MessageFix::Separator => write!(f, "separated the subject with `: `"),
+            MessageFix::SubjectCase(SubjectCase::Lower) => {
+                write!(f, "lower cased the subject")
+            }
+            MessageFix::SubjectCase(SubjectCase::Sentence) => {
+                write!(f, "capitalized the subject")
+            }
+            MessageFix::FullStop(true) => write!(f, "added the trailing period"),
+            MessageFix::FullStop(false) => write!(f, "removed the trailing period"),
+        }
+    }
+}
+
+/// [`FixedMessage`] is the result of [`Config::fix_message`]: the message along with the
+/// fixes it took, empty when there was nothing to fix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedMessage {
+    pub message: String,
+    pub fixes: Vec<MessageFix>,
+}
+
+impl Config {
+    /// [`fix_message`] rewrites the header of `message` to repair the lint violations with a
+    /// single right answer: the casing and aliases of the type, the separator after it and,
+    /// when the `[lint]` section sets them, the subject case and trailing period. The body is
+    /// kept as is, and so are the headers it cannot read, e.g. gitmoji or unknown types.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str("[lint]\nsubject_full_stop = false").unwrap();
+    /// let fixed = config.fix_message("Feat(api):add endpoints.\n\nDetails.\n");
+    ///
+    /// assert_eq!(fixed.message, "feat(api): add endpoints\n\nDetails.\n");
+    /// assert_eq!(
+    ///     fixed.fixes,
+    ///     vec![
+    ///         MessageFix::Type { from: "Feat".to_string(), to: "feat".to_string() },
+    ///         MessageFix::Separator,
+    ///         MessageFix::FullStop(false),
+    ///     ]
+    /// );
+    /// assert!(config.fix_message(&fixed.message).fixes.is_empty());
+    /// ```
+    pub fn fix_message(&self, message: &str) -> FixedMessage {
+        let (header, rest) = match message.find(['\r', '\n']) {
+            Some(end) => message.split_at(end),
+            None => (message, ""),
+        };
+        let unchanged = || FixedMessage {
+            message: message.to_string(),
+            fixes: vec![],
+        };
+        let Some(captures) = LOOSE_HEADER_PATTERN.captures(header) else {
+            return unchanged();
+        };
+        let breaking = captures.name("breaking").is_some();
+        let colon = captures.name("colon").is_some();
+        if !breaking && !colon {
+            return unchanged();
+        }
+
+        let parser = self.comment_parser();
+        let written = group(&captures, "type");
+        let semantic_type = parser.canonical_type(written);
+        if !parser.knows_type(&semantic_type) {
+            return unchanged();
+        }
+        let mut fixes = vec![];
+        if semantic_type != written {
+            fixes.push(MessageFix::Type {
+                from: written.to_string(),
+                to: semantic_type.clone(),
+            });
+        }
+
+        let subject = group(&captures, "subject").trim_end();
+        if !colon || group(&captures, "space") != " " && !subject.is_empty() {
+            fixes.push(MessageFix::Separator);
+        }
+        let mut subject = subject.to_string();
+        if let Some(case) = self.lint.subject_case {
+            let mut chars = subject.chars();
+            if let Some(first) = chars.next().filter(|c| c.is_alphabetic()) {
+                let cased: String = match case {
+                    SubjectCase::Lower => first.to_lowercase().collect(),
+                    SubjectCase::Sentence => first.to_uppercase().collect(),
+                };
+                if cased != first.to_string() {
+                    subject = cased + chars.as_str();
+                    fixes.push(MessageFix::SubjectCase(case));
+                }
+            }
+        }
+        match self.lint.subject_full_stop {
+            Some(true) if !subject.is_empty() && !subject.ends_with('.') => {
+                subject.push('.');
+                fixes.push(MessageFix::FullStop(true));
+            }
+            Some(false) if subject.ends_with('.') => {
+                subject = subject.trim_end_matches('.').trim_end().to_string();
+                fixes.push(MessageFix::FullStop(false));
+            }
+            _ => {}
+        }
+
+        if fixes.is_empty() {
+            return unchanged();
+        }
+        FixedMessage {
+            message: format!(
+                "{}{}{}:{}{}{}",
+                semantic_type,
+                group(&captures, "scope"),
+                if breaking { "!" } else { "" },
+                if subject.is_empty() { "" } else { " " },
+                subject,
+                rest
+            ),
+            fixes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fix_message_repairs_the_header_and_keeps_the_rest() {
+        let config = Config::from_toml_str(
+            r#"
+aliases = { feature = "feat" }
+
+[lint]
+subject_case = "lower"
+subject_full_stop = false
+"#,
+        )
+        .unwrap();
+
+        let cases = [
+            ("feat: add endpoints", "feat: add endpoints"),
+            ("Feature:  Add endpoints.", "feat: add endpoints"),
+            ("refact(core)!Simplify", "refactor(core)!: simplify"),
+            (
+                "FIX: handle errors...\n\nBody.\n",
+                "fix: handle errors\n\nBody.\n",
+            ),
+            ("feat:add endpoints\r\n", "feat: add endpoints\r\n"),
+            // left alone: unknown type, no separator, gitmoji, git revert
+            ("WIP: Add endpoints.", "WIP: Add endpoints."),
+            ("Add endpoints.", "Add endpoints."),
+            ("✨ Add endpoints.", "✨ Add endpoints."),
+            (
+                r#"Revert "feat: add endpoints""#,
+                r#"Revert "feat: add endpoints""#,
+            ),
+        ];
+        for (message, expected) in cases {
+            let fixed = config.fix_message(message);
+            assert_eq!(fixed.message, expected, "{}", message);
+            assert_eq!(fixed.fixes.is_empty(), message == expected, "{}", message);
+            assert!(config.fix_message(&fixed.message).fixes.is_empty());
+        }
+
+        let config = Config::from_toml_str("[lint]\nsubject_full_stop = true").unwrap();
+        assert_eq!(
+            config.fix_message("fix: handle errors").message,
+            "fix: handle errors."
+        );
+    }
+}
@@ -0,0 +1,532 @@
+use std::fmt;
+
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use crate::{
+    env_var, parse_remote, Contributor, Forge, ForgeKind, ForgeRelease, Git, Reference,
+    ReferenceKind, SemVerError, Tag, Vcs,
+};
+
+/// Number of tags fetched per page.
+const PAGE_SIZE: usize = 100;
+
+/// [`forge_from_env`] configures the backend of `kind` for the repository, see [`GitLab::from_env`].
+///
+/// `url` overrides the detected instance of self-hosted forges.
+pub fn forge_from_env(
+    kind: ForgeKind,
+    url: Option<&str>,
+    git: &Git,
+) -> Result<Box<dyn Forge>, SemVerError> {
+    match kind {
+        ForgeKind::GitLab => Ok(Box::new(GitLab::from_env(url, git)?)),
+        ForgeKind::Gitea => Ok(Box::new(Gitea::from_env(url, git)?)),
+    }
+}
+
+/// [`GitLab`] publishes to a GitLab instance through its REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitLab {
+    /// Base url of the instance, e.g. `https://gitlab.com`.
+    pub url: String,
+    /// Id or full path (e.g. `group/project`) of the project.
+    pub project: String,
+    /// Url of the web page of the project, e.g. `https://gitlab.com/group/project`.
+    pub web_url: String,
+    pub token: GitLabToken,
+}
+
+/// [`GitLabToken`] holds the token authenticating the API calls.
+#[derive(Clone, PartialEq, Eq)]
+pub enum GitLabToken {
+    /// `$CI_JOB_TOKEN` of a GitLab CI job.
+    Job(String),
+    /// A personal, project or group access token (`$GITLAB_TOKEN`).
+    Private(String),
+}
+
+impl fmt::Debug for GitLabToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Job(_) => write!(f, "Job(***)"),
+            Self::Private(_) => write!(f, "Private(***)"),
+        }
+    }
+}
+
+impl GitLab {
+    /// [`from_env`] configures the GitLab backend of the repository.
+    ///
+    /// The project is read from `$CI_PROJECT_ID`, falling back to the `origin` remote, and
+    /// the instance from `url`, `$CI_SERVER_URL` or the remote. The token is `$GITLAB_TOKEN`,
+    /// falling back to `$CI_JOB_TOKEN`.
+    pub fn from_env(url: Option<&str>, git: &Git) -> Result<Self, SemVerError> {
+        let remote = git
+            .run(["remote", "get-url", "origin"])
+            .ok()
+            .and_then(|remote| parse_remote(&remote));
+
+        let project = match env_var("CI_PROJECT_ID") {
+            Some(id) => id,
+            None => remote
+                .as_ref()
+                .map(|(_, path)| path.clone())
+                .ok_or_else(|| {
+                    SemVerError::ForgeError(
+                        "unable to detect the GitLab project, set `CI_PROJECT_ID`".to_string(),
+                    )
+                })?,
+        };
+        let url = match url.map(str::to_string).or_else(|| env_var("CI_SERVER_URL")) {
+            Some(url) => url,
+            None => remote.as_ref().map(|(url, _)| url.clone()).ok_or_else(|| {
+                SemVerError::ForgeError(
+                    "unable to detect the GitLab instance, pass `--gitlab-url`".to_string(),
+                )
+            })?,
+        };
+        let token = match (env_var("GITLAB_TOKEN"), env_var("CI_JOB_TOKEN")) {
+            (Some(token), _) => GitLabToken::Private(token),
+            (None, Some(token)) => GitLabToken::Job(token),
+            (None, None) => {
+                return Err(SemVerError::ForgeError(
+                    "missing `GITLAB_TOKEN` or `CI_JOB_TOKEN`".to_string(),
+                ))
+            }
+        };
+
+        let url = url.trim_end_matches('/').to_string();
+        let web_url = match env_var("CI_PROJECT_URL") {
+            Some(web_url) => web_url,
+            None => match remote {
+                Some((_, path)) => format!("{}/{}", url, path),
+                None => format!("{}/{}", url, project),
+            },
+        };
+
+        Ok(Self {
+            url,
+            project,
+            web_url,
+            token,
+        })
+    }
+
+    /// Returns the url of the API `endpoint` of the project.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let gitlab = GitLab {
+    ///     url: "https://gitlab.example.com".to_string(),
+    ///     project: "group/sub group/app".to_string(),
+    ///     web_url: "https://gitlab.example.com/group/sub group/app".to_string(),
+    ///     token: GitLabToken::Job("token".to_string()),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     gitlab.endpoint("releases"),
+    ///     "https://gitlab.example.com/api/v4/projects/group%2Fsub%20group%2Fapp/releases"
+    /// );
+    /// ```
+    pub fn endpoint(&self, endpoint: &str) -> String {
+        format!(
+            "{}/api/v4/projects/{}/{}",
+            self.url,
+            encode_path_segment(&self.project),
+            endpoint
+        )
+    }
+
+    fn request(&self, method: &str, endpoint: &str) -> ureq::Request {
+        let request = ureq::request(method, &self.endpoint(endpoint));
+
+        match &self.token {
+            GitLabToken::Job(token) => request.set("JOB-TOKEN", token),
+            GitLabToken::Private(token) => request.set("PRIVATE-TOKEN", token),
+        }
+    }
+}
+
+impl Forge for GitLab {
+    fn name(&self) -> &str {
+        "gitlab"
+    }
+
+    fn compare_url(&self, from: &str, to: &str) -> String {
+        format!("{}/-/compare/{}...{}", self.web_url, from, to)
+    }
+
+    fn commit_url(&self, sha: &str) -> String {
+        format!("{}/-/commit/{}", self.web_url, sha)
+    }
+
+    fn reference_url(&self, reference: &Reference) -> Option<String> {
+        match reference.kind {
+            ReferenceKind::Issue => Some(format!("{}/-/issues/{}", self.web_url, reference.number)),
+            ReferenceKind::MergeRequest => Some(format!(
+                "{}/-/merge_requests/{}",
+                self.web_url, reference.number
+            )),
+            ReferenceKind::GitHub => None,
+        }
+    }
+
+    fn create_tag(&self, name: &str, sha: &str, message: &str) -> Result<(), SemVerError> {
+        send(
+            "GitLab",
+            self.request("POST", "repository/tags"),
+            Some(json!({ "tag_name": name, "ref": sha, "message": message })),
+        )?;
+
+        Ok(())
+    }
+
+    fn create_release(&self, release: &ForgeRelease) -> Result<String, SemVerError> {
+        let response = send(
+            "GitLab",
+            self.request("POST", "releases"),
+            Some(json!({
+                "tag_name": release.tag,
+                "ref": release.sha,
+                "name": release.name,
+                "description": release.notes,
+            })),
+        )?;
+
+        // GitLab identifies the releases by their tag
+        release_id(response, "/tag_name")
+    }
+
+    fn list_tags(&self) -> Result<Vec<Tag>, SemVerError> {
+        fetch_tags(
+            |page| {
+                let request = self
+                    .request("GET", "repository/tags")
+                    .query("per_page", &PAGE_SIZE.to_string())
+                    .query("page", &page.to_string());
+
+                send("GitLab", request, None)
+            },
+            "/commit/id",
+        )
+    }
+}
+
+/// [`Gitea`] publishes to a Gitea or Forgejo instance through its REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gitea {
+    /// Base url of the instance, e.g. `https://codeberg.org`.
+    pub url: String,
+    /// Full name of the repository, e.g. `owner/repo`.
+    pub repository: String,
+    pub token: String,
+}
+
+impl Gitea {
+    /// [`from_env`] configures the Gitea backend of the repository.
+    ///
+    /// The repository and instance are read from the `origin` remote, `url` overriding the
+    /// instance. The token is `$GITEA_TOKEN`, falling back to `$FORGEJO_TOKEN`.
+    pub fn from_env(url: Option<&str>, git: &Git) -> Result<Self, SemVerError> {
+        let remote = git
+            .run(["remote", "get-url", "origin"])
+            .ok()
+            .and_then(|remote| parse_remote(&remote))
+            .ok_or_else(|| {
+                SemVerError::ForgeError("unable to detect the Gitea repository".to_string())
+            })?;
+        let token = env_var("GITEA_TOKEN")
+            .or_else(|| env_var("FORGEJO_TOKEN"))
+            .ok_or_else(|| {
+                SemVerError::ForgeError("missing `GITEA_TOKEN` or `FORGEJO_TOKEN`".to_string())
+            })?;
+
+        Ok(Self {
+            url: url.unwrap_or(&remote.0).trim_end_matches('/').to_string(),
+            repository: remote.1,
+            token,
+        })
+    }
+
+    /// Returns the url of the API `endpoint` of the repository.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let gitea = Gitea {
+    ///     url: "https://codeberg.org".to_string(),
+    ///     repository: "owner/app".to_string(),
+    ///     token: "token".to_string(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     gitea.endpoint("releases"),
+    ///     "https://codeberg.org/api/v1/repos/owner/app/releases"
+    /// );
+    /// ```
+    pub fn endpoint(&self, endpoint: &str) -> String {
+        let repository = self
+            .repository
+            .split('/')
+            .map(encode_path_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        format!("{}/api/v1/repos/{}/{}", self.url, repository, endpoint)
+    }
+
+    fn request(&self, method: &str, endpoint: &str) -> ureq::Request {
+        ureq::request(method, &self.endpoint(endpoint))
+            .set("Authorization", &format!("token {}", self.token))
+    }
+}
+
+impl Forge for Gitea {
+    fn name(&self) -> &str {
+        "gitea"
+    }
+
+    fn compare_url(&self, from: &str, to: &str) -> String {
+        format!("{}/{}/compare/{}...{}", self.url, self.repository, from, to)
+    }
+
+    fn commit_url(&self, sha: &str) -> String {
+        format!("{}/{}/commit/{}", self.url, self.repository, sha)
+    }
+
+    fn reference_url(&self, reference: &Reference) -> Option<String> {
+        // Gitea redirects issue numbers of pull requests to the pull request
+        match reference.kind {
+            ReferenceKind::Issue => Some(format!(
+                "{}/{}/issues/{}",
+                self.url, self.repository, reference.number
+            )),
+            _ => None,
+        }
+    }
+
+    fn create_tag(&self, name: &str, sha: &str, message: &str) -> Result<(), SemVerError> {
+        send(
+            "Gitea",
+            self.request("POST", "tags"),
+            Some(json!({ "tag_name": name, "target": sha, "message": message })),
+        )?;
+
+        Ok(())
+    }
+
+    fn create_release(&self, release: &ForgeRelease) -> Result<String, SemVerError> {
+        let response = send(
+            "Gitea",
+            self.request("POST", "releases"),
+            Some(json!({
+                "tag_name": release.tag,
+                "target_commitish": release.sha,
+                "name": release.name,
+                "body": release.notes,
+            })),
+        )?;
+
+        release_id(response, "/id")
+    }
+
+    fn list_tags(&self) -> Result<Vec<Tag>, SemVerError> {
+        fetch_tags(
+            |page| {
+                let request = self
+                    .request("GET", "tags")
+                    .query("limit", &PAGE_SIZE.to_string())
+                    .query("page", &page.to_string());
+
+                send("Gitea", request, None)
+            },
+            "/commit/sha",
+        )
+    }
+}
+
+/// [`GitHubUsers`] resolves the GitHub handles of contributors through the API: from the
+/// commits they authored, or from their email for co-authors.
+#[derive(Debug, Clone)]
+pub struct GitHubUsers {
+    /// Url of the API, e.g. `https://api.github.com`.
+    pub api_url: String,
+    /// Full name of the repository, e.g. `owner/repo`.
+    pub repository: String,
+    pub token: String,
+}
+
+impl GitHubUsers {
+    /// [`from_env`] reads the repository from `$GITHUB_REPOSITORY`, falling back to the
+    /// `origin` remote, the API from `$GITHUB_API_URL` and the token from `$GITHUB_TOKEN`.
+    pub fn from_env(vcs: &dyn Vcs) -> Result<Self, SemVerError> {
+        let repository = env_var("GITHUB_REPOSITORY")
+            .or_else(|| {
+                let remote = vcs.remote_url()?;
+                parse_remote(&remote).map(|(_, path)| path)
+            })
+            .ok_or_else(|| {
+                SemVerError::ForgeError("unable to detect the GitHub repository".to_string())
+            })?;
+        let token = env_var("GITHUB_TOKEN")
+            .ok_or_else(|| SemVerError::ForgeError("missing `GITHUB_TOKEN`".to_string()))?;
+
+        Ok(Self {
+            api_url: env_var("GITHUB_API_URL")
+                .unwrap_or_else(|| "https://api.github.com".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            repository,
+            token,
+        })
+    }
+
+    /// [`resolve`] sets the handle of the `contributors` without one. The ones the API does not
+    /// know, e.g. with a private email, are left unchanged.
+    pub fn resolve(&self, contributors: &mut [Contributor]) {
+        for contributor in contributors.iter_mut().filter(|c| c.handle.is_none()) {
+            let login = match (&contributor.sha, &contributor.email) {
+                (Some(sha), _) => self
+                    .get(&format!("repos/{}/commits/{}", self.repository, sha), &[])
+                    .map(|commit| login(&commit, "/author/login")),
+                (None, Some(email)) => self
+                    .get("search/users", &[("q", &format!("{} in:email", email))])
+                    .map(|users| login(&users, "/items/0/login")),
+                (None, None) => continue,
+            };
+
+            match login {
+                Ok(Some(login)) => {
+                    debug!(name = %contributor.name, %login, "resolved contributor");
+                    contributor.handle = Some(login);
+                }
+                Ok(None) => debug!(name = %contributor.name, "unknown contributor"),
+                Err(error) => warn!(name = %contributor.name, %error, "unresolved contributor"),
+            }
+        }
+    }
+
+    fn get(&self, endpoint: &str, query: &[(&str, &str)]) -> Result<Value, SemVerError> {
+        let request = query.iter().fold(
+            ureq::get(&format!("{}/{}", self.api_url, endpoint))
+                .set("Authorization", &format!("Bearer {}", self.token))
+                .set("Accept", "application/vnd.github+json"),
+            |request, (key, value)| request.query(key, value),
+        );
+
+        send("GitHub", request, None)?
+            .into_json()
+            .map_err(|e| SemVerError::ForgeError(e.to_string()))
+    }
+}
+
+fn login(value: &Value, pointer: &str) -> Option<String> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Sends `request`, turning error statuses into [`SemVerError::ForgeError`].
+pub(crate) fn send(
+    forge: &str,
+    request: ureq::Request,
+    body: Option<Value>,
+) -> Result<ureq::Response, SemVerError> {
+    let response = match body {
+        Some(body) => request.send_json(body),
+        None => request.call(),
+    };
+
+    match response {
+        Ok(response) => Ok(response),
+        Err(ureq::Error::Status(status, response)) => Err(SemVerError::ForgeError(format!(
+            "{} answered {}: {}",
+            forge,
+            status,
+            response.into_string().unwrap_or_default()
+        ))),
+        Err(error) => Err(SemVerError::ForgeError(error.to_string())),
+    }
+}
+
+/// Reads the identifier at `pointer` in the created release.
+fn release_id(response: ureq::Response, pointer: &str) -> Result<String, SemVerError> {
+    let release: Value = response
+        .into_json()
+        .map_err(|e| SemVerError::ForgeError(e.to_string()))?;
+
+    match release.pointer(pointer) {
+        Some(Value::String(id)) => Ok(id.clone()),
+        Some(Value::Number(id)) => Ok(id.to_string()),
+        _ => Err(SemVerError::ForgeError(format!(
+            "the created release has no `{}`",
+            pointer.trim_start_matches('/')
+        ))),
+    }
+}
+
+/// Fetches the pages of tags until a partial one, `sha` points to the commit in a tag.
+fn fetch_tags(
+    page: impl Fn(usize) -> Result<ureq::Response, SemVerError>,
+    sha: &str,
+) -> Result<Vec<Tag>, SemVerError> {
+    let mut tags = vec![];
+    for number in 1.. {
+        let response: Vec<Value> = page(number)?
+            .into_json()
+            .map_err(|e| SemVerError::ForgeError(e.to_string()))?;
+
+        tags.extend(parse_tags(&response, sha));
+        if response.len() < PAGE_SIZE {
+            break;
+        }
+    }
+
+    Ok(tags)
+}
+
+fn parse_tags(tags: &[Value], sha: &str) -> Vec<Tag> {
+    tags.iter()
+        .filter_map(|tag| {
+            Some(Tag::new(
+                tag.get("name")?.as_str()?,
+                tag.pointer(sha)?.as_str()?,
+            ))
+        })
+        .collect()
+}
+
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags_reads_the_commit_of_gitlab_and_gitea_tags() {
+        let gitlab = json!([{ "name": "v1.0.0", "commit": { "id": "a1" } }, { "name": "broken" }]);
+        let gitea = json!([{ "name": "v1.0.0", "id": "t1", "commit": { "sha": "a1" } }]);
+
+        assert_eq!(
+            parse_tags(gitlab.as_array().unwrap(), "/commit/id"),
+            vec![Tag::new("v1.0.0", "a1")]
+        );
+        assert_eq!(
+            parse_tags(gitea.as_array().unwrap(), "/commit/sha"),
+            vec![Tag::new("v1.0.0", "a1")]
+        );
+    }
+}
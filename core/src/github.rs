@@ -0,0 +1,62 @@
+use std::{env, fs::OpenOptions, io::Write};
+
+use crate::{updaters::bare_version, ReleasePlan, SemVerError};
+
+/// Environment variable naming the file GitHub Actions reads step outputs from.
+pub const GITHUB_OUTPUT_ENV: &str = "GITHUB_OUTPUT";
+
+/// [`release_outputs`] returns the step outputs describing a release:
+/// `version` (without `v`, with its prerelease if any), `tag`, `bump`, `released` and `security`.
+pub fn release_outputs(plan: &ReleasePlan, released: bool) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "version",
+            match &plan.prerelease {
+                Some(prerelease) => format!("{}-{}", bare_version(&plan.version), prerelease),
+                None => bare_version(&plan.version),
+            },
+        ),
+        ("tag", plan.tag.clone()),
+        ("bump", plan.bump.to_string()),
+        ("released", released.to_string()),
+        ("security", plan.security.to_string()),
+    ]
+}
+
+/// [`render_github_output`] renders `outputs` as `key=value` lines, the format of the
+/// `$GITHUB_OUTPUT` file, or as `::set-output` workflow commands when `legacy` is set.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let outputs = vec![("version", "1.3.0".to_string()), ("released", "true".to_string())];
+///
+/// assert_eq!(render_github_output(&outputs, false), "version=1.3.0\nreleased=true\n");
+/// assert_eq!(
+///     render_github_output(&outputs, true),
+///     "::set-output name=version::1.3.0\n::set-output name=released::true\n"
+/// );
+/// ```
+pub fn render_github_output(outputs: &[(&str, String)], legacy: bool) -> String {
+    outputs
+        .iter()
+        .map(|(key, value)| match legacy {
+            true => format!("::set-output name={}::{}\n", key, value),
+            false => format!("{}={}\n", key, value),
+        })
+        .collect()
+}
+
+/// [`write_github_output`] appends `outputs` to the file named by `$GITHUB_OUTPUT`,
+/// falling back to printing `::set-output` commands when it is not set.
+pub fn write_github_output(outputs: &[(&str, String)]) -> Result<(), SemVerError> {
+    match env::var_os(GITHUB_OUTPUT_ENV).filter(|path| !path.is_empty()) {
+        Some(path) => {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            file.write_all(render_github_output(outputs, false).as_bytes())?;
+        }
+        None => print!("{}", render_github_output(outputs, true)),
+    }
+
+    Ok(())
+}
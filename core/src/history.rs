@@ -0,0 +1,537 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::models::literal_regex;
+use crate::{CommentParser, SemVerError, SemanticComment, SemanticType, SemanticVersion};
+
+/// Subjects of the merge commits created by git and forges.
+static MERGE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    literal_regex(r"^Merge (branch|branches|remote-tracking branch|pull request|tag) ")
+});
+
+/// git's own revert body, `This reverts commit <sha>.`.
+static REVERTED_SHA_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| literal_regex(r"This reverts commit (?P<sha>[0-9a-f]{7,40})"));
+
+/// [`Commit`] holds a commit of the repository history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Commit {
+    pub sha: String,
+    pub message: String,
+    /// Author of the commit, `name <email>`.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub author: String,
+    /// Commit date, in seconds since the unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+}
+
+impl Commit {
+    pub fn new(sha: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            sha: sha.into(),
+            message: message.into(),
+            author: String::new(),
+            timestamp: None,
+        }
+    }
+
+    /// [`with_author`] sets the author, e.g. `dependabot[bot] <support@github.com>`.
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// [`with_timestamp`] sets the commit date, in seconds since the unix epoch.
+    pub fn with_timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Returns `true` for the merge commits created by git and forges,
+    /// e.g. `Merge branch 'main'` or `Merge pull request #12 from ...`.
+    pub fn is_merge(&self) -> bool {
+        MERGE_PATTERN.is_match(self.subject())
+    }
+
+    /// Returns the first line of the commit message.
+    pub fn subject(&self) -> &str {
+        self.message.lines().next().unwrap_or_default()
+    }
+
+    /// Returns the sha of the reverted commit, from git's `This reverts commit <sha>.` body.
+    pub fn reverted_sha(&self) -> Option<&str> {
+        REVERTED_SHA_PATTERN
+            .captures(&self.message)
+            .and_then(|captures| captures.name("sha"))
+            .map(|sha| sha.as_str())
+    }
+}
+
+/// [`ParsedCommit`] holds a commit with its parsed subject.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedCommit {
+    pub commit: Commit,
+    pub comment: SemanticComment,
+}
+
+/// [`parse_commits`] parses the subject of every commit with `parser`.
+///
+/// Returns the parsed commits and the commits that could not be parsed with their error.
+pub fn parse_commits(
+    commits: &[Commit],
+    parser: &CommentParser,
+) -> (Vec<ParsedCommit>, Vec<(Commit, SemVerError)>) {
+    parse_each(commits, |commit| parse_commit(commit, parser))
+}
+
+/// [`parse_squashed_commits`] parses commits like [`parse_commits`], expanding squash merges:
+/// when the body holds bullet lines (`* feat: x`, `- fix: y`) that parse, each of them is parsed
+/// as its own comment of the commit, instead of the subject.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let commits = vec![Commit::new("a1", "Add search (#12)\n\n* feat: search.\n* fix: typo.\n* wip")];
+///
+/// let (parsed, unparsed) = parse_squashed_commits(&commits, &CommentParser::default());
+/// assert_eq!(
+///     parsed.iter().map(|p| p.comment.to_commit_string()).collect::<Vec<_>>(),
+///     vec!["feat: search.", "fix: typo."]
+/// );
+/// assert!(unparsed.is_empty());
+/// ```
+pub fn parse_squashed_commits(
+    commits: &[Commit],
+    parser: &CommentParser,
+) -> (Vec<ParsedCommit>, Vec<(Commit, SemVerError)>) {
+    parse_each(commits, |commit| parse_squashed_commit(commit, parser))
+}
+
+/// Parses the message of `commit`.
+pub(crate) fn parse_commit(
+    commit: &Commit,
+    parser: &CommentParser,
+) -> Result<Vec<ParsedCommit>, SemVerError> {
+    Ok(vec![ParsedCommit {
+        commit: commit.clone(),
+        comment: parser.parse_message(&commit.message)?,
+    }])
+}
+
+/// Parses the bullet lines of the body of `commit`, or its message when none parses.
+pub(crate) fn parse_squashed_commit(
+    commit: &Commit,
+    parser: &CommentParser,
+) -> Result<Vec<ParsedCommit>, SemVerError> {
+    let squashed = commit
+        .message
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("* ").or_else(|| line.strip_prefix("- "))
+        })
+        .filter_map(|bullet| parser.parse(bullet.trim()).ok())
+        .map(|comment| ParsedCommit {
+            commit: commit.clone(),
+            comment,
+        })
+        .collect::<Vec<_>>();
+
+    match squashed.is_empty() {
+        true => parse_commit(commit, parser),
+        false => Ok(squashed),
+    }
+}
+
+/// Parses every commit with `parse`, on the rayon thread pool with the `parallel` feature,
+/// keeping the order of the commits and the error of every unparsed one.
+pub(crate) fn parse_each<'a, F>(
+    commits: impl IntoIterator<Item = &'a Commit>,
+    parse: F,
+) -> (Vec<ParsedCommit>, Vec<(Commit, SemVerError)>)
+where
+    F: Fn(&Commit) -> Result<Vec<ParsedCommit>, SemVerError> + Sync,
+{
+    let commits = commits.into_iter().collect::<Vec<_>>();
+    #[cfg(feature = "parallel")]
+    let results = commits
+        .par_iter()
+        .map(|commit| parse(commit))
+        .collect::<Vec<_>>();
+    #[cfg(not(feature = "parallel"))]
+    let results = commits
+        .iter()
+        .map(|commit| parse(commit))
+        .collect::<Vec<_>>();
+
+    let mut parsed = vec![];
+    let mut unparsed = vec![];
+    for (commit, result) in commits.into_iter().zip(results) {
+        match result {
+            Ok(comments) => {
+                for comment in &comments {
+                    debug!(
+                        sha = %commit.sha,
+                        subject = commit.subject(),
+                        breaking = comment.comment.semantic_type.metadata().is_breaking,
+                        "parsed commit"
+                    );
+                }
+                parsed.extend(comments);
+            }
+            Err(error) => {
+                debug!(sha = %commit.sha, subject = commit.subject(), %error, "unparsed commit");
+                unparsed.push((commit.clone(), error));
+            }
+        }
+    }
+
+    (parsed, unparsed)
+}
+
+/// [`cancel_reverts`] drops the reverts and the commits they revert when both belong to `commits`,
+/// so that they do not contribute to the release.
+///
+/// The reverted commit is found by the sha of git's revert body, or else by its comment.
+/// Reverts of commits outside `commits` are kept.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let parse = |sha: &str, message: &str| ParsedCommit {
+///     commit: Commit::new(sha, message),
+///     comment: CommentParser::default().parse(message).unwrap(),
+/// };
+/// let commits = vec![
+///     parse("a1", "feat: add X."),
+///     parse("b2", "fix: a fix."),
+///     parse("c3", "revert: feat: add X."),
+///     parse("d4", "revert: feat: released earlier."),
+/// ];
+///
+/// let kept = cancel_reverts(commits);
+/// assert_eq!(
+///     kept.iter().map(|parsed| parsed.commit.sha.as_str()).collect::<Vec<_>>(),
+///     vec!["b2", "d4"]
+/// );
+/// ```
+pub fn cancel_reverts(commits: Vec<ParsedCommit>) -> Vec<ParsedCommit> {
+    let mut kept: Vec<Option<ParsedCommit>> = vec![];
+
+    for parsed in commits {
+        let SemanticType::Revert(_, reverted) = &parsed.comment.semantic_type else {
+            kept.push(Some(parsed));
+            continue;
+        };
+
+        // every comment of a reverted squash merge shares its sha
+        if let Some(sha) = parsed.commit.reverted_sha() {
+            let mut cancelled = false;
+            for candidate in &mut kept {
+                if candidate
+                    .as_ref()
+                    .is_some_and(|candidate| candidate.commit.sha.starts_with(sha))
+                {
+                    *candidate = None;
+                    cancelled = true;
+                }
+            }
+            if !cancelled {
+                kept.push(Some(parsed));
+            }
+            continue;
+        }
+
+        let position = kept.iter().rposition(|candidate| {
+            candidate
+                .as_ref()
+                .is_some_and(|candidate| same_subject(&candidate.comment, reverted))
+        });
+
+        match position {
+            Some(position) => kept[position] = None,
+            None => kept.push(Some(parsed)),
+        }
+    }
+
+    kept.into_iter().flatten().collect()
+}
+
+/// Returns whether `comment` has the type, scope, breaking flag and description of `subject`,
+/// the fields parsed from its body aside.
+fn same_subject(comment: &SemanticComment, subject: &SemanticComment) -> bool {
+    let strip = |comment: &SemanticComment| SemanticComment {
+        references: vec![],
+        tickets: vec![],
+        trailers: vec![],
+        breaking_changes: vec![],
+        ..comment.clone()
+    };
+    let mut stripped = strip(comment);
+    // a `BREAKING CHANGE` footer breaks the comment whatever its subject line says
+    if !comment.breaking_changes.is_empty() {
+        stripped.semantic_type.metadata_mut().is_breaking =
+            subject.semantic_type.metadata().is_breaking;
+    }
+    stripped == strip(subject)
+}
+
+/// [`Tag`] holds a tag name and the sha of the commit it points to.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub sha: String,
+}
+
+impl Tag {
+    pub fn new(name: impl Into<String>, sha: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sha: sha.into(),
+        }
+    }
+}
+
+/// [`Release`] groups the commits that were shipped by a version.
+///
+/// Commits that were not released yet are grouped in a release without `version`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub version: Option<SemanticVersion>,
+    pub commits: Vec<Commit>,
+}
+
+/// [`split_releases`] partitions the history into per-release buckets.
+///
+/// `history` is expected in chronological order (oldest first, like `git log --reverse`).
+/// A tagged commit closes the release it belongs to; tags that are not semantic versions are ignored
+/// and when a commit carries several semantic version tags the greatest one wins.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let history = vec![
+///     Commit::new("a1", "feat: first feature."),
+///     Commit::new("b2", "fix: first fix."),
+///     Commit::new("c3", "feat: second feature."),
+/// ];
+/// let tags = vec![Tag::new("v0.1.0", "b2")];
+///
+/// let releases = split_releases(&history, &tags);
+/// assert_eq!(releases.len(), 2);
+/// assert_eq!(releases[0].version, Some(SemanticVersion::new(0, 1, 0)));
+/// assert_eq!(releases[0].commits.len(), 2);
+/// assert_eq!(releases[1].version, None);
+/// assert_eq!(releases[1].commits, vec![Commit::new("c3", "feat: second feature.")]);
+/// ```
+pub fn split_releases(history: &[Commit], tags: &[Tag]) -> Vec<Release> {
+    let mut versions: HashMap<&str, SemanticVersion> = HashMap::new();
+    for tag in tags {
+        if let Ok(version) = SemanticVersion::try_from(tag.name.as_str()) {
+            match versions.get(tag.sha.as_str()) {
+                Some(existing) if *existing >= version => {}
+                _ => {
+                    versions.insert(tag.sha.as_str(), version);
+                }
+            }
+        }
+    }
+
+    let mut releases = vec![];
+    let mut commits = vec![];
+    for commit in history {
+        commits.push(commit.clone());
+
+        if let Some(version) = versions.remove(commit.sha.as_str()) {
+            releases.push(Release {
+                version: Some(version),
+                commits: std::mem::take(&mut commits),
+            });
+        }
+    }
+    if !commits.is_empty() {
+        releases.push(Release {
+            version: None,
+            commits,
+        });
+    }
+
+    releases
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cancel_reverts_drops_every_comment_of_a_reverted_squash_merge() {
+        let parser = CommentParser::default();
+        let commits = [
+            Commit::new(
+                "aaaaaaaa11",
+                "Add search (#12)\n\n* feat: search.\n* fix: typo.",
+            ),
+            Commit::new("bbbbbbbb22", "fix: a fix."),
+            Commit::new(
+                "cccccccc33",
+                "Revert \"feat: search.\"\n\nThis reverts commit aaaaaaaa11.",
+            ),
+        ];
+        let parsed = commits
+            .iter()
+            .flat_map(|commit| parse_squashed_commit(commit, &parser).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 4);
+
+        let kept = cancel_reverts(parsed);
+        assert_eq!(
+            kept.iter()
+                .map(|parsed| parsed.comment.to_commit_string())
+                .collect::<Vec<_>>(),
+            vec!["fix: a fix."]
+        );
+    }
+
+    #[test]
+    fn test_split_releases_groups_commits_between_tags() {
+        let history = vec![
+            Commit::new("a1", "feat: a."),
+            Commit::new("b2", "fix: b."),
+            Commit::new("c3", "feat: c."),
+            Commit::new("d4", "fix: d."),
+        ];
+        let tags = vec![
+            Tag::new("v0.1.0", "a1"),
+            Tag::new("latest", "d4"),
+            Tag::new("v0.2.0", "d4"),
+            Tag::new("v0.1.1", "d4"),
+        ];
+
+        let releases = split_releases(&history, &tags);
+
+        assert_eq!(
+            releases,
+            vec![
+                Release {
+                    version: Some(SemanticVersion::new(0, 1, 0)),
+                    commits: vec![Commit::new("a1", "feat: a.")],
+                },
+                Release {
+                    version: Some(SemanticVersion::new(0, 2, 0)),
+                    commits: vec![
+                        Commit::new("b2", "fix: b."),
+                        Commit::new("c3", "feat: c."),
+                        Commit::new("d4", "fix: d."),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancel_reverts_matches_git_reverts_by_sha() {
+        let parser = CommentParser::default();
+        let parse = |sha: &str, message: &str| ParsedCommit {
+            commit: Commit::new(sha, message),
+            comment: parser.parse(Commit::new(sha, message).subject()).unwrap(),
+        };
+        let commits = vec![
+            parse("aaaaaaaa11", "feat: add X."),
+            parse("bbbbbbbb22", "feat: add X."),
+            parse(
+                "cccccccc33",
+                "Revert \"feat: add X.\"\n\nThis reverts commit aaaaaaaa11.",
+            ),
+        ];
+
+        let kept = cancel_reverts(commits);
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].commit.sha, "bbbbbbbb22");
+    }
+
+    #[test]
+    fn test_cancel_reverts_ignores_the_body_of_the_reverted_commit() {
+        let parser = CommentParser::default();
+        let parse = |sha: &str, message: &str| ParsedCommit {
+            commit: Commit::new(sha, message),
+            comment: parser.parse_message(message).unwrap(),
+        };
+        let commits = vec![
+            parse("a1", "feat: add X\n\nCloses #12"),
+            parse("b2", "feat(api): add Y\n\nBREAKING CHANGE: Y replaces Z."),
+            parse("c3", "fix: a fix."),
+            parse("d4", "revert: feat: add X"),
+            parse("e5", "revert: feat(api): add Y"),
+        ];
+
+        let kept = cancel_reverts(commits);
+
+        assert_eq!(
+            kept.iter()
+                .map(|parsed| parsed.commit.sha.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c3"]
+        );
+    }
+
+    #[test]
+    fn test_parse_commits_keeps_the_order_and_errors_of_large_histories() {
+        let commits = (0..10_000)
+            .map(|i| match i % 3 {
+                0 => Commit::new(format!("s{}", i), "wip"),
+                _ => Commit::new(format!("s{}", i), format!("fix: fix {}.", i)),
+            })
+            .collect::<Vec<_>>();
+
+        let (parsed, unparsed) = parse_commits(&commits, &CommentParser::default());
+
+        assert_eq!(parsed.len(), 6_666);
+        assert_eq!(parsed[0].commit.sha, "s1");
+        assert_eq!(parsed[6_665].comment.comment, "fix 9998.");
+        assert_eq!(unparsed.len(), 3_334);
+        assert!(unparsed
+            .iter()
+            .enumerate()
+            .all(|(n, (commit, error))| commit.sha == format!("s{}", n * 3)
+                && *error == SemVerError::InvalidCommentFormat));
+    }
+
+    #[test]
+    fn test_split_releases_returns_single_unreleased_bucket_without_tags() {
+        let history = vec![Commit::new("a1", "feat: a.\n\nbody")];
+
+        let releases = split_releases(&history, &[]);
+
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].version, None);
+        assert_eq!(releases[0].commits[0].subject(), "feat: a.");
+    }
+
+    #[test]
+    fn test_commit_recognizes_merges_and_reverted_shas() {
+        for subject in [
+            "Merge branch 'main' into feature",
+            "Merge pull request #12 from team/feature",
+            "Merge remote-tracking branch 'origin/main'",
+            "Merge tag 'v1.0.0'",
+        ] {
+            assert!(Commit::new("a1", subject).is_merge(), "{}", subject);
+        }
+        assert!(!Commit::new("a1", "feat: Merge branch handling.").is_merge());
+
+        let revert = Commit::new(
+            "b2",
+            "Revert \"feat: search.\"\n\nThis reverts commit 0123abcd4567.",
+        );
+        assert_eq!(revert.reverted_sha(), Some("0123abcd4567"));
+        assert_eq!(Commit::new("c3", "fix: a fix.").reverted_sha(), None);
+    }
+}
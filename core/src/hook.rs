@@ -0,0 +1,225 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Config, Git, ScopePresence, SemVerError, SubjectCase};
+
+/// Name of the git hook pre-filling the commit messages, see [`prepare_commit_message`].
+pub const PREPARE_COMMIT_MSG_HOOK: &str = "prepare-commit-msg";
+
+/// Script of the `prepare-commit-msg` hook, reading the configuration at every commit so that
+/// the template follows its changes.
+pub const PREPARE_COMMIT_MSG_SCRIPT: &str =
+    "#!/bin/sh\n# installed by `semver hook install`\nexec semver hook prepare-commit-msg \"$@\"\n";
+
+impl Config {
+    /// [`commit_template`] lists the accepted types, the scopes and the subject rules as `#`
+    /// comment lines, which git leaves out of the message.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str(r#"
+    /// aliases = { feature = "feat" }
+    ///
+    /// [lint]
+    /// scopes = ["api", "cli"]
+    /// subject_max_length = 50
+    /// "#).unwrap();
+    /// let template = config.commit_template();
+    ///
+    /// assert!(template.contains("#   feat      minor, also feature\n"));
+    /// assert!(template.contains("# Scopes: api, cli\n"));
+    /// assert!(template.contains("# Subject: at most 50 characters\n"));
+    /// assert!(template.lines().all(|line| line.starts_with('#')));
+    /// ```
+    pub fn commit_template(&self) -> String {
+        let conventions = self.conventions();
+        let mut lines = vec![
+            "# <type>(<scope>): <subject>, with `!` after the scope for a breaking change"
+                .to_string(),
+            "#".to_string(),
+            "# Types:".to_string(),
+        ];
+        let width = conventions
+            .types
+            .iter()
+            .map(|t| t.name.len())
+            .max()
+            .unwrap_or_default();
+        for convention in &conventions.types {
+            let mut line = format!("#   {:<width$}  {}", convention.name, convention.bump);
+            if !convention.aliases.is_empty() {
+                line.push_str(&format!(", also {}", convention.aliases.join(", ")));
+            }
+            lines.push(line);
+        }
+
+        if !conventions.scopes.is_empty() {
+            lines.push(format!("# Scopes: {}", conventions.scopes.join(", ")));
+        }
+        let types_with = |presence: ScopePresence| {
+            conventions
+                .types
+                .iter()
+                .map(|t| t.name.as_str())
+                .filter(|name| self.lint.scope_presence(name) == presence)
+                .collect::<Vec<_>>()
+        };
+        for (presence, label) in [
+            (ScopePresence::Required, "required"),
+            (ScopePresence::Forbidden, "forbidden"),
+        ] {
+            let names = types_with(presence);
+            if !names.is_empty() {
+                lines.push(format!("# Scope {} for: {}", label, names.join(", ")));
+            }
+        }
+
+        let rules = &self.lint;
+        let mut subject = vec![];
+        match rules.subject_case {
+            Some(SubjectCase::Lower) => subject.push("lower case".to_string()),
+            Some(SubjectCase::Sentence) => subject.push("capitalized".to_string()),
+            None => {}
+        }
+        if rules.subject_imperative {
+            subject.push("imperative, e.g. `add` rather than `added`".to_string());
+        }
+        match rules.subject_full_stop {
+            Some(true) => subject.push("ending with a period".to_string()),
+            Some(false) => subject.push("no trailing period".to_string()),
+            None => {}
+        }
+        if let Some(max) = rules.subject_max_length {
+            subject.push(format!("at most {} characters", max));
+        }
+        if let Some(max) = rules.header_max_length {
+            subject.push(format!("header of at most {} characters", max));
+        }
+        if !subject.is_empty() {
+            lines.push(format!("# Subject: {}", subject.join(", ")));
+        }
+        if let Some(pattern) = &conventions.ticket_pattern {
+            lines.push(format!(
+                "# Tickets: {}{}",
+                pattern,
+                if conventions.ticket_required {
+                    ", required"
+                } else {
+                    ""
+                }
+            ));
+        }
+        lines.push("#".to_string());
+
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+}
+
+/// [`prepare_commit_message`] runs as the `prepare-commit-msg` hook: it inserts
+/// [`Config::commit_template`] under the first line of the message file at `path`. `source` is
+/// the second argument git passes to the hook, the template is only inserted when it is
+/// missing, i.e. for a plain `git commit`, and not for `-m`, merges, squashes or amends.
+/// Returns whether the file was changed.
+pub fn prepare_commit_message(
+    config: &Config,
+    path: impl AsRef<Path>,
+    source: Option<&str>,
+) -> Result<bool, SemVerError> {
+    if source.is_some_and(|source| !source.is_empty()) {
+        return Ok(false);
+    }
+    let contents = fs::read_to_string(&path)?;
+    let template = config.commit_template();
+    let contents = match contents.split_once('\n') {
+        Some((first, rest)) => format!("{}\n{}{}", first, template, rest),
+        None => format!("{}\n{}", contents, template),
+    };
+    fs::write(&path, contents)?;
+
+    Ok(true)
+}
+
+/// [`install_hook`] writes `script` as the git hook `name` of the repository, in
+/// `core.hooksPath` when set, and returns its path. A different hook already installed is
+/// kept unless `force` is set.
+pub fn install_hook(
+    git: &Git,
+    name: &str,
+    script: &str,
+    force: bool,
+) -> Result<PathBuf, SemVerError> {
+    let path = git.hooks_directory()?.join(name);
+    if !force && path.exists() && fs::read_to_string(&path)? != script {
+        return Err(SemVerError::HookAlreadyExists(path.display().to_string()));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::git::test::init_repository;
+
+    #[test]
+    fn test_prepared_commit_message_keeps_the_template_out_of_the_commit() {
+        let git = init_repository("hook");
+        let config = Config::from_toml_str("[lint]\nscopes = [\"api\"]").unwrap();
+
+        let path = install_hook(
+            &git,
+            PREPARE_COMMIT_MSG_HOOK,
+            PREPARE_COMMIT_MSG_SCRIPT,
+            false,
+        )
+        .unwrap();
+        assert!(path.ends_with(".git/hooks/prepare-commit-msg"));
+        // reinstalling the same hook is fine, replacing another one takes force
+        assert!(install_hook(
+            &git,
+            PREPARE_COMMIT_MSG_HOOK,
+            PREPARE_COMMIT_MSG_SCRIPT,
+            false
+        )
+        .is_ok());
+        assert!(matches!(
+            install_hook(&git, PREPARE_COMMIT_MSG_HOOK, "#!/bin/sh\n", false),
+            Err(SemVerError::HookAlreadyExists(_))
+        ));
+        assert!(install_hook(&git, PREPARE_COMMIT_MSG_HOOK, "#!/bin/sh\n", true).is_ok());
+
+        let message = git.directory().join("COMMIT_EDITMSG");
+        fs::write(&message, "\n# Please enter the commit message.\n").unwrap();
+        assert!(!prepare_commit_message(&config, &message, Some("message")).unwrap());
+        assert!(prepare_commit_message(&config, &message, None).unwrap());
+        let prepared = fs::read_to_string(&message).unwrap();
+        assert!(prepared.starts_with("\n# <type>(<scope>): <subject>"));
+        assert!(prepared.contains("# Scopes: api\n#\n# Please enter the commit message.\n"));
+
+        fs::write(&message, format!("feat(api): add export\n{}", prepared)).unwrap();
+        git.run([
+            "commit",
+            "--allow-empty",
+            "-q",
+            "--cleanup=strip",
+            "-F",
+            "COMMIT_EDITMSG",
+        ])
+        .unwrap();
+        assert_eq!(git.commit("HEAD").unwrap().message, "feat(api): add export");
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tracing::{debug, trace};
+
+use crate::{
+    git::{parse_log, FIELD_SEPARATOR, RECORD_SEPARATOR},
+    mercurial::parse_tags,
+    Commit, Git, SemVerError, Tag, Vcs,
+};
+
+/// [`Jujutsu`] runs `jj` commands in a repository, see [`Vcs`].
+///
+/// The working copy is a commit of its own, so `HEAD` is its parent `@-`, as in a git repository
+/// colocated with jj. Branches are bookmarks. jj does not create tags: [`Vcs::create_tag`] needs
+/// a colocated git repository, tags with `git` and imports the tag.
+#[derive(Debug, Clone)]
+pub struct Jujutsu {
+    directory: PathBuf,
+}
+
+impl Jujutsu {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// [`run`] runs `jj` with `args` and returns its trimmed stdout.
+    pub fn run<I, S>(&self, args: I) -> Result<String, SemVerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let args = args.into_iter().collect::<Vec<_>>();
+        trace!(
+            args = ?args.iter().map(|arg| arg.as_ref()).collect::<Vec<_>>(),
+            "running jj"
+        );
+        let output = Command::new("jj")
+            .arg("-R")
+            .arg(&self.directory)
+            .args(["--no-pager", "--color", "never"])
+            .args(args)
+            .output()
+            .map_err(|e| SemVerError::JujutsuError(e.to_string()))?;
+
+        if !output.status.success() {
+            return Err(SemVerError::JujutsuError(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// [`revset`] translates a git revision range, see [`Vcs`], to a jj revset.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// assert_eq!(Jujutsu::revset(None), "::@-");
+    /// assert_eq!(Jujutsu::revset(Some("v1.0.0..HEAD")), "\"v1.0.0\"..@-");
+    /// assert_eq!(Jujutsu::revset(Some("api-v1.0.0")), "::\"api-v1.0.0\"");
+    /// ```
+    pub fn revset(range: Option<&str>) -> String {
+        match range.map(|range| range.split_once("..")) {
+            None => "::@-".to_string(),
+            Some(Some((from, to))) => format!("{}..{}", revision(from), revision(to)),
+            Some(None) => format!("::{}", revision(range.unwrap_or_default())),
+        }
+    }
+
+    /// Returns `true` when the repository shares its directory with a git repository.
+    pub fn is_colocated(&self) -> bool {
+        self.directory.join(".git").exists()
+    }
+
+    /// Returns the commits of `revset`, oldest first.
+    fn log(&self, revset: &str) -> Result<Vec<Commit>, SemVerError> {
+        // jj reads any character but `"` and `\` in its string literals, separators included
+        let template = format!(
+            "commit_id ++ \"{0}\" ++ author.name() ++ \" <\" ++ author.email() ++ \">{0}\" ++ author.timestamp().utc().format(\"%s\") ++ \"{0}\" ++ description ++ \"{1}\"",
+            FIELD_SEPARATOR, RECORD_SEPARATOR
+        );
+
+        Ok(parse_log(&self.run([
+            "log",
+            "--no-graph",
+            "--reversed",
+            "-r",
+            revset,
+            "-T",
+            template.as_str(),
+        ])?))
+    }
+}
+
+/// Translates a git revision to a jj one, `HEAD` being the parent of the working copy and other
+/// names quoted, so that e.g. `api-v1.0.0` is not read as a parent operator.
+fn revision(revision: &str) -> String {
+    match revision {
+        "HEAD" | "" => "@-".to_string(),
+        _ => format!(
+            "\"{}\"",
+            revision.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+    }
+}
+
+/// Parses the lines of `jj git remote list`, `<name> <url>`, returning the url of `origin`.
+fn parse_origin(output: &str) -> Option<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(name, _)| *name == "origin")
+        .map(|(_, url)| url.trim().to_string())
+}
+
+impl Vcs for Jujutsu {
+    fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the closest bookmark of `HEAD` or its ancestors, `None` when there is none.
+    fn current_branch(&self) -> Result<Option<String>, SemVerError> {
+        let bookmarks = self.run([
+            "log",
+            "--no-graph",
+            "-r",
+            "heads(::@- & bookmarks())",
+            "-T",
+            "local_bookmarks.map(|b| b.name() ++ \"\\n\").join(\"\")",
+        ])?;
+
+        Ok(bookmarks.lines().next().map(str::to_string))
+    }
+
+    fn tags(&self) -> Result<Vec<Tag>, SemVerError> {
+        let output = self.run([
+            "log",
+            "--no-graph",
+            "-r",
+            "tags() & ::@-",
+            "-T",
+            "commit_id ++ tags.map(|t| \"\\t\" ++ t.name()).join(\"\") ++ \"\\n\"",
+        ])?;
+
+        Ok(parse_tags(&output))
+    }
+
+    fn tag_names(&self) -> Result<Vec<String>, SemVerError> {
+        let output = self.run([
+            "log",
+            "--no-graph",
+            "-r",
+            "tags()",
+            "-T",
+            "tags.map(|t| t.name() ++ \"\\n\").join(\"\")",
+        ])?;
+
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    fn commits(&self, range: Option<&str>) -> Result<Vec<Commit>, SemVerError> {
+        self.log(&Self::revset(range))
+    }
+
+    fn commit(&self, revision: &str) -> Result<Commit, SemVerError> {
+        self.log(&self::revision(revision))?
+            .pop()
+            .ok_or_else(|| SemVerError::JujutsuError(format!("unknown revision `{}`", revision)))
+    }
+
+    fn changed_paths(&self, revision: &str) -> Result<Vec<PathBuf>, SemVerError> {
+        let output = self.run(["diff", "--name-only", "-r", &self::revision(revision)])?;
+
+        Ok(output.lines().map(PathBuf::from).collect())
+    }
+
+    /// Returns the paths changed by the working copy commit `@`.
+    fn staged_paths(&self) -> Result<Vec<PathBuf>, SemVerError> {
+        let output = self.run(["diff", "--name-only", "-r", "@"])?;
+
+        Ok(output.lines().map(PathBuf::from).collect())
+    }
+
+    fn create_tag(&self, name: &str, message: &str) -> Result<(), SemVerError> {
+        if !self.is_colocated() {
+            return Err(SemVerError::JujutsuError(
+                "tagging needs a repository colocated with git".to_string(),
+            ));
+        }
+        let sha = self.commit("HEAD")?.sha;
+        debug!(%name, %sha, "tagging with git");
+        Git::new(&self.directory).run(["tag", "-a", name, "-m", message, &sha])?;
+        self.run(["git", "import"])?;
+
+        Ok(())
+    }
+
+    fn remote_url(&self) -> Option<String> {
+        parse_origin(&self.run(["git", "remote", "list"]).ok()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_origin_reads_the_url_of_origin() {
+        let output = "upstream https://github.com/team/app\norigin git@github.com:me/app.git\n";
+
+        assert_eq!(
+            parse_origin(output).as_deref(),
+            Some("git@github.com:me/app.git")
+        );
+        assert_eq!(parse_origin("upstream https://github.com/team/app"), None);
+        assert_eq!(revision("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+}
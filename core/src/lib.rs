@@ -1,7 +1,99 @@
+pub mod analyze;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_interop;
+pub mod cache;
+pub mod calver;
+pub mod canonicalize;
+pub mod changelog;
+pub mod check;
+pub mod ci;
+pub mod cocogitto;
 pub mod comment_parser;
+pub mod commitlint;
+pub mod config;
+pub mod contributors;
+pub mod docker;
+pub mod fix;
+#[cfg(feature = "forge")]
+pub mod forge;
+pub mod git;
+pub mod github;
+pub mod history;
+pub mod hook;
+pub mod hosting;
+pub mod jujutsu;
+pub mod links;
+pub mod lint;
+pub mod manifest;
+pub mod mercurial;
 pub mod models;
+pub mod packages;
+pub mod promote;
+pub mod pull_request;
+pub mod regenerate;
+#[cfg(feature = "forge")]
+pub mod registry;
+pub mod release;
+pub mod requirement;
+pub mod schema;
+pub mod scopes;
+pub mod semantic_release;
+#[cfg(feature = "semver")]
+pub mod semver_interop;
+pub mod sort;
+pub mod stats;
+pub mod tag_format;
+pub mod updaters;
+pub mod vcs;
 pub mod versioner;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
+pub use analyze::*;
+pub use cache::*;
+pub use calver::*;
+pub use canonicalize::*;
+pub use changelog::*;
+pub use check::*;
+pub use ci::*;
+pub use cocogitto::*;
 pub use comment_parser::*;
+pub use commitlint::*;
+pub use config::*;
+pub use contributors::*;
+pub use docker::*;
+pub use fix::*;
+#[cfg(feature = "forge")]
+pub use forge::*;
+pub use git::*;
+pub use github::*;
+pub use history::*;
+pub use hook::*;
+pub use hosting::*;
+pub use jujutsu::*;
+pub use links::*;
+pub use lint::*;
+pub use manifest::*;
+pub use mercurial::*;
 pub use models::*;
+pub use packages::*;
+pub use promote::*;
+pub use pull_request::*;
+pub use regenerate::*;
+#[cfg(feature = "forge")]
+pub use registry::*;
+pub use release::*;
+pub use requirement::*;
+pub use schema::*;
+pub use scopes::*;
+pub use semantic_release::*;
+pub use sort::*;
+pub use stats::*;
+pub use tag_format::*;
+pub use updaters::*;
+pub use vcs::*;
 pub use versioner::*;
+#[cfg(feature = "webhook")]
+pub use webhook::*;
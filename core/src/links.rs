@@ -0,0 +1,269 @@
+use serde::Deserialize;
+
+use crate::{parse_remote, Config, Reference, ReferenceKind, Vcs};
+
+/// [`HostKind`] is the hosting service of a repository, telling the layout of its web pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HostKind {
+    GitHub,
+    GitLab,
+    /// Gitea and Forgejo, e.g. Codeberg.
+    Gitea,
+}
+
+impl HostKind {
+    /// [`detect`] returns the kind of the host of the web url `url`, `None` when it does not
+    /// tell, e.g. for self-hosted instances on a custom domain.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// assert_eq!(HostKind::detect("https://github.com"), Some(HostKind::GitHub));
+    /// assert_eq!(HostKind::detect("https://gitlab.example.com"), Some(HostKind::GitLab));
+    /// assert_eq!(HostKind::detect("https://codeberg.org"), Some(HostKind::Gitea));
+    /// assert_eq!(HostKind::detect("https://git.example.com"), None);
+    /// ```
+    pub fn detect(url: &str) -> Option<Self> {
+        let host = url.split_once("://").map_or(url, |(_, host)| host);
+        let host = host.to_lowercase();
+
+        if host.contains("github") {
+            Some(Self::GitHub)
+        } else if host.contains("gitlab") {
+            Some(Self::GitLab)
+        } else if ["gitea", "forgejo", "codeberg"]
+            .iter()
+            .any(|name| host.contains(name))
+        {
+            Some(Self::Gitea)
+        } else {
+            None
+        }
+    }
+}
+
+/// [`LinksConfig`] holds the `[links]` section, linking the changelog to the web pages of the
+/// repository detected from the `origin` remote.
+///
+/// The templates override those of the host, with the `{repository}` url and the `{sha}`,
+/// `{from}`, `{to}` and `{number}` of each link.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// [links]
+/// repository = "https://git.example.com/team/app"
+/// kind = "gitlab"
+/// commit = "https://review.example.com/app/+/{sha}"
+/// "#).unwrap();
+///
+/// let links = config.repository_links(&Git::new(".")).unwrap();
+/// assert_eq!(links.commit_url("a1"), "https://review.example.com/app/+/a1");
+/// assert_eq!(
+///     links.compare_url("v1.0.0", "v1.1.0"),
+///     "https://git.example.com/team/app/-/compare/v1.0.0...v1.1.0"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinksConfig {
+    /// Links the changelog, `true` by default.
+    pub enabled: bool,
+    /// Web url of the repository, e.g. `https://github.com/team/app`, read from the `origin`
+    /// remote when unset.
+    pub repository: Option<String>,
+    /// Kind of the host, detected from its name when unset.
+    pub kind: Option<HostKind>,
+    /// Template of the commit pages.
+    pub commit: Option<String>,
+    /// Template of the pages comparing two tags.
+    pub compare: Option<String>,
+    /// Template of the issue pages, linking `#123`.
+    pub issue: Option<String>,
+    /// Template of the pull and merge request pages, linking `!45`.
+    pub pull_request: Option<String>,
+}
+
+impl Default for LinksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            repository: None,
+            kind: None,
+            commit: None,
+            compare: None,
+            issue: None,
+            pull_request: None,
+        }
+    }
+}
+
+/// [`RepositoryLinks`] renders the urls of the web pages of a repository, see
+/// [`crate::render_linked_release`].
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let links = RepositoryLinks::new(HostKind::GitHub, "https://github.com/team/app");
+/// let reference = Reference { kind: ReferenceKind::Issue, number: 12, closes: false };
+///
+/// assert_eq!(links.commit_url("a1"), "https://github.com/team/app/commit/a1");
+/// assert_eq!(links.compare_url("v1.0.0", "v1.1.0"), "https://github.com/team/app/compare/v1.0.0...v1.1.0");
+/// assert_eq!(links.reference_url(&reference).unwrap(), "https://github.com/team/app/issues/12");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryLinks {
+    pub commit: String,
+    pub compare: String,
+    pub issue: String,
+    /// Template of the `!45` references, left unlinked when `None`.
+    pub pull_request: Option<String>,
+    /// Links the `GH-123` references with the `issue` template.
+    pub github_references: bool,
+}
+
+impl RepositoryLinks {
+    /// [`new`] returns the links of the repository at the web url `repository` on a `kind` host.
+    pub fn new(kind: HostKind, repository: &str) -> Self {
+        let repository = repository.trim_end_matches('/');
+        let (commit, compare, issue, pull_request) = match kind {
+            HostKind::GitHub => (
+                "/commit/{sha}",
+                "/compare/{from}...{to}",
+                "/issues/{number}",
+                None,
+            ),
+            HostKind::GitLab => (
+                "/-/commit/{sha}",
+                "/-/compare/{from}...{to}",
+                "/-/issues/{number}",
+                Some("/-/merge_requests/{number}"),
+            ),
+            HostKind::Gitea => (
+                "/commit/{sha}",
+                "/compare/{from}...{to}",
+                // Gitea redirects issue numbers of pull requests to the pull request
+                "/issues/{number}",
+                Some("/pulls/{number}"),
+            ),
+        };
+
+        Self {
+            commit: format!("{}{}", repository, commit),
+            compare: format!("{}{}", repository, compare),
+            issue: format!("{}{}", repository, issue),
+            pull_request: pull_request.map(|path| format!("{}{}", repository, path)),
+            github_references: kind == HostKind::GitHub,
+        }
+    }
+
+    /// Returns the url of the web page of the commit `sha`.
+    pub fn commit_url(&self, sha: &str) -> String {
+        self.commit.replace("{sha}", sha)
+    }
+
+    /// Returns the url of the web page comparing the tags `from` and `to`.
+    pub fn compare_url(&self, from: &str, to: &str) -> String {
+        self.compare.replace("{from}", from).replace("{to}", to)
+    }
+
+    /// Returns the url of the issue or pull request referenced, `None` when it cannot be linked.
+    pub fn reference_url(&self, reference: &Reference) -> Option<String> {
+        let template = match reference.kind {
+            ReferenceKind::Issue => Some(&self.issue),
+            ReferenceKind::GitHub => Some(&self.issue).filter(|_| self.github_references),
+            ReferenceKind::MergeRequest => self.pull_request.as_ref(),
+        }?;
+
+        Some(template.replace("{number}", &reference.number.to_string()))
+    }
+}
+
+impl Config {
+    /// [`repository_links`] returns the links of the changelog, following the `[links]`
+    /// section and the `origin` remote of `vcs`. It is `None` when disabled, or when the
+    /// repository or the kind of its host are not known and no template replaces them.
+    pub fn repository_links(&self, vcs: &dyn Vcs) -> Option<RepositoryLinks> {
+        let config = &self.links;
+        if !config.enabled {
+            return None;
+        }
+        let repository = match &config.repository {
+            Some(repository) => Some(repository.trim_end_matches('/').to_string()),
+            None => vcs
+                .remote_url()
+                .and_then(|remote| parse_remote(&remote))
+                .map(|(url, path)| format!("{}/{}", url, path)),
+        };
+        let preset = repository.as_deref().and_then(|repository| {
+            let kind = config.kind.or_else(|| HostKind::detect(repository))?;
+            Some(RepositoryLinks::new(kind, repository))
+        });
+
+        let template = |template: &Option<String>, preset: Option<&String>| {
+            let template = template.clone().or_else(|| preset.cloned())?;
+            Some(match &repository {
+                Some(repository) => template.replace("{repository}", repository),
+                None => template,
+            })
+        };
+        let (Some(commit), Some(compare), Some(issue)) = (
+            template(&config.commit, preset.as_ref().map(|p| &p.commit)),
+            template(&config.compare, preset.as_ref().map(|p| &p.compare)),
+            template(&config.issue, preset.as_ref().map(|p| &p.issue)),
+        ) else {
+            return None;
+        };
+
+        Some(RepositoryLinks {
+            commit,
+            compare,
+            issue,
+            pull_request: template(
+                &config.pull_request,
+                preset.as_ref().and_then(|p| p.pull_request.as_ref()),
+            ),
+            github_references: preset.is_some_and(|p| p.github_references),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Git;
+
+    use super::*;
+
+    #[test]
+    fn test_repository_links_need_a_known_host_or_templates() {
+        let git = Git::new(std::env::temp_dir());
+        let config = |toml: &str| Config::from_toml_str(toml).unwrap();
+
+        let links = config("[links]\nrepository = \"https://codeberg.org/team/app/\"\n")
+            .repository_links(&git)
+            .unwrap();
+        assert_eq!(
+            links.commit_url("a1"),
+            "https://codeberg.org/team/app/commit/a1"
+        );
+        assert_eq!(
+            links.pull_request.as_deref(),
+            Some("https://codeberg.org/team/app/pulls/{number}")
+        );
+
+        let custom = "[links]\nrepository = \"https://git.example.com/app\"\n";
+        assert_eq!(config(custom).repository_links(&git), None);
+        let templates = format!(
+            "{}commit = \"{{repository}}/c/{{sha}}\"\ncompare = \"{{repository}}/d/{{from}}..{{to}}\"\nissue = \"https://jira.example.com/{{number}}\"\n",
+            custom
+        );
+        let links = config(&templates).repository_links(&git).unwrap();
+        assert_eq!(links.commit_url("a1"), "https://git.example.com/app/c/a1");
+        assert_eq!(links.pull_request, None);
+
+        let disabled = "[links]\nenabled = false\nrepository = \"https://github.com/team/app\"\n";
+        assert_eq!(config(disabled).repository_links(&git), None);
+    }
+}
@@ -0,0 +1,531 @@
+use std::fmt;
+
+use serde::Serialize;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    Commit, Config, RefactorKeyword, ScopePresence, SemVerError, SemanticComment, SubjectCase,
+};
+
+/// Words ending like a past tense, gerund or third person verb which are imperative anyway.
+const IMPERATIVE_EXCEPTIONS: [&str; 17] = [
+    "bleed", "breed", "embed", "exceed", "feed", "need", "proceed", "seed", "shred", "speed",
+    "succeed", "bring", "ping", "ring", "sing", "spring", "string",
+];
+
+/// [`LintWarning`] reports a commit accepted by [`Config::lint`] that should still be fixed.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintWarning {
+    /// The type is spelled the deprecated way, e.g. `refact` rather than `refactor`.
+    DeprecatedType {
+        used: &'static str,
+        canonical: &'static str,
+    },
+    /// A rule of the `[lint]` section configured as a warning is broken.
+    Rule(RuleViolation),
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeprecatedType { used, canonical } => {
+                write!(f, "`{}` is deprecated, use `{}`", used, canonical)
+            }
+            Self::Rule(violation) => write!(f, "{}", violation),
+        }
+    }
+}
+
+/// [`RuleViolation`] reports a commit breaking a rule of the `[lint]` section,
+/// see [`crate::LintConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleViolation {
+    /// Name of the rule, e.g. `scope-enum`.
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.rule)
+    }
+}
+
+impl From<RuleViolation> for SemVerError {
+    fn from(violation: RuleViolation) -> Self {
+        SemVerError::RuleViolation(violation.rule.to_string(), violation.message)
+    }
+}
+
+/// [`LintReport`] holds the result of linting a range of commits, see [`Config::lint_report`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LintReport {
+    pub commits: Vec<CommitLint>,
+    /// Number of errors across the commits.
+    pub errors: usize,
+    /// Number of warnings across the commits.
+    pub warnings: usize,
+}
+
+/// [`CommitLint`] holds the errors and warnings of a commit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommitLint {
+    pub sha: String,
+    pub subject: String,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl LintReport {
+    /// Returns `true` when no commit fails and, with `max_warnings`, the warnings do not
+    /// exceed it.
+    pub fn passed(&self, max_warnings: Option<usize>) -> bool {
+        self.errors == 0 && max_warnings.is_none_or(|max| self.warnings <= max)
+    }
+
+    /// [`as_json_string`] returns json representation of the report.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+impl Config {
+    /// [`lint`] checks a commit message against the configuration: its subject must be a
+    /// valid comment and, when `[tickets] required` is set, the message must reference a ticket.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::default();
+    ///
+    /// assert!(config.lint("feat: new endpoint.\n\nDetails.").is_ok());
+    /// assert_eq!(config.lint("new endpoint"), Err(SemVerError::InvalidCommentFormat));
+    /// ```
+    pub fn lint(&self, message: &str) -> Result<SemanticComment, SemVerError> {
+        let comment = self.comment_parser().parse_message(message)?;
+
+        match self.lint_errors(message, &comment).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(comment),
+        }
+    }
+
+    /// [`rule_violations`] checks the message of `comment` against the rules of the `[lint]`
+    /// section, whatever their severity.
+    pub fn rule_violations(&self, message: &str, comment: &SemanticComment) -> Vec<RuleViolation> {
+        let rules = &self.lint;
+        let mut violations = vec![];
+        let mut violation = |rule, message| violations.push(RuleViolation { rule, message });
+
+        if let Some(scope) = comment.scope.as_ref() {
+            let scopes = self.allowed_scopes();
+            if !scopes.is_empty() && !scopes.contains(&scope.as_str()) {
+                violation(
+                    "scope-enum",
+                    format!("scope `{}` is not one of {}", scope, scopes.join(", ")),
+                );
+            }
+        }
+        let semantic_type = comment.semantic_type.keyword(RefactorKeyword::Refactor);
+        match (rules.scope_presence(semantic_type), &comment.scope) {
+            (ScopePresence::Required, None) => violation(
+                "scope-empty",
+                format!("`{}` requires a scope", semantic_type),
+            ),
+            (ScopePresence::Forbidden, Some(scope)) => violation(
+                "scope-empty",
+                format!(
+                    "`{}` does not take a scope, found `{}`",
+                    semantic_type, scope
+                ),
+            ),
+            _ => {}
+        }
+        let mut lines = message.lines();
+        let header = lines.next().unwrap_or_default();
+        let first_letter = comment.comment.chars().next().filter(|c| c.is_alphabetic());
+        match (rules.subject_case, first_letter) {
+            (Some(SubjectCase::Lower), Some(letter)) if !letter.is_lowercase() => violation(
+                "subject-case",
+                "subject must start with a lower case letter".to_string(),
+            ),
+            (Some(SubjectCase::Sentence), Some(letter)) if !letter.is_uppercase() => violation(
+                "subject-case",
+                "subject must start with an upper case letter".to_string(),
+            ),
+            _ => {}
+        }
+        if rules.subject_no_leading_whitespace {
+            let separator = header
+                .trim_end()
+                .strip_suffix(comment.comment.as_str())
+                .unwrap_or_default();
+            let whitespace = &separator[separator.trim_end().len()..];
+            if whitespace.len() > 1 || whitespace.contains('\t') {
+                violation(
+                    "subject-leading-whitespace",
+                    "subject is preceded by more than one space".to_string(),
+                );
+            }
+        }
+        match (rules.subject_full_stop, comment.comment.ends_with('.')) {
+            (Some(true), false) => violation(
+                "subject-full-stop",
+                "subject must end with a period".to_string(),
+            ),
+            (Some(false), true) => violation(
+                "subject-full-stop",
+                "subject must not end with a period".to_string(),
+            ),
+            _ => {}
+        }
+        if rules.subject_imperative {
+            if let Some(word) = comment.comment.split_whitespace().next() {
+                if !is_imperative(word) {
+                    violation(
+                        "subject-imperative",
+                        format!("subject must start with an imperative verb, not `{}`", word),
+                    );
+                }
+            }
+        }
+        let blocked = comment
+            .comment
+            .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '\'')
+            .find(|word| {
+                rules
+                    .subject_blocklist
+                    .iter()
+                    .any(|blocked| blocked.eq_ignore_ascii_case(word))
+            });
+        if let Some(word) = blocked {
+            violation(
+                "subject-blocklist",
+                format!("subject holds the blocked word `{}`", word),
+            );
+        }
+        // lengths count what a reader sees, e.g. one for `é` or a family emoji
+        let too_long =
+            |text: &str, max: Option<usize>| max.filter(|max| text.graphemes(true).count() > *max);
+        if let Some(max) = too_long(&comment.comment, rules.subject_max_length) {
+            violation(
+                "subject-max-length",
+                format!("subject is longer than {} characters", max),
+            );
+        }
+        if let Some(max) = too_long(header, rules.header_max_length) {
+            violation(
+                "header-max-length",
+                format!("header is longer than {} characters", max),
+            );
+        }
+        if let Some(max) = rules.body_max_line_length {
+            if lines.any(|line| too_long(line, Some(max)).is_some()) {
+                violation(
+                    "body-max-line-length",
+                    format!("body has lines longer than {} characters", max),
+                );
+            }
+        }
+
+        violations
+    }
+
+    /// Returns every error of the message of `comment`, a missing ticket first.
+    fn lint_errors(&self, message: &str, comment: &SemanticComment) -> Vec<SemVerError> {
+        let mut errors = vec![];
+        if self.tickets.required && comment.tickets.is_empty() {
+            errors.push(SemVerError::MissingTicket);
+        }
+        errors.extend(
+            self.rule_violations(message, comment)
+                .into_iter()
+                .filter(|violation| !self.is_warning(violation))
+                .map(SemVerError::from),
+        );
+
+        errors
+    }
+
+    fn is_warning(&self, violation: &RuleViolation) -> bool {
+        self.lint.warnings.iter().any(|rule| rule == violation.rule)
+    }
+
+    /// [`lint_warnings`] returns the warnings of a commit message, e.g. the use of `refact`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::default();
+    ///
+    /// assert_eq!(
+    ///     config.lint_warnings("refact: simplify.")[0].to_string(),
+    ///     "`refact` is deprecated, use `refactor`"
+    /// );
+    /// assert!(config.lint_warnings("refactor: simplify.").is_empty());
+    /// ```
+    pub fn lint_warnings(&self, message: &str) -> Vec<LintWarning> {
+        let subject = message.lines().next().unwrap_or_default();
+        let parser = self.comment_parser();
+
+        let mut warnings = parser
+            .deprecated_type(subject)
+            .map(|(used, canonical)| LintWarning::DeprecatedType { used, canonical })
+            .into_iter()
+            .collect::<Vec<_>>();
+        if let Ok(comment) = parser.parse_message(message) {
+            warnings.extend(
+                self.rule_violations(message, &comment)
+                    .into_iter()
+                    .filter(|violation| self.is_warning(violation))
+                    .map(LintWarning::Rule),
+            );
+        }
+
+        warnings
+    }
+
+    /// [`lint_commits`] lints `commits`, leaving out the merge and bot commits filtered by
+    /// the `[history]` section, and returns the ones failing with their error.
+    pub fn lint_commits(&self, commits: &[Commit]) -> Vec<(Commit, SemVerError)> {
+        self.lintable(commits)
+            .filter_map(|commit| {
+                self.lint(&commit.message)
+                    .err()
+                    .map(|error| (commit.clone(), error))
+            })
+            .collect()
+    }
+
+    /// [`lint_commit_warnings`] returns the warnings of `commits`, filtered like in
+    /// [`Config::lint_commits`].
+    pub fn lint_commit_warnings(&self, commits: &[Commit]) -> Vec<(Commit, LintWarning)> {
+        self.lintable(commits)
+            .flat_map(|commit| {
+                self.lint_warnings(&commit.message)
+                    .into_iter()
+                    .map(|warning| (commit.clone(), warning))
+            })
+            .collect()
+    }
+
+    /// [`lint_report`] lints `commits`, filtered like in [`Config::lint_commits`], and reports
+    /// every error and warning of each one, along with their totals.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str("[lint]\nsubject_max_length = 10\nwarnings = [\"subject-max-length\"]\n").unwrap();
+    /// let commits = vec![
+    ///     Commit::new("a1", "feat: export."),
+    ///     Commit::new("b2", "fix: a rather long subject."),
+    ///     Commit::new("c3", "not conventional"),
+    /// ];
+    ///
+    /// let report = config.lint_report(&commits);
+    /// assert_eq!((report.errors, report.warnings), (1, 1));
+    /// assert!(report.commits[0].errors.is_empty() && report.commits[0].warnings.is_empty());
+    /// assert!(!report.passed(None));
+    /// ```
+    pub fn lint_report(&self, commits: &[Commit]) -> LintReport {
+        let parser = self.comment_parser();
+        let mut report = LintReport::default();
+
+        for commit in self.lintable(commits) {
+            let errors = match parser.parse_message(&commit.message) {
+                Ok(comment) => self.lint_errors(&commit.message, &comment),
+                Err(error) => vec![error],
+            };
+            let warnings = self.lint_warnings(&commit.message);
+            report.errors += errors.len();
+            report.warnings += warnings.len();
+            report.commits.push(CommitLint {
+                sha: commit.sha.clone(),
+                subject: commit.subject().to_string(),
+                errors: errors.iter().map(ToString::to_string).collect(),
+                warnings: warnings.iter().map(ToString::to_string).collect(),
+            });
+        }
+
+        report
+    }
+
+    fn lintable<'a>(&'a self, commits: &'a [Commit]) -> impl Iterator<Item = &'a Commit> {
+        commits
+            .iter()
+            .filter(|commit| !(self.history.skip_merges && commit.is_merge()))
+            .filter(|commit| !self.history.is_bot(commit))
+    }
+}
+
+/// Tells whether `word` reads as an imperative verb, rather than e.g. `added`, `adding` or
+/// `adds`. This is a heuristic: unknown words ending like verbs are taken for verbs.
+fn is_imperative(word: &str) -> bool {
+    let word = word.to_lowercase();
+    if !word.chars().all(|c| c.is_alphabetic()) || IMPERATIVE_EXCEPTIONS.contains(&word.as_str()) {
+        return true;
+    }
+    let ends_with = |suffix: &str, min: usize| word.len() >= min && word.ends_with(suffix);
+    let third_person = ends_with("s", 4)
+        && !["ss", "us", "is", "as", "os", "ys"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix));
+
+    !(ends_with("ed", 4) || ends_with("ing", 5) || third_person)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lint_commits_requires_tickets_when_configured() {
+        let mut config = Config::from_toml_str("[tickets]\npattern = \"[A-Z]+-\\\\d+\"\n").unwrap();
+        let commits = vec![
+            Commit::new("a1", "feat: new endpoint.\n\nRefs: API-3"),
+            Commit::new("b2", "fix: a fix."),
+            Commit::new("c3", "Merge branch 'main' into feature"),
+            Commit::new("d4", "not conventional"),
+        ];
+
+        let failures = |config: &Config| {
+            config
+                .lint_commits(&commits)
+                .into_iter()
+                .map(|(commit, error)| (commit.sha, error))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            failures(&config),
+            vec![("d4".to_string(), SemVerError::InvalidCommentFormat)]
+        );
+
+        config.tickets.required = true;
+        assert_eq!(
+            failures(&config),
+            vec![
+                ("b2".to_string(), SemVerError::MissingTicket),
+                ("d4".to_string(), SemVerError::InvalidCommentFormat)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rule_violations_count_graphemes() {
+        let config = Config::from_toml_str("[lint]\nsubject_max_length = 6\n").unwrap();
+        let violations = |message: &str| {
+            let comment = config.comment_parser().parse_message(message).unwrap();
+            config.rule_violations(message, &comment)
+        };
+
+        assert!(violations("feat(ü): café 👨‍👩‍👧").is_empty());
+        assert_eq!(violations("feat: cafés 🎉")[0].rule, "subject-max-length");
+    }
+
+    #[test]
+    fn test_scope_presence_follows_the_type_overrides() {
+        let config = Config::from_toml_str(
+            r#"
+types = ["feat", "fix", "docs"]
+aliases = { feature = "feat" }
+
+[lint]
+scope_by_type = { FEAT = "required", docs = "forbidden" }
+warnings = ["scope-empty"]
+"#,
+        )
+        .unwrap();
+
+        assert!(config.lint("fix: crash.").is_ok());
+        assert!(config.lint("fix(api): crash.").is_ok());
+        assert_eq!(
+            config.lint_warnings("feature: export.")[0].to_string(),
+            "`feat` requires a scope (scope-empty)"
+        );
+        assert_eq!(
+            config.lint_warnings("docs(readme): usage.")[0].to_string(),
+            "`docs` does not take a scope, found `readme` (scope-empty)"
+        );
+        assert!(config.lint("docs(readme): usage.").is_ok());
+    }
+
+    #[test]
+    fn test_subject_rules_check_case_whitespace_mood_and_words() {
+        let config = Config::from_toml_str(
+            r#"
+[lint]
+subject_case = "lower"
+subject_full_stop = true
+subject_no_leading_whitespace = true
+subject_imperative = true
+subject_blocklist = ["wip", "oops"]
+warnings = ["subject-imperative"]
+"#,
+        )
+        .unwrap();
+        let rules = |message: &str| {
+            let comment = config.comment_parser().parse_message(message).unwrap();
+            config
+                .rule_violations(message, &comment)
+                .into_iter()
+                .map(|violation| violation.rule)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(rules("feat(api): add the export endpoint.").is_empty());
+        assert!(rules("fix: 404 on empty pages.").is_empty());
+        assert_eq!(rules("feat: Add export."), vec!["subject-case"]);
+        assert_eq!(
+            rules("feat:  add export."),
+            vec!["subject-leading-whitespace"]
+        );
+        assert_eq!(
+            rules("feat:\tadd export."),
+            vec!["subject-leading-whitespace"]
+        );
+        assert_eq!(
+            rules("fix: crash on empty pages"),
+            vec!["subject-full-stop"]
+        );
+        assert_eq!(rules("fix: fixes the crash."), vec!["subject-imperative"]);
+        assert_eq!(rules("fix: crash on WIP pages."), vec!["subject-blocklist"]);
+        assert!(config.lint("fix: added a check.").is_ok());
+        assert!(config.lint("fix: oops.").is_err());
+
+        for word in ["added", "adding", "adds", "fixes", "updated"] {
+            assert!(!is_imperative(word), "{}", word);
+        }
+        for word in ["add", "embed", "bring", "process", "focus", "bump", "v2"] {
+            assert!(is_imperative(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_lint_report_fails_past_the_warning_threshold() {
+        let config = Config::from_toml_str(
+            "[lint]\nsubject_imperative = true\nwarnings = [\"subject-imperative\"]\n",
+        )
+        .unwrap();
+        let commits = vec![
+            Commit::new("a1", "feat: adds export."),
+            Commit::new("b2", "refact: tidy."),
+            Commit::new("c3", "Merge branch 'main' into feature"),
+        ];
+
+        let report = config.lint_report(&commits);
+        assert_eq!(report.commits.len(), 2);
+        assert_eq!((report.errors, report.warnings), (0, 2));
+        assert!(report.passed(None));
+        assert!(report.passed(Some(2)));
+        assert!(!report.passed(Some(1)));
+    }
+
+    #[test]
+    fn test_config_rejects_invalid_ticket_pattern() {
+        assert!(matches!(
+            Config::from_toml_str("[tickets]\npattern = \"PROJ-(\"\n"),
+            Err(SemVerError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,358 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::{value, DocumentMut, Item};
+
+use crate::{
+    updaters::bare_version, BumpPlan, FileChange, SemVerError, SemanticComment, SemanticVersion,
+};
+
+/// Dependency tables whose requirements are updated by [`CargoManifest::set_dependency_requirement`].
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// [`CargoManifest`] edits a `Cargo.toml` while preserving its formatting and comments.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let mut manifest = CargoManifest::parse(
+///     "Cargo.toml",
+///     "[package]\nname = \"foo\"\nversion = \"0.1.0\" # keep me\n",
+/// ).unwrap();
+///
+/// manifest.set_version(&SemanticVersion::new(0, 2, 0)).unwrap();
+/// assert_eq!(manifest.to_string(), "[package]\nname = \"foo\"\nversion = \"0.2.0\" # keep me\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CargoManifest {
+    path: PathBuf,
+    document: DocumentMut,
+}
+
+impl CargoManifest {
+    /// [`load`] reads and parses the manifest at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SemVerError> {
+        let contents = fs::read_to_string(path.as_ref())?;
+        Self::parse(path, &contents)
+    }
+
+    /// [`parse`] parses `contents` as the manifest located at `path`.
+    pub fn parse(path: impl AsRef<Path>, contents: &str) -> Result<Self, SemVerError> {
+        let document = contents
+            .parse::<DocumentMut>()
+            .map_err(|e| SemVerError::InvalidManifest(e.to_string()))?;
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            document,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the `[package] name`.
+    pub fn package_name(&self) -> Result<&str, SemVerError> {
+        self.document
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(Item::as_str)
+            .ok_or_else(|| SemVerError::InvalidManifest("missing `[package] name`".to_string()))
+    }
+
+    /// Returns the `[package] version`.
+    pub fn version(&self) -> Result<SemanticVersion, SemVerError> {
+        let version = self.version_item()?.as_str().ok_or_else(|| {
+            SemVerError::InvalidManifest("`[package] version` is not a string".to_string())
+        })?;
+
+        SemanticVersion::try_from(format!("v{}", version).as_str())
+    }
+
+    /// Rewrites the `[package] version`, keeping the surrounding decoration.
+    pub fn set_version(&mut self, version: &SemanticVersion) -> Result<(), SemVerError> {
+        let item = self.version_item_mut()?;
+        if !item.is_str() {
+            return Err(SemVerError::InvalidManifest(
+                "`[package] version` is not a string".to_string(),
+            ));
+        }
+        replace_string(item, bare_version(version));
+
+        Ok(())
+    }
+
+    /// Updates the version requirement of dependency `name` in every dependency table,
+    /// including `[workspace.dependencies]`, keeping its comparison operator (`^`, `~`, `=`...).
+    ///
+    /// Returns `true` if any requirement changed.
+    pub fn set_dependency_requirement(&mut self, name: &str, version: &SemanticVersion) -> bool {
+        let mut changed = false;
+
+        for table in DEPENDENCY_TABLES {
+            if let Some(table) = self.document.get_mut(table) {
+                changed |= set_requirement(table, name, version);
+            }
+        }
+        if let Some(table) = self
+            .document
+            .get_mut("workspace")
+            .and_then(Item::as_table_like_mut)
+            .and_then(|workspace| workspace.get_mut("dependencies"))
+        {
+            changed |= set_requirement(table, name, version);
+        }
+
+        changed
+    }
+
+    /// Returns the manifests of the `[workspace] members`, supporting trailing `/*` globs.
+    pub fn workspace_members(&self) -> Vec<PathBuf> {
+        let root = self.directory();
+        let members = self
+            .document
+            .get("workspace")
+            .and_then(|workspace| workspace.get("members"))
+            .and_then(Item::as_array);
+
+        let mut manifests = vec![];
+        for member in members.into_iter().flatten().filter_map(|m| m.as_str()) {
+            match member.strip_suffix("/*") {
+                Some(parent) => {
+                    let mut dirs = fs::read_dir(root.join(parent))
+                        .into_iter()
+                        .flatten()
+                        .flatten()
+                        .map(|entry| entry.path().join("Cargo.toml"))
+                        .filter(|path| path.is_file())
+                        .collect::<Vec<_>>();
+                    dirs.sort();
+                    manifests.extend(dirs);
+                }
+                None => manifests.push(root.join(member).join("Cargo.toml")),
+            }
+        }
+
+        manifests
+    }
+
+    /// Returns `true` when the manifest declares a `[workspace]`.
+    pub fn is_workspace_root(&self) -> bool {
+        self.document.contains_key("workspace")
+    }
+
+    /// Writes the manifest back to its path.
+    pub fn save(&self) -> Result<(), SemVerError> {
+        Ok(fs::write(&self.path, self.document.to_string())?)
+    }
+
+    fn directory(&self) -> PathBuf {
+        self.path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    fn version_item(&self) -> Result<&Item, SemVerError> {
+        self.document
+            .get("package")
+            .and_then(|package| package.get("version"))
+            .ok_or_else(|| SemVerError::InvalidManifest("missing `[package] version`".to_string()))
+    }
+
+    fn version_item_mut(&mut self) -> Result<&mut Item, SemVerError> {
+        self.document
+            .get_mut("package")
+            .and_then(Item::as_table_like_mut)
+            .and_then(|package| package.get_mut("version"))
+            .ok_or_else(|| SemVerError::InvalidManifest("missing `[package] version`".to_string()))
+    }
+}
+
+impl std::fmt::Display for CargoManifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)
+    }
+}
+
+/// [`bump_manifest`] bumps the `[package] version` of the manifest at `path` according to the comment.
+///
+/// When `update_workspace_dependencies` is set, the requirements on this package declared by the
+/// other members of the enclosing workspace are updated as well.
+///
+/// Returns the new version.
+pub fn bump_manifest(
+    path: impl AsRef<Path>,
+    incomming_commit_comment: &SemanticComment,
+    update_workspace_dependencies: bool,
+) -> Result<SemanticVersion, SemVerError> {
+    let plan = plan_manifest_bump(
+        path,
+        incomming_commit_comment,
+        update_workspace_dependencies,
+    )?;
+    plan.apply()?;
+
+    Ok(plan.version)
+}
+
+/// [`plan_manifest_bump`] computes the changes made by [`bump_manifest`] without writing them.
+pub fn plan_manifest_bump(
+    path: impl AsRef<Path>,
+    incomming_commit_comment: &SemanticComment,
+    update_workspace_dependencies: bool,
+) -> Result<BumpPlan, SemVerError> {
+    let mut manifest = CargoManifest::load(path)?;
+    let original = manifest.to_string();
+    let mut version = manifest.version()?;
+    version.bump(&incomming_commit_comment.semantic_type)?;
+
+    manifest.set_version(&version)?;
+
+    let mut changes = vec![];
+    if update_workspace_dependencies {
+        let name = manifest.package_name()?.to_string();
+        let own_path = fs::canonicalize(manifest.path())?;
+
+        if let Some(root) = find_workspace_root(manifest.path())? {
+            let mut paths = root.workspace_members();
+            paths.push(root.path().to_path_buf());
+
+            for path in paths {
+                if fs::canonicalize(&path)? == own_path {
+                    manifest.set_dependency_requirement(&name, &version);
+                    continue;
+                }
+                let mut dependent = CargoManifest::load(&path)?;
+                let dependent_original = dependent.to_string();
+                if dependent.set_dependency_requirement(&name, &version) {
+                    changes.push(FileChange {
+                        path,
+                        original: dependent_original,
+                        updated: dependent.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    changes.insert(
+        0,
+        FileChange {
+            path: manifest.path().to_path_buf(),
+            original,
+            updated: manifest.to_string(),
+        },
+    );
+
+    Ok(BumpPlan { version, changes })
+}
+
+/// Looks for the closest manifest declaring a `[workspace]`, starting at `manifest_path` itself.
+fn find_workspace_root(manifest_path: &Path) -> Result<Option<CargoManifest>, SemVerError> {
+    let manifest_path = fs::canonicalize(manifest_path)?;
+
+    for directory in manifest_path.ancestors().skip(1) {
+        let candidate = directory.join("Cargo.toml");
+        if candidate.is_file() {
+            let manifest = CargoManifest::load(&candidate)?;
+            if manifest.is_workspace_root() {
+                return Ok(Some(manifest));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn replace_string(item: &mut Item, new_value: String) {
+    let decor = item.as_value().map(|v| v.decor().clone());
+    *item = value(new_value);
+    if let (Some(decor), Some(v)) = (decor, item.as_value_mut()) {
+        *v.decor_mut() = decor;
+    }
+}
+
+fn set_requirement(table: &mut Item, name: &str, version: &SemanticVersion) -> bool {
+    let Some(requirement) = table
+        .as_table_like_mut()
+        .and_then(|table| table.get_mut(name))
+        .and_then(|dep| match dep.is_str() {
+            true => Some(dep),
+            false => dep
+                .as_table_like_mut()
+                .and_then(|dep| dep.get_mut("version")),
+        })
+    else {
+        return false;
+    };
+    let Some(current) = requirement.as_str() else {
+        return false;
+    };
+
+    let operator: String = current
+        .chars()
+        .take_while(|c| !c.is_ascii_digit())
+        .collect();
+    replace_string(
+        requirement,
+        format!("{}{}", operator, bare_version(version)),
+    );
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_set_version_preserves_formatting_and_comments() {
+        let mut manifest = CargoManifest::parse(
+            "Cargo.toml",
+            "# header\n[package]\nname = \"core\"\nversion   = \"1.2.3\"  # current\nedition = \"2021\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.version().unwrap(), SemanticVersion::new(1, 2, 3));
+
+        manifest
+            .set_version(&SemanticVersion::new(2, 0, 0))
+            .unwrap();
+
+        assert_eq!(
+            manifest.to_string(),
+            "# header\n[package]\nname = \"core\"\nversion   = \"2.0.0\"  # current\nedition = \"2021\"\n"
+        );
+    }
+
+    #[test]
+    fn test_set_dependency_requirement_keeps_operator_and_ignores_path_only_dependencies() {
+        let mut manifest = CargoManifest::parse(
+            "Cargo.toml",
+            "[dependencies]\ncore = { path = \"../core\", version = \"^0.1.0\" }\n\n[dev-dependencies]\nother = { path = \"../other\" }\n",
+        )
+        .unwrap();
+        let version = SemanticVersion::new(0, 2, 0);
+
+        assert!(manifest.set_dependency_requirement("core", &version));
+        assert!(!manifest.set_dependency_requirement("other", &version));
+        assert_eq!(
+            manifest.to_string(),
+            "[dependencies]\ncore = { path = \"../core\", version = \"^0.2.0\" }\n\n[dev-dependencies]\nother = { path = \"../other\" }\n"
+        );
+    }
+
+    #[test]
+    fn test_version_returns_error_when_version_is_missing() {
+        let manifest = CargoManifest::parse("Cargo.toml", "[package]\nname = \"core\"\n").unwrap();
+
+        assert_eq!(
+            manifest.version().unwrap_err(),
+            SemVerError::InvalidManifest("missing `[package] version`".to_string())
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tracing::trace;
+
+use crate::{
+    git::{parse_log, FIELD_SEPARATOR, RECORD_SEPARATOR},
+    Commit, SemVerError, Tag, Vcs,
+};
+
+/// Tag Mercurial moves to the latest commit, which is not a release.
+const TIP_TAG: &str = "tip";
+
+/// File where Mercurial records the tags, committed by `hg tag`.
+const TAGS_FILE: &str = ".hgtags";
+
+/// [`Mercurial`] runs `hg` commands in a repository, see [`Vcs`].
+///
+/// Tags are commits adding to `.hgtags`, so [`Vcs::create_tag`] commits on top of the tagged
+/// revision. [`Vcs::commit`] reads `HEAD` as that revision while the working directory parent
+/// only changes `.hgtags`, so that a release just tagged is found at `HEAD`.
+#[derive(Debug, Clone)]
+pub struct Mercurial {
+    directory: PathBuf,
+}
+
+impl Mercurial {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// [`run`] runs `hg` with `args` and returns its trimmed stdout.
+    pub fn run<I, S>(&self, args: I) -> Result<String, SemVerError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let args = args.into_iter().collect::<Vec<_>>();
+        trace!(
+            args = ?args.iter().map(|arg| arg.as_ref()).collect::<Vec<_>>(),
+            "running hg"
+        );
+        let output = Command::new("hg")
+            .arg("--cwd")
+            .arg(&self.directory)
+            .args(["--noninteractive", "--config", "ui.color=never"])
+            .args(args)
+            .env("HGPLAIN", "1")
+            .output()
+            .map_err(|e| SemVerError::MercurialError(e.to_string()))?;
+
+        if !output.status.success() {
+            return Err(SemVerError::MercurialError(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn log(&self, revset: &str) -> Result<Vec<Commit>, SemVerError> {
+        let template = format!(
+            "{{node}}{0}{{author}}{0}{{word(0, date|hgdate)}}{0}{{desc}}{1}",
+            FIELD_SEPARATOR, RECORD_SEPARATOR
+        );
+
+        Ok(parse_log(&self.run([
+            "log",
+            "-r",
+            revset,
+            "-T",
+            template.as_str(),
+        ])?))
+    }
+
+    /// Translates `revision` as [`revision`] does, `HEAD` skipping a tagging commit, see
+    /// [`head_revision`].
+    fn resolve(&self, revision: &str) -> Result<String, SemVerError> {
+        match revision {
+            "HEAD" | "" => Ok(head_revision(&self.changed_paths(".")?).to_string()),
+            _ => Ok(self::revision(revision)),
+        }
+    }
+}
+
+/// Returns the revision `HEAD` stands for given the `changed` paths of the working directory
+/// parent: its own parent when it only changes `.hgtags`, as the commit of `hg tag` does.
+fn head_revision(changed: &[PathBuf]) -> &'static str {
+    match changed {
+        [path] if path == Path::new(TAGS_FILE) => "p1(.)",
+        _ => ".",
+    }
+}
+
+/// [`revset`] translates a git revision range, see [`Vcs`], to a Mercurial revset listing its
+/// commits oldest first.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// assert_eq!(revset(None), "sort(::., rev)");
+/// assert_eq!(revset(Some("v1.0.0..HEAD")), "sort(only(., 'v1.0.0'), rev)");
+/// assert_eq!(revset(Some("api-v1.0.0")), "sort(::'api-v1.0.0', rev)");
+/// ```
+pub fn revset(range: Option<&str>) -> String {
+    let revset = match range.map(|range| range.split_once("..")) {
+        None => "::.".to_string(),
+        Some(Some((from, to))) => format!("only({}, {})", revision(to), revision(from)),
+        Some(None) => format!("::{}", revision(range.unwrap_or_default())),
+    };
+
+    format!("sort({}, rev)", revset)
+}
+
+/// Translates a git revision to a Mercurial one, `HEAD` being the working directory parent and
+/// other names quoted, so that e.g. `api-v1.0.0` is not read as a subtraction.
+fn revision(revision: &str) -> String {
+    match revision {
+        "HEAD" | "" => ".".to_string(),
+        _ => format!("'{}'", revision.replace('\\', "\\\\").replace('\'', "\\'")),
+    }
+}
+
+/// Parses the lines of `hg log -T "{node}{tags % '\t{tag}'}\n"`, leaving out `tip`.
+pub(crate) fn parse_tags(output: &str) -> Vec<Tag> {
+    output
+        .lines()
+        .flat_map(|line| {
+            let mut fields = line.split('\t');
+            let node = fields.next().unwrap_or_default();
+            fields
+                .filter(|name| *name != TIP_TAG && !name.is_empty())
+                .map(move |name| Tag::new(name, node))
+        })
+        .collect()
+}
+
+impl Vcs for Mercurial {
+    fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the active bookmark, which is what git users call a branch, or the named branch.
+    fn current_branch(&self) -> Result<Option<String>, SemVerError> {
+        let bookmark = self.run(["log", "-r", ".", "-T", "{activebookmark}"])?;
+        if !bookmark.is_empty() {
+            return Ok(Some(bookmark));
+        }
+
+        Ok(Some(self.run(["branch"])?))
+    }
+
+    fn tags(&self) -> Result<Vec<Tag>, SemVerError> {
+        let output = self.run([
+            "log",
+            "-r",
+            "tag() and ::.",
+            "-T",
+            "{node}{tags % '\\t{tag}'}\\n",
+        ])?;
+
+        Ok(parse_tags(&output))
+    }
+
+    fn tag_names(&self) -> Result<Vec<String>, SemVerError> {
+        Ok(self
+            .run(["tags", "-q"])?
+            .lines()
+            .filter(|name| *name != TIP_TAG)
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn commits(&self, range: Option<&str>) -> Result<Vec<Commit>, SemVerError> {
+        self.log(&revset(range))
+    }
+
+    fn commit(&self, revision: &str) -> Result<Commit, SemVerError> {
+        self.log(&self.resolve(revision)?)?
+            .pop()
+            .ok_or_else(|| SemVerError::MercurialError(format!("unknown revision `{}`", revision)))
+    }
+
+    fn changed_paths(&self, revision: &str) -> Result<Vec<PathBuf>, SemVerError> {
+        let output = self.run([
+            "log",
+            "-r",
+            &self::revision(revision),
+            "-T",
+            "{join(files, '\\n')}",
+        ])?;
+
+        Ok(output.lines().map(PathBuf::from).collect())
+    }
+
+    /// Returns the paths modified, added or removed in the working directory.
+    fn staged_paths(&self) -> Result<Vec<PathBuf>, SemVerError> {
+        let output = self.run(["status", "--no-status", "-mar"])?;
+
+        Ok(output.lines().map(PathBuf::from).collect())
+    }
+
+    fn create_tag(&self, name: &str, message: &str) -> Result<(), SemVerError> {
+        self.run(["tag", "-m", message, name])?;
+
+        Ok(())
+    }
+
+    fn remote_url(&self) -> Option<String> {
+        self.run(["paths", "default"])
+            .ok()
+            .filter(|url| !url.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags_reads_every_tag_of_a_revision_but_tip() {
+        let output = "a1\tv0.1.0\nb2\tv0.2.0\tapi-v1.0.0\nc3\ttip\n";
+
+        assert_eq!(
+            parse_tags(output),
+            vec![
+                Tag::new("v0.1.0", "a1"),
+                Tag::new("v0.2.0", "b2"),
+                Tag::new("api-v1.0.0", "b2"),
+            ]
+        );
+        assert_eq!(revision("it's"), "'it\\'s'");
+    }
+
+    #[test]
+    fn test_head_revision_skips_a_tagging_commit() {
+        assert_eq!(head_revision(&[PathBuf::from(".hgtags")]), "p1(.)");
+        assert_eq!(head_revision(&[PathBuf::from("src/lib.rs")]), ".");
+        assert_eq!(
+            head_revision(&[PathBuf::from(".hgtags"), PathBuf::from("src/lib.rs")]),
+            "."
+        );
+        assert_eq!(head_revision(&[]), ".");
+    }
+}
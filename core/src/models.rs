@@ -1,6 +1,13 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, num::ParseIntError};
+use std::{cmp::Ordering, convert::TryFrom, fmt, num::ParseIntError, sync::LazyLock};
 use thiserror::Error;
 
 #[non_exhaustive]
@@ -9,7 +16,7 @@ use thiserror::Error;
 ///
 /// Provides error that can occur when parsing comment.
 pub enum SemVerError {
-    #[error("The format provided is invalid! \nPlease follow the format:\n<fix | refact | feat>: this is non breaking change\n<fix | refact | feat>! this is a breaking change")]
+    #[error("The format provided is invalid! \nPlease follow the format:\n<fix | refactor | feat>: this is non breaking change\n<fix | refactor | feat>! this is a breaking change")]
     InvalidCommentFormat,
     #[error("Unexpected semantic type")]
     UnexpectedSemanticType(String),
@@ -19,6 +26,58 @@ pub enum SemVerError {
     InvalidVersionFormat(String),
     #[error("error when converting version numbers")]
     ErrorWhenConvertingVersionNumber,
+    #[error("io error: {0}")]
+    IoError(String),
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(String),
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("unable to update version file {0}: {1}")]
+    VersionFileUpdateFailed(String, String),
+    #[error("invalid policy: {0}")]
+    InvalidPolicy(String),
+    #[error("git error: {0}")]
+    GitError(String),
+    #[error("mercurial error: {0}")]
+    MercurialError(String),
+    #[error("jujutsu error: {0}")]
+    JujutsuError(String),
+    #[error("no semantic version tag found")]
+    NoVersionTag,
+    #[error("no version tag in the shallow history: fetch it with `git fetch --unshallow --tags`, `fetch-depth: 0` in actions/checkout, or `--fetch`")]
+    ShallowHistory,
+    #[error("unknown package `{0}`")]
+    UnknownPackage(String),
+    #[error("forge error: {0}")]
+    ForgeError(String),
+    #[error("registry error: {0}")]
+    RegistryError(String),
+    #[error("the commit does not reference a ticket")]
+    MissingTicket,
+    #[error("{0} is not greater than the existing {1}")]
+    NonMonotonicVersion(String, String),
+    #[error("tag `{0}` already exists")]
+    TagAlreadyExists(String),
+    #[error("no prerelease tag found")]
+    NoPrereleaseTag,
+    #[error("breaking change {1} landed since {0}")]
+    BreakingSincePrerelease(String, String),
+    #[error("invalid version requirement `{0}`")]
+    InvalidRequirement(String),
+    #[error("{1} ({0})")]
+    RuleViolation(String, String),
+    #[error("no failed release to resume")]
+    NoReleaseToResume,
+    #[error("no release to roll back")]
+    NoReleaseToRollback,
+    #[error("the release of {0} failed halfway, finish it with `semver release --resume`")]
+    UnfinishedRelease(String),
+    #[error("{0} cannot be bumped past the largest version component")]
+    VersionOverflow(String),
+    #[error("no signing key: set one with `git config user.signingkey <key>`, and `git config gpg.format ssh` for an SSH key")]
+    SigningKeyMissing,
+    #[error("hook {0} already exists, pass `--force` to replace it")]
+    HookAlreadyExists(String),
 }
 
 impl From<serde_json::Error> for SemVerError {
@@ -27,6 +86,12 @@ impl From<serde_json::Error> for SemVerError {
     }
 }
 
+impl From<std::io::Error> for SemVerError {
+    fn from(error: std::io::Error) -> Self {
+        Self::IoError(error.to_string())
+    }
+}
+
 impl From<ParseIntError> for SemVerError {
     fn from(_: ParseIntError) -> Self {
         Self::ErrorWhenConvertingVersionNumber
@@ -35,17 +100,125 @@ impl From<ParseIntError> for SemVerError {
 
 /// Provides semantic type assumed from the commit message.
 /// # Possible breaking values
-/// - fix!, feat!, refact!
+/// - fix!, feat!, refactor!
 /// # Possible non breaking values
-/// - fix:, feat:, refact:
-#[derive(Debug, Serialize, Deserialize)]
+/// - fix:, feat:, refactor:
+///
+/// `refact` is accepted as an alias of `refactor`.
+///
+/// Any other type allowed by the parser configuration, e.g. `docs` or `chore`, is held by [`SemanticType::Other`].
+///
+/// Reverts, `revert: feat: add X` or git's `Revert "feat: add X"`, hold the reverted comment.
+///
+/// Security fixes, `security: escape user input.`, are accepted whatever the configured types.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SemanticType {
     Fix(SemanticTypeMetadata),
     Feature(SemanticTypeMetadata),
     Refactoring(SemanticTypeMetadata),
+    Security(SemanticTypeMetadata),
+    Other(String, SemanticTypeMetadata),
+    Revert(SemanticTypeMetadata, Box<SemanticComment>),
+}
+
+impl SemanticType {
+    /// Returns the metadata of the semantic type.
+    pub fn metadata(&self) -> &SemanticTypeMetadata {
+        match self {
+            Self::Fix(meta) | Self::Feature(meta) | Self::Refactoring(meta) => meta,
+            Self::Security(meta) => meta,
+            Self::Other(_, meta) | Self::Revert(meta, _) => meta,
+        }
+    }
+
+    pub(crate) fn metadata_mut(&mut self) -> &mut SemanticTypeMetadata {
+        match self {
+            Self::Fix(meta) | Self::Feature(meta) | Self::Refactoring(meta) => meta,
+            Self::Security(meta) => meta,
+            Self::Other(_, meta) | Self::Revert(meta, _) => meta,
+        }
+    }
+
+    /// Returns the keyword used in comments, e.g. `feat`, spelling refactorings as `refactor_keyword`.
+    pub fn keyword(&self, refactor_keyword: RefactorKeyword) -> &str {
+        match self {
+            Self::Fix(_) => "fix",
+            Self::Feature(_) => "feat",
+            Self::Refactoring(_) => refactor_keyword.as_str(),
+            Self::Security(_) => "security",
+            Self::Other(keyword, _) => keyword,
+            Self::Revert(_, _) => "revert",
+        }
+    }
+
+    /// Returns the [`BumpLevel`] implied by the type when no policy applies.
+    ///
+    /// Breaking changes are major, features are minor, fixes, refactorings, security fixes and
+    /// reverts are patches and other types do not bump the version.
+    pub fn default_bump(&self) -> BumpLevel {
+        match self {
+            _ if self.metadata().is_breaking => BumpLevel::Major,
+            Self::Feature(_) => BumpLevel::Minor,
+            Self::Fix(_) | Self::Refactoring(_) | Self::Security(_) | Self::Revert(_, _) => {
+                BumpLevel::Patch
+            }
+            Self::Other(_, _) => BumpLevel::None,
+        }
+    }
+}
+
+/// [`BumpLevel`] is the version component incremented by a change.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    #[default]
+    None,
+    Patch,
+    Minor,
+    Major,
 }
+
+impl BumpLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+}
+
+impl fmt::Display for BumpLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// [`RefactorKeyword`] selects how refactorings are spelled when rendering comments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RefactorKeyword {
+    /// Conventional Commits spelling, `refactor`.
+    #[default]
+    Refactor,
+    /// Legacy spelling, `refact`.
+    Refact,
+}
+
+impl RefactorKeyword {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Refactor => "refactor",
+            Self::Refact => "refact",
+        }
+    }
+}
+
 /// Holds metadata about the semantic type.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticTypeMetadata {
     pub is_breaking: bool,
 }
@@ -66,16 +239,75 @@ impl PartialEq for SemanticType {
             (Self::Refactoring(l_meta), Self::Refactoring(r_meta)) => {
                 l_meta.is_breaking == r_meta.is_breaking
             }
+            (Self::Security(l_meta), Self::Security(r_meta)) => {
+                l_meta.is_breaking == r_meta.is_breaking
+            }
+            (Self::Other(l_keyword, l_meta), Self::Other(r_keyword, r_meta)) => {
+                l_keyword == r_keyword && l_meta.is_breaking == r_meta.is_breaking
+            }
+            (Self::Revert(l_meta, l_reverted), Self::Revert(r_meta, r_reverted)) => {
+                l_meta.is_breaking == r_meta.is_breaking && l_reverted == r_reverted
+            }
 
             _ => false,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// [`ReferenceKind`] tells what a [`Reference`] points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// An issue or pull request, `#123`.
+    Issue,
+    /// A GitHub issue or pull request, `GH-123`.
+    GitHub,
+    /// A GitLab merge request, `!45`.
+    MergeRequest,
+}
+
+/// [`Reference`] holds an issue or pull request referenced by a commit, e.g. `closes #123`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    pub number: u64,
+    /// Set when the reference follows a closing keyword, e.g. `closes`, `fixes` or `resolves`.
+    pub closes: bool,
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ReferenceKind::Issue => write!(f, "#{}", self.number),
+            ReferenceKind::GitHub => write!(f, "GH-{}", self.number),
+            ReferenceKind::MergeRequest => write!(f, "!{}", self.number),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticComment {
     pub comment: String,
     pub semantic_type: SemanticType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Issues and pull requests referenced by the subject, body and footers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<Reference>,
+    /// Tickets matched by the configured ticket pattern, e.g. `PROJ-123`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tickets: Vec<String>,
+    /// Git trailers of the last paragraph of the message, in order,
+    /// e.g. `("Co-authored-by", "Jane Doe <jane@example.com>")`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trailers: Vec<(String, String)>,
+    /// Text of the `BREAKING CHANGE` footers, e.g. migration notes, which may span lines.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breaking_changes: Vec<String>,
+    /// Spelling of refactorings in [`SemanticComment::to_commit_string`], set by the parser
+    /// from its configuration, see [`crate::CommentParserBuilder::refactor_keyword`].
+    #[serde(skip)]
+    pub refactor_keyword: RefactorKeyword,
 }
 
 impl SemanticComment {
@@ -83,9 +315,98 @@ impl SemanticComment {
         Self {
             comment,
             semantic_type,
+            scope: None,
+            references: vec![],
+            tickets: vec![],
+            trailers: vec![],
+            breaking_changes: vec![],
+            refactor_keyword: RefactorKeyword::default(),
         }
     }
 
+    /// [`with_scope`] sets the scope, e.g. `api` for `feat(api): new endpoint.`.
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// [`to_commit_string`] renders the comment back to its commit form,
+    /// e.g. `refactor(parser)! simplify prefix matching.`, spelling refactorings as the
+    /// parser was configured to.
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let comment = SemanticComment::try_from("refact(parser): simplify.").unwrap();
+    /// assert_eq!(comment.to_commit_string(), "refactor(parser): simplify.");
+    /// assert_eq!(comment.to_commit_string_with(RefactorKeyword::Refact), "refact(parser): simplify.");
+    /// ```
+    pub fn to_commit_string(&self) -> String {
+        self.to_commit_string_with(self.refactor_keyword)
+    }
+
+    /// [`to_commit_string_with`] renders the comment spelling refactorings as `refactor_keyword`.
+    pub fn to_commit_string_with(&self, refactor_keyword: RefactorKeyword) -> String {
+        let scope = match &self.scope {
+            Some(scope) => format!("({})", scope),
+            None => String::new(),
+        };
+        let separator = match self.semantic_type.metadata().is_breaking {
+            true => '!',
+            false => ':',
+        };
+
+        format!(
+            "{}{}{} {}",
+            self.semantic_type.keyword(refactor_keyword),
+            scope,
+            separator,
+            self.comment
+        )
+    }
+
+    /// [`trailer`] returns the values of the trailer `key`, compared case insensitively.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let comment = CommentParser::default()
+    ///     .parse_message("feat: pairing.\n\nCo-authored-by: Jane <jane@example.com>\nSigned-off-by: Joe <joe@example.com>")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(comment.co_authors(), vec!["Jane <jane@example.com>"]);
+    /// assert_eq!(comment.signed_off_by(), vec!["Joe <joe@example.com>"]);
+    /// assert_eq!(comment.trailer("signed-off-by"), comment.signed_off_by());
+    /// ```
+    pub fn trailer(&self, key: &str) -> Vec<&str> {
+        self.trailers
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// Returns the `Co-authored-by` trailers.
+    pub fn co_authors(&self) -> Vec<&str> {
+        self.trailer("Co-authored-by")
+    }
+
+    /// Returns the `Signed-off-by` trailers.
+    pub fn signed_off_by(&self) -> Vec<&str> {
+        self.trailer("Signed-off-by")
+    }
+
+    /// Returns the `Deprecated` footers, e.g. `Deprecated: the v1 endpoints, use v2.`.
+    ///
+    /// Deprecations are announced in the changelog without making the change breaking.
+    pub fn deprecations(&self) -> Vec<&str> {
+        self.trailer("Deprecated")
+    }
+
+    /// Returns the `Reviewed-by` trailers.
+    pub fn reviewed_by(&self) -> Vec<&str> {
+        self.trailer("Reviewed-by")
+    }
+
     /// [`as_json_string`] returns json representation of the structure.
     pub fn as_json_string(&self) -> Result<String, SemVerError> {
         Ok(serde_json::to_string(&self)?)
@@ -94,37 +415,380 @@ impl SemanticComment {
 
 impl PartialEq for SemanticComment {
     fn eq(&self, other: &Self) -> bool {
-        self.comment == other.comment && self.semantic_type == other.semantic_type
+        self.comment == other.comment
+            && self.semantic_type == other.semantic_type
+            && self.scope == other.scope
+            && self.references == other.references
+            && self.tickets == other.tickets
+            && self.trailers == other.trailers
+            && self.breaking_changes == other.breaking_changes
     }
 }
 
 /// [`SemantiVersion`] provides a structure to hold version string.
 ///
-/// **expected format:** `v1.0.0`.
-#[derive(Debug, PartialEq)]
+/// **expected format:** `v1.0.0`, optionally followed by a prerelease and build metadata,
+/// e.g. `v1.0.0-rc.1+sha.abc1234`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct SemanticVersion {
-    pub major: u32,
-    pub minor: u32,
-    pub patch: u32,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot separated prerelease identifiers, e.g. `rc.1`.
+    pub pre: Option<String>,
+    /// Dot separated build metadata, e.g. `sha.abc1234`, ignored by precedence.
+    pub build: Option<String>,
 }
 
-impl Default for SemanticVersion {
-    fn default() -> Self {
-        Self {
-            major: 0,
-            minor: 0,
-            patch: 0,
+/// [`ComparisonReason`] names the component that decided a version comparison.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComparisonReason {
+    Equal,
+    Major(u64, u64),
+    Minor(u64, u64),
+    Patch(u64, u64),
+    /// Only one of the versions is a prerelease, the release being greater.
+    Prerelease(Option<String>, Option<String>),
+    /// The prerelease identifiers at a 1-based index differ, `None` for the side running out of
+    /// identifiers first, e.g. `alpha` and `beta` at 1 for `1.0.0-alpha` and `1.0.0-beta`.
+    PrereleaseIdentifier(usize, Option<String>, Option<String>),
+}
+
+/// [`VersionComparison`] holds the result of [`SemanticVersion::explain_cmp`].
+///
+/// Its [`fmt::Display`] implementation describes which component decided the comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionComparison {
+    pub ordering: Ordering,
+    pub reason: ComparisonReason,
+}
+
+impl fmt::Display for VersionComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self.ordering {
+            Ordering::Less => "<",
+            Ordering::Equal => "=",
+            Ordering::Greater => ">",
+        };
+
+        match &self.reason {
+            ComparisonReason::Equal => write!(f, "all components are equal"),
+            ComparisonReason::Major(l, r) => write!(f, "major `{}` {} `{}`", l, symbol, r),
+            ComparisonReason::Minor(l, r) => write!(f, "minor `{}` {} `{}`", l, symbol, r),
+            ComparisonReason::Patch(l, r) => write!(f, "patch `{}` {} `{}`", l, symbol, r),
+            ComparisonReason::Prerelease(l, r) => write!(
+                f,
+                "prerelease `{}` {} `{}`",
+                l.as_deref().unwrap_or("none"),
+                symbol,
+                r.as_deref().unwrap_or("none")
+            ),
+            ComparisonReason::PrereleaseIdentifier(index, l, r) => write!(
+                f,
+                "prerelease `{}` {} `{}` at identifier {}",
+                l.as_deref().unwrap_or("none"),
+                symbol,
+                r.as_deref().unwrap_or("none"),
+                index
+            ),
+        }
+    }
+}
+
+/// [`DiffKind`] names the most significant component separating two versions.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+}
+
+impl fmt::Display for DiffKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            DiffKind::Major => "major",
+            DiffKind::Minor => "minor",
+            DiffKind::Patch => "patch",
+            DiffKind::Prerelease => "prerelease",
+        };
+
+        write!(f, "{}", kind)
+    }
+}
+
+/// [`VersionDiff`] holds the result of [`SemanticVersion::diff`].
+///
+/// Its [`fmt::Display`] implementation renders `lt`, `eq` or `gt` followed by the kind, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionDiff {
+    pub ordering: Ordering,
+    /// Component separating the versions, `None` when they have the same precedence.
+    pub kind: Option<DiffKind>,
+}
+
+impl VersionDiff {
+    /// [`keyword`] returns `lt`, `eq` or `gt`.
+    pub fn keyword(&self) -> &'static str {
+        match self.ordering {
+            Ordering::Less => "lt",
+            Ordering::Equal => "eq",
+            Ordering::Greater => "gt",
+        }
+    }
+
+    /// [`as_json_string`] returns json representation of the diff.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+impl fmt::Display for VersionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Some(kind) => write!(f, "{} {}", self.keyword(), kind),
+            None => write!(f, "{}", self.keyword()),
         }
     }
 }
 
+/// Serializes as `{"ordering": "lt", "kind": "minor"}`.
+impl Serialize for VersionDiff {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut diff = serializer.serialize_struct("VersionDiff", 2)?;
+        diff.serialize_field("ordering", self.keyword())?;
+        diff.serialize_field("kind", &self.kind)?;
+        diff.end()
+    }
+}
+
+impl SemanticVersion {
+    /// [`new`] returns the release `v<major>.<minor>.<patch>`, without prerelease or build.
+    ///
+    /// Like the accessors, it is a `const fn`, e.g. for the static tables of other crates.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// static SUPPORTED: [SemanticVersion; 2] = [SemanticVersion::new(1, 4, 0), SemanticVersion::new(2, 0, 0)];
+    /// const LATEST_MAJOR: u64 = SUPPORTED[1].major();
+    ///
+    /// assert_eq!(LATEST_MAJOR, 2);
+    /// assert!(!SUPPORTED[0].is_prerelease());
+    /// ```
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        SemanticVersion {
+            major,
+            minor,
+            patch,
+            pre: None,
+            build: None,
+        }
+    }
+
+    /// [`major`] returns the major component.
+    pub const fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// [`minor`] returns the minor component.
+    pub const fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// [`patch`] returns the patch component.
+    pub const fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// [`is_prerelease`] returns `true` when the version has a prerelease, e.g. `v2.0.0-rc.1`.
+    pub const fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// [`release`] returns the version without its prerelease and build metadata.
+    pub const fn release(&self) -> Self {
+        Self::new(self.major, self.minor, self.patch)
+    }
+
+    /// [`diff`] compares two versions by precedence and names the component separating them.
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let left = SemanticVersion::try_from("v1.2.3").unwrap();
+    /// let right = SemanticVersion::try_from("v1.3.0-rc.1").unwrap();
+    ///
+    /// assert_eq!(left.diff(&right).to_string(), "lt minor");
+    /// assert_eq!(right.diff(&SemanticVersion::new(1, 3, 0)).to_string(), "lt prerelease");
+    /// assert_eq!(left.diff(&left).to_string(), "eq");
+    /// ```
+    pub fn diff(&self, other: &SemanticVersion) -> VersionDiff {
+        let comparison = self.explain_cmp(other);
+
+        VersionDiff {
+            ordering: comparison.ordering,
+            kind: match comparison.reason {
+                ComparisonReason::Equal => None,
+                ComparisonReason::Major(_, _) => Some(DiffKind::Major),
+                ComparisonReason::Minor(_, _) => Some(DiffKind::Minor),
+                ComparisonReason::Patch(_, _) => Some(DiffKind::Patch),
+                ComparisonReason::Prerelease(_, _)
+                | ComparisonReason::PrereleaseIdentifier(_, _, _) => Some(DiffKind::Prerelease),
+            },
+        }
+    }
+
+    /// [`explain_cmp`] compares two versions and reports which component decided the result.
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let comparison = SemanticVersion::try_from("v1.2.3").unwrap()
+    ///     .explain_cmp(&SemanticVersion::try_from("v1.10.0").unwrap());
+    ///
+    /// assert_eq!(comparison.ordering, std::cmp::Ordering::Less);
+    /// assert_eq!(comparison.to_string(), "minor `2` < `10`");
+    /// ```
+    pub fn explain_cmp(&self, other: &SemanticVersion) -> VersionComparison {
+        let components = [
+            (
+                self.major,
+                other.major,
+                ComparisonReason::Major as fn(u64, u64) -> _,
+            ),
+            (self.minor, other.minor, ComparisonReason::Minor),
+            (self.patch, other.patch, ComparisonReason::Patch),
+        ];
+
+        for (left, right, reason) in components {
+            let ordering = left.cmp(&right);
+            if ordering != Ordering::Equal {
+                return VersionComparison {
+                    ordering,
+                    reason: reason(left, right),
+                };
+            }
+        }
+
+        let (ordering, identifier) = compare_prerelease(self.pre.as_deref(), other.pre.as_deref());
+        VersionComparison {
+            ordering,
+            reason: match (ordering, identifier) {
+                (Ordering::Equal, _) => ComparisonReason::Equal,
+                (_, Some((index, left, right))) => ComparisonReason::PrereleaseIdentifier(
+                    index,
+                    left.map(str::to_string),
+                    right.map(str::to_string),
+                ),
+                (_, None) => ComparisonReason::Prerelease(self.pre.clone(), other.pre.clone()),
+            },
+        }
+    }
+}
+
+/// Compares prereleases following the semver precedence: a release is greater than its
+/// prereleases, numeric identifiers compare numerically and are lower than alphanumeric ones,
+/// and a larger set of identifiers is greater when the preceding ones are equal.
+///
+/// Returns the ordering with, when both are prereleases and differ, the 1-based index of the
+/// identifiers deciding it and these identifiers, `None` for the side running out of them.
+fn compare_prerelease<'a>(
+    left: Option<&'a str>,
+    right: Option<&'a str>,
+) -> (Ordering, Option<PrereleaseIdentifiers<'a>>) {
+    let (left, right) = match (left, right) {
+        (None, None) => return (Ordering::Equal, None),
+        (None, Some(_)) => return (Ordering::Greater, None),
+        (Some(_), None) => return (Ordering::Less, None),
+        (Some(left), Some(right)) => (left, right),
+    };
+
+    let mut left_identifiers = left.split('.');
+    let mut right_identifiers = right.split('.');
+    for index in 1.. {
+        let (left, right) = match (left_identifiers.next(), right_identifiers.next()) {
+            (None, None) => break,
+            (Some(left), None) => return (Ordering::Greater, Some((index, Some(left), None))),
+            (None, Some(right)) => return (Ordering::Less, Some((index, None, Some(right)))),
+            (Some(left), Some(right)) => (left, right),
+        };
+        let ordering = match (left.parse::<u64>(), right.parse::<u64>()) {
+            (Ok(l), Ok(r)) => l.cmp(&r),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => left.cmp(right),
+        };
+        if ordering != Ordering::Equal {
+            return (ordering, Some((index, Some(left), Some(right))));
+        }
+    }
+
+    (Ordering::Equal, None)
+}
+
+/// Index and identifiers deciding a prerelease comparison, see [`compare_prerelease`].
+type PrereleaseIdentifiers<'a> = (usize, Option<&'a str>, Option<&'a str>);
+
+/// Serializes as `v<major>.<minor>.<patch>[-<pre>][+<build>]`.
+impl Serialize for SemanticVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&String::from(self.clone()))
+    }
+}
+
+/// Deserializes `v<major>.<minor>.<patch>`, the `v` being optional.
+impl<'de> Deserialize<'de> for SemanticVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = String::deserialize(deserializer)?;
+
+        SemanticVersion::try_from(format!("v{}", version.trim_start_matches('v')).as_str())
+            .map_err(|_| serde::de::Error::custom(format!("invalid version `{}`", version)))
+    }
+}
+
+/// Orders versions by precedence, see [`SemanticVersion::explain_cmp`], then by build metadata
+/// so that the ordering agrees with equality.
+impl Ord for SemanticVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.explain_cmp(other)
+            .ordering
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for SemanticVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Pattern of `v<major>.<minor>.<patch>[-<pre>][+<build>]`, see [`SemanticVersion::try_from`].
+pub(crate) const VERSION_PATTERN: &str = r"^v(?P<major>[0-9]+)\.(?P<minor>[0-9]+)\.(?P<patch>[0-9]+)(?:-(?P<pre>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?(?:\+(?P<build>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?$";
+
+/// Compiles one of the crate's own patterns. They are literals exercised by the tests, so a
+/// failure here is a bug in the crate and never depends on the parsed input.
+#[allow(clippy::expect_used)]
+pub(crate) fn literal_regex(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("the crate's patterns are valid")
+}
+
+/// Returns the text of the group `name` of `captures`, empty when it did not participate.
+pub(crate) fn group<'h>(captures: &regex::Captures<'h>, name: &str) -> &'h str {
+    captures.name(name).map_or("", |m| m.as_str())
+}
+
 ///
 /// # Example
 /// ```
 /// # use core::*;
-/// assert_eq!(SemanticVersion::try_from("v1.2.3").unwrap(), SemanticVersion{ major: 1, minor: 2, patch: 3 });
-/// assert_eq!(SemanticVersion::try_from("v40.2.8").unwrap(), SemanticVersion{ major: 40, minor: 2, patch: 8 });
-/// assert_eq!(SemanticVersion::try_from("v1.300.3").unwrap(), SemanticVersion{ major: 1, minor: 300, patch: 3 });
+/// assert_eq!(SemanticVersion::try_from("v1.2.3").unwrap(), SemanticVersion::new(1, 2, 3));
+/// assert_eq!(SemanticVersion::try_from("v40.2.8").unwrap(), SemanticVersion::new(40, 2, 8));
+/// assert_eq!(SemanticVersion::try_from("v1.300.3").unwrap(), SemanticVersion::new(1, 300, 3));
+/// assert_eq!(SemanticVersion::try_from("v2.0.0-rc.1+sha.abc1234").unwrap().pre.as_deref(), Some("rc.1"));
 ///
 /// assert_eq!(SemanticVersion::try_from("version-1").unwrap_err(), SemVerError::InvalidVersionFormat("version-1".to_string()));
 /// assert_eq!(SemanticVersion::try_from("v.34.34.2").unwrap_err(), SemVerError::InvalidVersionFormat("v.34.34.2".to_string()));
@@ -133,32 +797,40 @@ impl TryFrom<&str> for SemanticVersion {
     type Error = SemVerError;
 
     fn try_from(version_str: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(r"v[0-9]+(\.{1}[0-9]+){2}").unwrap();
-        if !re.is_match(version_str) {
-            return Err(SemVerError::InvalidVersionFormat(version_str.to_string()));
-        }
-
-        let version_numbers = &version_str[1..version_str.len()];
-        let version_numbers_vector: Vec<&str> = version_numbers.split(".").collect();
+        static VERSION: LazyLock<Regex> = LazyLock::new(|| literal_regex(VERSION_PATTERN));
+        let captures = VERSION
+            .captures(version_str)
+            .ok_or_else(|| SemVerError::InvalidVersionFormat(version_str.to_string()))?;
 
         Ok(SemanticVersion {
-            major: version_numbers_vector[0].parse()?,
-            minor: version_numbers_vector[1].parse()?,
-            patch: version_numbers_vector[2].parse()?,
+            major: group(&captures, "major").parse()?,
+            minor: group(&captures, "minor").parse()?,
+            patch: group(&captures, "patch").parse()?,
+            pre: captures.name("pre").map(|pre| pre.as_str().to_string()),
+            build: captures
+                .name("build")
+                .map(|build| build.as_str().to_string()),
         })
     }
 }
 
-/// Returns the version in following format: `v<major>.<minor>.<patch>`
+/// Returns the version in following format: `v<major>.<minor>.<patch>[-<pre>][+<build>]`
 /// # Example:
 /// ```
 /// # use core::*;
-/// assert_eq!(String::from(SemanticVersion{ major: 1, minor: 2, patch: 3 }), "v1.2.3");
-/// assert_eq!(String::from(SemanticVersion{ major: 23, minor: 0, patch: 2 }), "v23.0.2");
+/// assert_eq!(String::from(SemanticVersion::new(1, 2, 3)), "v1.2.3");
+/// assert_eq!(String::from(SemanticVersion::new(23, 0, 2)), "v23.0.2");
 /// ```
 impl From<SemanticVersion> for String {
     fn from(sem_ver: SemanticVersion) -> Self {
-        format!("v{}.{}.{}", sem_ver.major, sem_ver.minor, sem_ver.patch)
+        let mut version = format!("v{}.{}.{}", sem_ver.major, sem_ver.minor, sem_ver.patch);
+        if let Some(pre) = &sem_ver.pre {
+            version.push_str(&format!("-{}", pre));
+        }
+        if let Some(build) = &sem_ver.build {
+            version.push_str(&format!("+{}", build));
+        }
+        version
     }
 }
 
@@ -169,13 +841,143 @@ mod test {
     #[test]
     fn semantic_version_try_from_creates_right_semantic_version_from_version_string() {
         let semantic_version = SemanticVersion::try_from("v1.2.3").unwrap();
+        assert_eq!(semantic_version, SemanticVersion::new(1, 2, 3));
+    }
+
+    #[test]
+    fn semantic_version_parses_u64_max_components() {
+        let max = format!("v{0}.{0}.{0}", u64::MAX);
+        let version = SemanticVersion::try_from(max.as_str()).unwrap();
+
+        assert_eq!(version, SemanticVersion::new(u64::MAX, u64::MAX, u64::MAX));
+        assert_eq!(String::from(version), max);
+        assert!(SemanticVersion::try_from("v18446744073709551616.0.0").is_err());
+        assert!(SemanticVersion::try_from("v1.18446744073709551616.0").is_err());
+        assert!(SemanticVersion::try_from("v1.0.18446744073709551616").is_err());
+    }
+
+    #[test]
+    fn semantic_version_try_from_rejects_malformed_versions_without_panicking() {
+        for invalid in [
+            "v99999999999999999999.0.0",
+            "vé.1.2",
+            "v1.2.3-é",
+            "v1.2.3+ü",
+            "v",
+        ] {
+            assert!(SemanticVersion::try_from(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn semantic_version_explain_cmp_reports_deciding_component() {
+        let cases = vec![
+            (
+                "v1.2.3",
+                "v2.0.0",
+                Ordering::Less,
+                ComparisonReason::Major(1, 2),
+            ),
+            (
+                "v1.3.0",
+                "v1.2.9",
+                Ordering::Greater,
+                ComparisonReason::Minor(3, 2),
+            ),
+            (
+                "v1.2.3",
+                "v1.2.4",
+                Ordering::Less,
+                ComparisonReason::Patch(3, 4),
+            ),
+            ("v1.2.3", "v1.2.3", Ordering::Equal, ComparisonReason::Equal),
+            (
+                "v1.2.3-rc.1",
+                "v1.2.3",
+                Ordering::Less,
+                ComparisonReason::Prerelease(Some("rc.1".to_string()), None),
+            ),
+            (
+                "v1.2.3-rc.10",
+                "v1.2.3-rc.9",
+                Ordering::Greater,
+                ComparisonReason::PrereleaseIdentifier(
+                    2,
+                    Some("10".to_string()),
+                    Some("9".to_string()),
+                ),
+            ),
+            (
+                "v1.2.3-alpha",
+                "v1.2.3-alpha.1",
+                Ordering::Less,
+                ComparisonReason::PrereleaseIdentifier(2, None, Some("1".to_string())),
+            ),
+            (
+                "v1.2.3+sha.a",
+                "v1.2.3+sha.b",
+                Ordering::Equal,
+                ComparisonReason::Equal,
+            ),
+        ];
+
+        for (left, right, ordering, reason) in cases {
+            let left = SemanticVersion::try_from(left).unwrap();
+            let right = SemanticVersion::try_from(right).unwrap();
+
+            assert_eq!(
+                left.explain_cmp(&right),
+                VersionComparison { ordering, reason }
+            );
+        }
+    }
+
+    #[test]
+    fn semantic_version_diff_names_the_separating_component() {
+        let cases = [
+            ("v1.2.3", "v2.0.0", "lt major"),
+            ("v1.3.0", "v1.2.9", "gt minor"),
+            ("v1.2.3", "v1.2.4", "lt patch"),
+            ("v1.3.0-rc.1", "v1.3.0", "lt prerelease"),
+            ("v1.3.0-rc.10", "v1.3.0-rc.9", "gt prerelease"),
+            ("v1.2.3+sha.a", "v1.2.3+sha.b", "eq"),
+        ];
+
+        for (left, right, expected) in cases {
+            let left = SemanticVersion::try_from(left).unwrap();
+            let right = SemanticVersion::try_from(right).unwrap();
+
+            assert_eq!(left.diff(&right).to_string(), expected);
+        }
         assert_eq!(
-            semantic_version,
-            SemanticVersion {
-                major: 1,
-                minor: 2,
-                patch: 3
+            SemanticVersion::new(1, 2, 3).diff(&SemanticVersion::new(1, 3, 0)),
+            VersionDiff {
+                ordering: Ordering::Less,
+                kind: Some(DiffKind::Minor),
             }
         );
     }
+
+    #[test]
+    fn semantic_version_explain_cmp_renders_the_deciding_prerelease_identifier() {
+        let explain = |left: &str, right: &str| {
+            SemanticVersion::try_from(left)
+                .unwrap()
+                .explain_cmp(&SemanticVersion::try_from(right).unwrap())
+                .to_string()
+        };
+
+        assert_eq!(
+            explain("v1.0.0-alpha", "v1.0.0-beta"),
+            "prerelease `alpha` < `beta` at identifier 1"
+        );
+        assert_eq!(
+            explain("v1.0.0-rc.1.a", "v1.0.0-rc.1"),
+            "prerelease `a` > `none` at identifier 3"
+        );
+        assert_eq!(
+            explain("v1.0.0", "v1.0.0-rc.1"),
+            "prerelease `none` > `rc.1`"
+        );
+    }
 }
@@ -0,0 +1,370 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    path::Path,
+};
+
+use regex::Regex;
+
+use crate::{Config, ParsedCommit, SemVerError, SemanticVersion, Tag, TagFormat, Vcs};
+
+/// [`Package`] holds a package of a monorepo, released independently with tags like `api-v1.4.0`.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let package = Package::new("api", &["services/api/**", "proto/*.proto"]).unwrap();
+///
+/// assert!(package.contains("services/api/src/main.rs"));
+/// assert!(package.contains("proto/api.proto"));
+/// assert!(!package.contains("proto/v1/api.proto"));
+/// assert_eq!(package.tag(&TagFormat::default(), &SemanticVersion::new(1, 4, 0)).unwrap(), "api-v1.4.0");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Package {
+    pub name: String,
+    paths: Vec<Regex>,
+    tag_prefix: Option<String>,
+}
+
+impl Package {
+    /// [`new`] compiles the path globs of the package.
+    ///
+    /// `*` and `?` do not match `/`, `**` matches any number of directories and
+    /// a glob also matches everything below the directory it names.
+    pub fn new<S: AsRef<str>>(name: impl Into<String>, globs: &[S]) -> Result<Self, SemVerError> {
+        let paths = globs
+            .iter()
+            .map(|glob| {
+                Regex::new(&glob_to_regex(glob.as_ref())).map_err(|e| {
+                    SemVerError::InvalidConfig(format!("glob `{}`: {}", glob.as_ref(), e))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name: name.into(),
+            paths,
+            tag_prefix: None,
+        })
+    }
+
+    /// [`with_tag_prefix`] sets the prefix of the release tags, e.g. `services/api/`, instead
+    /// of `<name>-`.
+    pub fn with_tag_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.tag_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Prefix of the release tags of the package, `<name>-` unless configured, e.g. `api-`.
+    pub fn tag_prefix(&self) -> String {
+        match &self.tag_prefix {
+            Some(prefix) => prefix.clone(),
+            None => format!("{}-", self.name),
+        }
+    }
+
+    /// Returns the format of the release tags of the package, `format` after the [`tag_prefix`].
+    ///
+    /// [`tag_prefix`]: Package::tag_prefix
+    pub fn tag_format(&self, format: &TagFormat) -> Result<TagFormat, SemVerError> {
+        format.with_prefix(&self.tag_prefix())
+    }
+
+    /// Returns the release tag of `version` in `format`, e.g. `api-v1.4.0`.
+    pub fn tag(
+        &self,
+        format: &TagFormat,
+        version: &SemanticVersion,
+    ) -> Result<String, SemVerError> {
+        Ok(self.tag_format(format)?.render(version))
+    }
+
+    /// Returns `true` when `path`, relative to the repository root, belongs to the package.
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref().to_string_lossy().replace('\\', "/");
+
+        self.paths.iter().any(|glob| glob.is_match(&path))
+    }
+
+    /// Returns `true` when the commit is scoped to the package or touches one of its `paths`.
+    pub fn includes<P: AsRef<Path>>(&self, commit: &ParsedCommit, paths: &[P]) -> bool {
+        commit.comment.scope.as_deref() == Some(self.name.as_str())
+            || paths.iter().any(|path| self.contains(path))
+    }
+}
+
+impl Config {
+    /// [`package`] returns the package `name` declared in the `[packages]` section.
+    pub fn package(&self, name: &str) -> Result<Package, SemVerError> {
+        let Some(config) = self.packages.get(name) else {
+            return Err(SemVerError::UnknownPackage(name.to_string()));
+        };
+        let package = Package::new(name, config.paths())?;
+
+        Ok(match config.tag_prefix() {
+            Some(prefix) => package.with_tag_prefix(prefix),
+            None => package,
+        })
+    }
+
+    /// [`latest_package_tags`] returns the greatest release tag of each tagged package, by
+    /// precedence among the tags of its prefix only, prereleases left out.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use core::*;
+    /// let config = Config::from_toml_str(r#"
+    /// [packages]
+    /// api = ["services/api"]
+    /// cli = { paths = ["cli"], tag_prefix = "tools/cli/" }
+    /// "#).unwrap();
+    ///
+    /// for (package, (tag, _)) in config.latest_package_tags(&Git::new(".")).unwrap() {
+    ///     println!("{}: {}", package, tag.name);
+    /// }
+    /// ```
+    pub fn latest_package_tags(
+        &self,
+        vcs: &dyn Vcs,
+    ) -> Result<BTreeMap<String, (Tag, SemanticVersion)>, SemVerError> {
+        let tags = vcs.tags()?;
+        let format = self.tag_format()?;
+
+        let mut latest_tags = BTreeMap::new();
+        for name in self.packages.keys() {
+            let format = self.package(name)?.tag_format(&format)?;
+            let latest = tags
+                .iter()
+                .filter_map(|tag| Some((tag, format.parse(&tag.name)?)))
+                .filter(|(_, version)| !version.is_prerelease())
+                .max_by(|(_, l), (_, r)| l.cmp(r));
+            if let Some((tag, version)) = latest {
+                latest_tags.insert(name.clone(), (tag.clone(), version));
+            }
+        }
+
+        Ok(latest_tags)
+    }
+
+    /// [`dependents`] returns the packages depending, directly or not, on the package `name`,
+    /// each with the dependency it is reached through, closest first.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str(r#"
+    /// [packages]
+    /// core = ["core"]
+    /// api = { paths = ["api"], depends_on = ["core"] }
+    /// web = { paths = ["web"], depends_on = ["api"] }
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     config.dependents("core").unwrap(),
+    ///     vec![("api".to_string(), "core".to_string()), ("web".to_string(), "api".to_string())]
+    /// );
+    /// ```
+    pub fn dependents(&self, name: &str) -> Result<Vec<(String, String)>, SemVerError> {
+        for dependency in self.packages.values().flat_map(|p| p.depends_on()) {
+            if !self.packages.contains_key(dependency) {
+                return Err(SemVerError::UnknownPackage(dependency.clone()));
+            }
+        }
+        if !self.packages.contains_key(name) {
+            return Err(SemVerError::UnknownPackage(name.to_string()));
+        }
+
+        let mut dependents = vec![];
+        let mut queue = VecDeque::from([name.to_string()]);
+        while let Some(dependency) = queue.pop_front() {
+            for (dependent, package) in &self.packages {
+                let reached = dependent == name || dependents.iter().any(|(d, _)| d == dependent);
+
+                if !reached && package.depends_on().contains(&dependency) {
+                    dependents.push((dependent.clone(), dependency.clone()));
+                    queue.push_back(dependent.clone());
+                }
+            }
+        }
+
+        Ok(dependents)
+    }
+}
+
+pub(crate) fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.trim_end_matches('/').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                match chars.peek() == Some(&'/') {
+                    true => {
+                        chars.next();
+                        regex.push_str("(.*/)?");
+                    }
+                    false => regex.push_str(".*"),
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push_str("(/.*)?$");
+
+    regex
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::git::test::{commit, init_repository};
+    use crate::Commit;
+
+    use super::*;
+
+    #[test]
+    fn test_package_includes_scoped_commits_and_commits_touching_its_paths() {
+        let package = Package::new("api", &["services/api"]).unwrap();
+        let parsed = |message: &str| ParsedCommit {
+            commit: Commit::new("a1", message),
+            comment: message.try_into().unwrap(),
+        };
+
+        assert!(package.includes(&parsed("feat(api): new endpoint."), &[] as &[&str]));
+        assert!(package.includes(&parsed("fix: a fix."), &["services/api/src/lib.rs"]));
+        assert!(!package.includes(&parsed("fix: a fix."), &["services/api-gateway/lib.rs"]));
+        assert!(!package.includes(&parsed("feat(web): a feature."), &["web/index.ts"]));
+    }
+
+    #[test]
+    fn test_config_package_returns_error_for_unknown_package() {
+        let config = Config::from_toml_str("[packages]\napi = [\"services/api/**\"]\n").unwrap();
+
+        assert_eq!(config.package("api").unwrap().tag_prefix(), "api-");
+        assert_eq!(
+            config.package("web").unwrap_err(),
+            SemVerError::UnknownPackage("web".to_string())
+        );
+    }
+
+    #[test]
+    fn test_package_tag_follows_the_tag_format() {
+        let config = Config::from_toml_str(
+            "tag_format = \"release/{major}.{minor}.{patch}{-pre}\"\n[packages]\ncli = { paths = [\"cli\"], tag_prefix = \"tools/cli/\" }\n",
+        )
+        .unwrap();
+        let format = config.tag_format().unwrap();
+        let package = config.package("cli").unwrap();
+        let version = SemanticVersion {
+            pre: Some("rc.1".to_string()),
+            ..SemanticVersion::new(1, 4, 0)
+        };
+
+        assert_eq!(
+            package.tag(&format, &version).unwrap(),
+            "tools/cli/release/1.4.0-rc.1"
+        );
+        assert_eq!(
+            package
+                .tag_format(&format)
+                .unwrap()
+                .parse("tools/cli/release/1.4.0-rc.1"),
+            Some(version)
+        );
+    }
+
+    #[test]
+    fn test_latest_package_tags_orders_the_tags_of_each_prefix() {
+        let git = init_repository("packages-latest-tags");
+        commit(&git, "a.txt", "feat: first.");
+        for tag in [
+            "api-v1.9.0",
+            "api-v1.10.0",
+            "api-v2.0.0-rc.1",
+            "tools/cli/v0.2.0",
+            "cli-v9.0.0",
+            "v3.0.0",
+        ] {
+            git.run(["tag", tag]).unwrap();
+        }
+
+        let config = Config::from_toml_str(
+            "[packages]\napi = [\"api\"]\ncli = { paths = [\"cli\"], tag_prefix = \"tools/cli/\" }\nweb = [\"web\"]\n",
+        )
+        .unwrap();
+        let latest = config
+            .latest_package_tags(&git)
+            .unwrap()
+            .into_iter()
+            .map(|(package, (tag, _))| (package, tag.name))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            latest,
+            vec![
+                ("api".to_string(), "api-v1.10.0".to_string()),
+                ("cli".to_string(), "tools/cli/v0.2.0".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_dependents_cascade_closest_first_once_each() {
+        let config = Config::from_toml_str(
+            r#"
+[packages]
+core = ["core"]
+web = { paths = ["web"], depends_on = ["api", "core"] }
+api = { paths = ["api"], depends_on = ["core"] }
+cli = { paths = ["cli"], depends_on = ["web"] }
+docs = ["docs"]
+"#,
+        )
+        .unwrap();
+        let dependents = |name: &str| {
+            config
+                .dependents(name)
+                .unwrap()
+                .into_iter()
+                .map(|(dependent, dependency)| format!("{} <- {}", dependent, dependency))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dependents("core"),
+            vec!["api <- core", "web <- core", "cli <- web"]
+        );
+        assert_eq!(dependents("api"), vec!["web <- api", "cli <- web"]);
+        assert!(dependents("docs").is_empty());
+        assert_eq!(
+            config.dependents("mobile").unwrap_err(),
+            SemVerError::UnknownPackage("mobile".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dependents_terminate_on_cycles_and_reject_unknown_dependencies() {
+        let cycle = Config::from_toml_str(
+            "[packages]\napi = { paths = [\"api\"], depends_on = [\"web\"] }\nweb = { paths = [\"web\"], depends_on = [\"api\"] }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            cycle.dependents("api").unwrap(),
+            vec![("web".to_string(), "api".to_string())]
+        );
+
+        let unknown = Config::from_toml_str(
+            "[packages]\napi = { paths = [\"api\"], depends_on = [\"core\"] }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            unknown.dependents("api").unwrap_err(),
+            SemVerError::UnknownPackage("core".to_string())
+        );
+    }
+}
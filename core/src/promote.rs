@@ -0,0 +1,130 @@
+use serde::Serialize;
+
+use crate::{Commit, Config, Git, SemVerError, SemanticVersion, Vcs};
+
+/// [`Promotion`] describes the promotion of a prerelease to the version it announces,
+/// e.g. `v2.0.0-rc.3` to `v2.0.0`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Promotion {
+    pub prerelease_tag: String,
+    pub version: SemanticVersion,
+    pub tag: String,
+    /// Commits landed since the prerelease was cut, released along with it.
+    pub commits: Vec<Commit>,
+}
+
+impl Promotion {
+    /// [`as_json_string`] returns json representation of the promotion.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+/// [`plan_promotion`] plans the promotion of the prerelease tag `from`, the greatest one
+/// reachable from HEAD by default.
+///
+/// Fails when a breaking change landed since the prerelease was cut, since the promoted
+/// version would not announce it.
+pub fn plan_promotion(
+    vcs: &dyn Vcs,
+    config: &Config,
+    from: Option<&str>,
+) -> Result<Promotion, SemVerError> {
+    let format = config.tag_format()?;
+    let (prerelease_tag, prerelease) = match from {
+        Some(tag) => {
+            let version = format
+                .parse(tag)
+                .filter(SemanticVersion::is_prerelease)
+                .ok_or_else(|| SemVerError::InvalidVersionFormat(tag.to_string()))?;
+            (tag.to_string(), version)
+        }
+        None => vcs
+            .tags()?
+            .into_iter()
+            .filter_map(|tag| {
+                let version = format.parse(&tag.name).filter(|v| v.is_prerelease())?;
+                Some((tag.name, version))
+            })
+            .max_by(|(_, l), (_, r)| l.cmp(r))
+            .ok_or(SemVerError::NoPrereleaseTag)?,
+    };
+    let version = prerelease.release();
+    let tag = format.render(&version);
+    if vcs.tag_names()?.contains(&tag) {
+        return Err(SemVerError::TagAlreadyExists(tag));
+    }
+
+    let commits = vcs.commits(Some(&format!("{}..HEAD", prerelease_tag)))?;
+    let (parsed, _) = config.parse_commits(&commits);
+    if let Some(breaking) = parsed
+        .iter()
+        .find(|parsed| parsed.comment.semantic_type.metadata().is_breaking)
+    {
+        let sha = &breaking.commit.sha;
+        return Err(SemVerError::BreakingSincePrerelease(
+            prerelease_tag,
+            sha.get(..7).unwrap_or(sha).to_string(),
+        ));
+    }
+
+    Ok(Promotion {
+        prerelease_tag,
+        version,
+        tag,
+        commits,
+    })
+}
+
+/// [`execute_promotion`] tags HEAD with the promoted version, signed with the
+/// [`Git::signing_key`] when `sign` is set, and pushes the tag to `remote`.
+pub fn execute_promotion(
+    git: &Git,
+    promotion: &Promotion,
+    remote: Option<&str>,
+    sign: bool,
+) -> Result<(), SemVerError> {
+    match sign {
+        true => {
+            git.signing_key()?;
+            git.create_signed_tag(&promotion.tag, &promotion.tag)?;
+        }
+        false => git.create_tag(&promotion.tag, &promotion.tag)?,
+    }
+    if let Some(remote) = remote {
+        git.push(remote, &[&promotion.tag])?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::git::test::{commit, init_repository};
+
+    use super::*;
+
+    #[test]
+    fn test_plan_promotion_refuses_breaking_changes_since_the_prerelease() {
+        let git = init_repository("promote");
+        commit(&git, "a.txt", "feat!: breaking.");
+        git.run(["tag", "v2.0.0-rc.1"]).unwrap();
+        commit(&git, "a.txt", "fix: a fix.");
+        git.run(["tag", "v2.0.0-rc.2"]).unwrap();
+        commit(&git, "a.txt", "fix: another fix.");
+
+        let promotion = plan_promotion(&git, &Config::default(), None).unwrap();
+        assert_eq!(promotion.prerelease_tag, "v2.0.0-rc.2");
+        assert_eq!(promotion.tag, "v2.0.0");
+        assert_eq!(promotion.commits.len(), 1);
+
+        commit(&git, "a.txt", "refactor!: drop the old api.");
+        assert_eq!(
+            plan_promotion(&git, &Config::default(), Some("v2.0.0-rc.1")).unwrap_err(),
+            SemVerError::BreakingSincePrerelease(
+                "v2.0.0-rc.1".to_string(),
+                git.run(["rev-parse", "--short=7", "HEAD"]).unwrap()
+            )
+        );
+    }
+}
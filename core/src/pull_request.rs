@@ -0,0 +1,109 @@
+use serde::Serialize;
+
+use crate::{BumpLevel, Config, Reference, ReferenceKind, SemVerError, SemanticComment};
+
+/// [`PullRequestTitle`] is a parsed pull request title with the bump its squash merge causes,
+/// see [`Config::analyze_pr_title`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PullRequestTitle {
+    /// Number of the `(#123)` suffix GitHub appends to squash merge subjects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<u64>,
+    pub comment: SemanticComment,
+    pub bump: BumpLevel,
+}
+
+impl PullRequestTitle {
+    /// [`as_json_string`] returns json representation of the title.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+/// [`split_pr_number`] splits the `(#123)` suffix GitHub appends to the titles of squash merged
+/// pull requests from `title`.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// assert_eq!(split_pr_number("feat: export. (#123)"), ("feat: export.", Some(123)));
+/// assert_eq!(split_pr_number("fix: escape (#1)(#2) "), ("fix: escape (#1)", Some(2)));
+/// assert_eq!(split_pr_number("fix: escape (#1a)"), ("fix: escape (#1a)", None));
+/// ```
+pub fn split_pr_number(title: &str) -> (&str, Option<u64>) {
+    let split = title
+        .trim_end()
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once("(#"))
+        .filter(|(_, number)| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()));
+
+    match split.and_then(|(subject, number)| Some((subject.trim_end(), number.parse().ok()?))) {
+        Some((subject, number)) => (subject, Some(number)),
+        None => (title, None),
+    }
+}
+
+impl Config {
+    /// [`analyze_pr_title`] parses the title of a pull request, without the `(#123)` suffix of
+    /// squash merges, and evaluates the bump it causes on `branch`, so that squash merge
+    /// workflows check the version a pull request releases before merging it.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let title = Config::default().analyze_pr_title("feat(api): export (#42)", None).unwrap();
+    ///
+    /// assert_eq!(title.number, Some(42));
+    /// assert_eq!(title.comment.comment, "export");
+    /// assert_eq!(title.bump, BumpLevel::Minor);
+    /// ```
+    pub fn analyze_pr_title(
+        &self,
+        title: &str,
+        branch: Option<&str>,
+    ) -> Result<PullRequestTitle, SemVerError> {
+        let (subject, number) = split_pr_number(title);
+        let mut comment = self.comment_parser().parse(subject)?;
+        if let Some(number) = number {
+            let reference = Reference {
+                kind: ReferenceKind::Issue,
+                number,
+                closes: false,
+            };
+            if !comment.references.contains(&reference) {
+                comment.references.push(reference);
+            }
+        }
+        let bump = self.bump_policy()?.evaluate(&comment, branch);
+
+        Ok(PullRequestTitle {
+            number,
+            comment,
+            bump,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_analyze_pr_title_follows_the_policy_and_keeps_the_number() {
+        let config =
+            Config::from_toml_str("[[policy.rules]]\nwhen = \"scope == api\"\nbump = \"major\"\n")
+                .unwrap();
+
+        let title = config
+            .analyze_pr_title("fix(api): escape ids. (#7)", Some("main"))
+            .unwrap();
+        assert_eq!(title.number, Some(7));
+        assert_eq!(title.comment.comment, "escape ids.");
+        assert_eq!(title.comment.references.len(), 1);
+        assert_eq!(title.bump, BumpLevel::Major);
+
+        let title = config.analyze_pr_title("fix: escape ids.", None).unwrap();
+        assert_eq!((title.number, title.bump), (None, BumpLevel::Patch));
+        assert!(config.analyze_pr_title("Update README (#8)", None).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use tracing::{debug, info};
+
+use crate::{
+    cancel_reverts, civil_from_days, insert_release,
+    release::{release_contributors, render_changelog_section},
+    Config, ParsedCommit, SemVerError, SemanticVersion, Tag, Vcs, CHANGELOG_HEADER,
+};
+
+/// [`regenerate_changelog`] renders the whole changelog of the repository from scratch, one
+/// section per version tag reachable from HEAD, newest first, e.g. to adopt `semver release`
+/// in a repository with years of history.
+///
+/// The tags follow `tag_format`, prereleases left out so that their commits are listed in the
+/// release they led to. Each section lists the commits since the previous tag, dated with its
+/// tagged commit and rendered like `semver release` renders it. Commits after the latest tag
+/// are left to the next release.
+pub fn regenerate_changelog(vcs: &dyn Vcs, config: &Config) -> Result<String, SemVerError> {
+    let format = config.tag_format()?;
+    let mut tags = vcs
+        .tags()?
+        .into_iter()
+        .filter_map(|tag| {
+            let version = format.parse(&tag.name).filter(|v| !v.is_prerelease())?;
+            Some((tag, version))
+        })
+        .collect::<Vec<_>>();
+    tags.sort_by(|(_, l), (_, r)| l.cmp(r));
+    info!(releases = tags.len(), "regenerating the changelog");
+
+    let mut changelog = format!("{}\n", CHANGELOG_HEADER);
+    let mut previous: Option<&Tag> = None;
+    for (tag, version) in &tags {
+        let section = render_tagged_release(vcs, config, previous, tag, version)?;
+        changelog = insert_release(&changelog, &section);
+        previous = Some(tag);
+    }
+
+    Ok(changelog)
+}
+
+/// [`render_unreleased`] renders the `Unreleased` section of the commits since the latest
+/// version tag, without bumping anything, see [`crate::insert_release`]. Its title links to the
+/// comparison of the latest tag with `HEAD` when the repository is known.
+pub fn render_unreleased(vcs: &dyn Vcs, config: &Config) -> Result<String, SemVerError> {
+    let latest = vcs.latest_version_tag_with_format(&config.tag_format()?)?;
+    let range = latest
+        .as_ref()
+        .map(|(tag, _)| format!("{}..HEAD", tag.name));
+    let commits = released_commits(vcs, config, range.as_deref())?;
+    let tags = latest.as_ref().map(|(tag, _)| (tag.name.as_str(), "HEAD"));
+    let contributors = release_contributors(vcs, config, &commits);
+
+    render_changelog_section(vcs, config, None, &commits, tags, &contributors)
+}
+
+/// Returns the parsed commits of `range`, skipped and reverted ones left out.
+fn released_commits(
+    vcs: &dyn Vcs,
+    config: &Config,
+    range: Option<&str>,
+) -> Result<Vec<ParsedCommit>, SemVerError> {
+    let commits = vcs
+        .commits(range)?
+        .into_iter()
+        .filter(|commit| !config.is_skipped(commit))
+        .collect::<Vec<_>>();
+    let (commits, unparsed) = config.parse_commits(&commits);
+    debug!(
+        range = range.unwrap_or("HEAD"),
+        commits = commits.len(),
+        unparsed = unparsed.len(),
+        "parsed commits"
+    );
+
+    Ok(cancel_reverts(commits))
+}
+
+fn render_tagged_release(
+    vcs: &dyn Vcs,
+    config: &Config,
+    previous: Option<&Tag>,
+    tag: &Tag,
+    version: &SemanticVersion,
+) -> Result<String, SemVerError> {
+    let range = match previous {
+        Some(previous) => format!("{}..{}", previous.name, tag.name),
+        None => tag.name.clone(),
+    };
+    let commits = released_commits(vcs, config, Some(&range))?;
+
+    let timestamp = vcs.commit(&tag.sha)?.timestamp.unwrap_or_default();
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(86_400));
+    let date = format!("{:04}-{:02}-{:02}", year, month, day);
+    let tags = previous.map(|previous| (previous.name.as_str(), tag.name.as_str()));
+    let contributors = release_contributors(vcs, config, &commits);
+
+    render_changelog_section(
+        vcs,
+        config,
+        Some((version, &date)),
+        &commits,
+        tags,
+        &contributors,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::git::test::{commit, init_repository};
+
+    use super::*;
+
+    #[test]
+    fn test_regenerate_changelog_renders_every_tagged_release_newest_first() {
+        let git = init_repository("regenerate");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "a.txt", "fix: a fix.");
+        git.run(["tag", "v0.1.1-rc.1"]).unwrap();
+        commit(&git, "a.txt", "feat(api): a feature.");
+        git.run(["tag", "v0.2.0"]).unwrap();
+        commit(&git, "a.txt", "fix: not released yet.");
+
+        let changelog = regenerate_changelog(&git, &Config::default()).unwrap();
+        let sections = changelog.split("\n## ").collect::<Vec<_>>();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].trim(), CHANGELOG_HEADER);
+        assert!(sections[1].starts_with("v0.2.0 ("));
+        assert!(sections[1].contains("- a fix. ("));
+        assert!(sections[1].contains("- **api:** a feature. ("));
+        assert!(sections[2].starts_with("v0.1.0 ("));
+        assert!(sections[2].contains("- first. ("));
+        assert!(!changelog.contains("not released yet"));
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_unreleased_section_is_replaced_by_the_release() {
+        let git = init_repository("unreleased");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "a.txt", "fix: a fix.");
+
+        let changelog = regenerate_changelog(&git, &Config::default()).unwrap();
+        let unreleased = render_unreleased(&git, &Config::default()).unwrap();
+        assert!(unreleased.starts_with("## Unreleased\n\n### Bug Fixes\n\n- a fix. ("));
+
+        let changelog = insert_release(&changelog, &unreleased);
+        let changelog = insert_release(&changelog, &unreleased);
+        assert_eq!(changelog.matches("## Unreleased").count(), 1);
+
+        let changelog = insert_release(&changelog, "## v0.1.1 (2024-06-01)\n");
+        assert!(!changelog.contains("Unreleased"));
+        assert!(changelog.contains("## v0.1.1 (2024-06-01)\n\n## v0.1.0 ("));
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+}
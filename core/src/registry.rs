@@ -0,0 +1,170 @@
+use std::{fmt, str::FromStr};
+
+use serde_json::Value;
+use tracing::debug;
+
+use crate::{SemVerError, SemanticVersion};
+
+/// Url of the crates.io API.
+const CRATES_IO_API_URL: &str = "https://crates.io/api/v1";
+/// Url of the npm registry.
+const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+/// Dist-tag of the versions npm installs by default.
+const NPM_LATEST_DIST_TAG: &str = "latest";
+
+/// [`Registry`] names the package registries the latest published version can be read from,
+/// see [`crate::ReleaseOptions::registry`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Registry {
+    CratesIo,
+    Npm,
+}
+
+impl FromStr for Registry {
+    type Err = SemVerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "crates-io" | "crates.io" => Ok(Self::CratesIo),
+            "npm" => Ok(Self::Npm),
+            _ => Err(SemVerError::RegistryError(format!(
+                "unknown registry `{}`",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CratesIo => write!(f, "crates-io"),
+            Self::Npm => write!(f, "npm"),
+        }
+    }
+}
+
+impl Registry {
+    /// [`latest_version`] returns the latest version of the package `name` published to the
+    /// registry, `None` when it was never published: the greatest stable one on crates.io,
+    /// yanked ones left out, and the version of the `latest` dist-tag on npm.
+    ///
+    /// npm names may pick another dist-tag the way npm does, e.g. `@team/web@next`.
+    pub fn latest_version(&self, name: &str) -> Result<Option<SemanticVersion>, SemVerError> {
+        let (name, dist_tag) = match self {
+            Self::CratesIo => (name, None),
+            Self::Npm => split_dist_tag(name),
+        };
+        let url = match self {
+            Self::CratesIo => format!("{}/crates/{}", CRATES_IO_API_URL, name),
+            // the slash of scoped packages is escaped, e.g. `@team%2fweb`
+            Self::Npm => format!(
+                "{}/-/package/{}/dist-tags",
+                NPM_REGISTRY_URL,
+                name.replace('/', "%2f")
+            ),
+        };
+        // crates.io rejects the requests without a user agent
+        let response = ureq::get(&url)
+            .set("User-Agent", concat!("semver/", env!("CARGO_PKG_VERSION")))
+            .call();
+
+        let body: Value = match response {
+            Ok(response) => response
+                .into_json()
+                .map_err(|e| SemVerError::RegistryError(e.to_string()))?,
+            Err(ureq::Error::Status(404, _)) => {
+                debug!(registry = %self, %name, "never published");
+                return Ok(None);
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                return Err(SemVerError::RegistryError(format!(
+                    "{} answered {}: {}",
+                    self,
+                    status,
+                    response.into_string().unwrap_or_default()
+                )))
+            }
+            Err(error) => return Err(SemVerError::RegistryError(error.to_string())),
+        };
+
+        let version = match self {
+            Self::CratesIo => parse_crate_version(&body),
+            Self::Npm => parse_dist_tag(&body, dist_tag.unwrap_or(NPM_LATEST_DIST_TAG)),
+        };
+        debug!(
+            registry = %self,
+            %name,
+            version = ?version.clone().map(String::from),
+            "published version"
+        );
+        Ok(version)
+    }
+}
+
+/// Reads the greatest stable version of a crate from its crates.io API response.
+fn parse_crate_version(body: &Value) -> Option<SemanticVersion> {
+    let version = body.pointer("/crate/max_stable_version")?.as_str()?;
+
+    SemanticVersion::try_from(format!("v{}", version).as_str()).ok()
+}
+
+/// Splits the dist-tag off an npm package spec, e.g. `next` off `@team/web@next`.
+fn split_dist_tag(spec: &str) -> (&str, Option<&str>) {
+    match spec.rfind('@').filter(|at| *at > 0) {
+        Some(at) => (&spec[..at], Some(&spec[at + 1..])),
+        None => (spec, None),
+    }
+}
+
+/// Reads the version of `dist_tag` from the dist-tags of an npm package, e.g. `{"latest": "1.2.0"}`.
+fn parse_dist_tag(body: &Value, dist_tag: &str) -> Option<SemanticVersion> {
+    let version = body.get(dist_tag)?.as_str()?;
+
+    SemanticVersion::try_from(format!("v{}", version).as_str()).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_crate_version_reads_the_greatest_stable_version() {
+        let body = json!({
+            "crate": { "name": "foo", "max_version": "2.0.0-rc.1", "max_stable_version": "1.4.2" }
+        });
+        assert_eq!(
+            parse_crate_version(&body),
+            Some(SemanticVersion::new(1, 4, 2))
+        );
+
+        let body =
+            json!({ "crate": { "max_version": "0.1.0-alpha.1", "max_stable_version": null } });
+        assert_eq!(parse_crate_version(&body), None);
+        assert_eq!("crates.io".parse::<Registry>().unwrap(), Registry::CratesIo);
+    }
+
+    #[test]
+    fn test_npm_reads_the_version_of_a_dist_tag() {
+        assert_eq!(
+            split_dist_tag("@team/web@next"),
+            ("@team/web", Some("next"))
+        );
+        assert_eq!(split_dist_tag("@team/web"), ("@team/web", None));
+        assert_eq!(split_dist_tag("web"), ("web", None));
+
+        let body = json!({ "latest": "1.4.2", "next": "2.0.0-rc.1" });
+        assert_eq!(
+            parse_dist_tag(&body, "latest"),
+            Some(SemanticVersion::new(1, 4, 2))
+        );
+        assert_eq!(
+            parse_dist_tag(&body, "next").map(String::from).as_deref(),
+            Some("v2.0.0-rc.1")
+        );
+        assert_eq!(parse_dist_tag(&body, "beta"), None);
+    }
+}
@@ -0,0 +1,1815 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::{
+    cancel_reverts,
+    changelog::{render_grouped_section, render_heading, render_section},
+    civil_from_days, collect_contributors, group_by_scope, insert_release, plan_updates,
+    render_contributor_list, render_release_with_links, today,
+    updaters::PlanOutput,
+    BumpDecision, BumpLevel, ChangelogGroup, ChangelogLayout, Commit, Config, Contributor,
+    FileChange, Forge, ForgeKind, ForgeRelease, Git, Package, ParseCache, ParsedCommit,
+    SemVerError, SemanticType, SemanticVersion, Tag, TagFormat, Vcs, VersionComponents, Versioning,
+    CACHE_FILE_NAME, OTHER_GROUP, UNRELEASED_TITLE,
+};
+
+/// Template of the snapshot versions, see [`ReleasePlan::snapshot_tag`].
+pub const DEFAULT_SNAPSHOT_TEMPLATE: &str = "{version}-nightly.{date}+sha.{sha}";
+
+/// Template of the release commit messages, see [`Config::release_commit_message`].
+pub const DEFAULT_RELEASE_COMMIT_TEMPLATE: &str = "chore(release): v{version} [skip ci]";
+
+/// [`ReleaseOptions`] holds the options of `semver release`.
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseOptions {
+    /// Branch the policy is evaluated for, defaults to the checked out branch.
+    pub branch: Option<String>,
+    /// Remote the release is pushed to, nothing is pushed when `None`.
+    pub push: Option<String>,
+    /// Package of the `[packages]` section to release instead of the whole repository.
+    pub package: Option<String>,
+    /// Forge the release is published to, see [`publish_release`].
+    pub forge: Option<ForgeKind>,
+    /// Accepts a version equal to the greatest existing one and reuses its tag, e.g. to re-run
+    /// a release whose push failed.
+    pub allow_same_version: bool,
+    /// Version of the first release when nothing is tagged yet, see [`Config::initial_version`].
+    pub initial_version: Option<SemanticVersion>,
+    /// Reuses the commits parsed by previous runs, and stores the new ones, in the
+    /// [`CACHE_FILE_NAME`] of the repository, see [`ParseCache`].
+    pub cache: bool,
+    /// Registry and name of the package whose latest published version is the baseline of the
+    /// release instead of the latest tag, catching tags and publications that drifted apart.
+    #[cfg(feature = "forge")]
+    pub registry: Option<(crate::Registry, String)>,
+    /// Signs the release commit and tags with the [`Git::signing_key`] of the repository.
+    pub sign: bool,
+}
+
+/// [`ReleasePlan`] describes everything `semver release` is about to do.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReleasePlan {
+    pub previous_tag: String,
+    pub previous_version: SemanticVersion,
+    pub version: SemanticVersion,
+    pub bump: BumpLevel,
+    pub commits: Vec<ParsedCommit>,
+    pub unparsed: Vec<Commit>,
+    /// Commits excluded by a skip marker, e.g. `[skip release]`.
+    pub skipped: Vec<Commit>,
+    pub changes: Vec<FileChange>,
+    pub commit_message: String,
+    pub tag: String,
+    pub push: Option<String>,
+    #[serde(serialize_with = "serialize_display")]
+    pub forge: Option<ForgeKind>,
+    /// Releases of the packages depending, directly or not, on the released package.
+    pub cascade: Vec<CascadeRelease>,
+    /// Set when a released commit is a security fix, e.g. to trigger an advisory.
+    pub security: bool,
+    /// Set when no version was tagged yet: the whole history is released with the initial version.
+    pub first_release: bool,
+    /// Prerelease of the tag on a channel branch, e.g. `rc.1`, see [`Config::channel`].
+    pub prerelease: Option<String>,
+    /// Set when HEAD is already tagged, e.g. on a pipeline retry: nothing is released and `tag`
+    /// is the existing one.
+    pub already_released: bool,
+    /// Authors and co-authors of the commits, when `changelog_contributors` is set.
+    pub contributors: Vec<Contributor>,
+    /// Set when the release commit and tags are signed, see [`ReleaseOptions::sign`].
+    pub sign: bool,
+    /// Commits and rules deciding `bump`, see [`crate::BumpPolicy::decide`].
+    pub decision: BumpDecision,
+}
+
+/// [`CascadeRelease`] describes the release of a package caused by the release of a dependency.
+///
+/// The package receives at least a patch bump, more when its own commits require it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CascadeRelease {
+    pub package: String,
+    pub dependency: String,
+    pub previous_tag: String,
+    pub version: SemanticVersion,
+    pub bump: BumpLevel,
+    pub tag: String,
+}
+
+impl ReleasePlan {
+    /// Returns `true` when no commit requires a release.
+    pub fn is_empty(&self) -> bool {
+        self.bump == BumpLevel::None
+    }
+
+    /// Describes every step of the release, in order.
+    pub fn steps(&self) -> Vec<String> {
+        if self.already_released {
+            return vec![format!(
+                "detect last tag: {}, already released at HEAD",
+                self.tag
+            )];
+        }
+        let mut steps = vec![
+            match self.first_release {
+                true => "detect last tag: none, first release".to_string(),
+                false => format!("detect last tag: {}", self.previous_tag),
+            },
+            format!(
+                "parse {} commits ({} unparseable, {} skipped)",
+                self.commits.len() + self.unparsed.len() + self.skipped.len(),
+                self.unparsed.len(),
+                self.skipped.len()
+            ),
+            format!(
+                "compute next version: {} -> {} ({})",
+                String::from(self.previous_version.clone()),
+                String::from(self.version.clone()),
+                self.bump
+            ),
+        ];
+        if self.is_empty() {
+            return steps;
+        }
+
+        steps.extend(
+            self.changes
+                .iter()
+                .map(|change| format!("update {}", change.path.display())),
+        );
+        let signed = match self.sign {
+            true => "signed ",
+            false => "",
+        };
+        if !self.changes.is_empty() {
+            steps.push(format!(
+                "create {}release commit: {}",
+                signed, self.commit_message
+            ));
+        }
+        steps.push(format!("create {}tag: {}", signed, self.tag));
+        for release in &self.cascade {
+            steps.push(format!(
+                "create {}tag: {} ({} bump, depends on {})",
+                signed, release.tag, release.bump, release.dependency
+            ));
+        }
+        if let Some(remote) = &self.push {
+            steps.push(format!(
+                "push HEAD and {} to {}",
+                self.tags().join(", "),
+                remote
+            ));
+        }
+        if let Some(forge) = &self.forge {
+            steps.push(format!("publish {} to {}", self.tags().join(", "), forge));
+        }
+
+        steps
+    }
+
+    /// [`snapshot_tag`] returns the tag of a build between releases, built at `seconds` since
+    /// the epoch from the commit `sha`, rendering the placeholders of `template`: `{version}`,
+    /// `{date}` (`YYYYMMDD`, UTC), `{time}` (`HHMMSS`, UTC) and `{sha}`.
+    ///
+    /// `{version}` is the tag of the next release in `format`, without prerelease and with as
+    /// many components as [`ReleasePlan::tag`], the next patch when nothing requires a release,
+    /// so that snapshots sort after the latest release.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let plan = ReleasePlan {
+    ///     version: SemanticVersion::new(1, 3, 0),
+    ///     bump: BumpLevel::Minor,
+    ///     tag: "v1.3.0".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     plan.snapshot_tag(&TagFormat::default(), DEFAULT_SNAPSHOT_TEMPLATE, 1_717_243_200, "abc1234").unwrap(),
+    ///     "v1.3.0-nightly.20240601+sha.abc1234"
+    /// );
+    /// ```
+    pub fn snapshot_tag(
+        &self,
+        format: &TagFormat,
+        template: &str,
+        seconds: u64,
+        sha: &str,
+    ) -> Result<String, SemVerError> {
+        let mut version = SemanticVersion {
+            pre: None,
+            build: None,
+            ..self.version.clone()
+        };
+        if self.is_empty() {
+            version.apply_bump(BumpLevel::Patch)?;
+        }
+        let components = format.components(&self.tag).unwrap_or(3);
+        let tag = format.render_components(&version, components);
+
+        let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+        let time = seconds % 86_400;
+        Ok(template
+            .replace("{version}", &tag)
+            .replace("{date}", &format!("{:04}{:02}{:02}", year, month, day))
+            .replace(
+                "{time}",
+                &format!("{:02}{:02}{:02}", time / 3600, time / 60 % 60, time % 60),
+            )
+            .replace("{sha}", sha))
+    }
+
+    /// Returns the tags created by the release, cascaded releases included.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags = vec![self.tag.as_str()];
+        tags.extend(self.cascade.iter().map(|release| release.tag.as_str()));
+        tags
+    }
+
+    /// [`as_json_string`] returns json representation of the plan, including its steps.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&PlanOutput {
+            steps: self.steps(),
+            plan: self,
+        })?)
+    }
+}
+
+/// [`plan_release`] computes the next release of the repository without touching it.
+///
+/// When releasing a package, only the commits scoped to it or touching its paths are considered
+/// and the version files and changelog, which belong to the whole repository, are left untouched.
+/// The packages depending on it are released too, see [`CascadeRelease`].
+pub fn plan_release(
+    vcs: &dyn Vcs,
+    config: &Config,
+    options: &ReleaseOptions,
+) -> Result<ReleasePlan, SemVerError> {
+    if options.package.is_some() && config.versioning == Versioning::Lockstep {
+        return Err(SemVerError::InvalidConfig(
+            "packages cannot be released on their own with lockstep versioning".to_string(),
+        ));
+    }
+
+    let mut plan = plan_single_release(vcs, config, options)?;
+    if !plan.is_empty() {
+        let package = options
+            .package
+            .as_deref()
+            .map(|name| config.package(name))
+            .transpose()?;
+        ensure_monotonic(
+            vcs,
+            &tag_format(config, package.as_ref())?,
+            &plan.tag,
+            options,
+        )?;
+    }
+    let Some(package) = options.package.as_deref().filter(|_| !plan.is_empty()) else {
+        return Ok(plan);
+    };
+
+    let branch = match &options.branch {
+        Some(branch) => Some(branch.clone()),
+        None => vcs.current_branch()?,
+    };
+    for (dependent, dependency) in config.dependents(package)? {
+        let options = ReleaseOptions {
+            branch: options.branch.clone(),
+            push: None,
+            package: Some(dependent.clone()),
+            forge: None,
+            allow_same_version: options.allow_same_version,
+            initial_version: options.initial_version.clone(),
+            cache: options.cache,
+            #[cfg(feature = "forge")]
+            registry: None,
+            sign: options.sign,
+        };
+        let dependent_plan = plan_single_release(vcs, config, &options)?;
+        let bump = dependent_plan.bump.max(BumpLevel::Patch);
+        let version = match dependent_plan.first_release {
+            true => dependent_plan.version,
+            false => config.next_version(&dependent_plan.previous_version, bump)?,
+        };
+
+        let format = tag_format(config, Some(&config.package(&dependent)?))?;
+        let components = tag_components(config, &format, &dependent_plan.previous_tag);
+        let channel = config.channel(branch.as_deref());
+        let (tag, _) = channel_tag(vcs, &format, channel, &version, components)?;
+
+        plan.cascade.push(CascadeRelease {
+            tag,
+            package: dependent,
+            dependency,
+            previous_tag: dependent_plan.previous_tag,
+            version,
+            bump,
+        });
+    }
+    for release in &plan.cascade {
+        let format = tag_format(config, Some(&config.package(&release.package)?))?;
+        ensure_monotonic(vcs, &format, &release.tag, options)?;
+    }
+
+    Ok(plan)
+}
+
+/// Returns the format of the tags of `package`, or of the repository.
+fn tag_format(config: &Config, package: Option<&Package>) -> Result<TagFormat, SemVerError> {
+    let format = config.tag_format()?;
+
+    Ok(match package {
+        Some(package) => package.tag_format(&format)?,
+        None => format,
+    })
+}
+
+/// Checks that `tag` does not exist yet and that its version is greater than every version
+/// tagged in `format`, unless [`ReleaseOptions::allow_same_version`] accepts an equal one.
+fn ensure_monotonic(
+    vcs: &dyn Vcs,
+    format: &TagFormat,
+    tag: &str,
+    options: &ReleaseOptions,
+) -> Result<(), SemVerError> {
+    let tags = vcs.tag_names()?;
+    if tags.iter().any(|name| name == tag) && !options.allow_same_version {
+        return Err(SemVerError::TagAlreadyExists(tag.to_string()));
+    }
+
+    let version = format
+        .parse(tag)
+        .ok_or_else(|| SemVerError::InvalidVersionFormat(tag.to_string()))?;
+    let greatest = tags
+        .iter()
+        .filter_map(|name| Some((name, format.parse(name)?)))
+        .max_by(|(_, l), (_, r)| l.cmp(r));
+
+    match greatest {
+        Some((name, greatest)) if greatest > version => Err(SemVerError::NonMonotonicVersion(
+            tag.to_string(),
+            name.to_string(),
+        )),
+        Some((name, greatest)) if greatest == version && !options.allow_same_version => Err(
+            SemVerError::NonMonotonicVersion(tag.to_string(), name.to_string()),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the contributors of `commits` when `changelog_contributors` is set, with their
+/// GitHub handles when `contributor_handles` is.
+pub(crate) fn release_contributors(
+    vcs: &dyn Vcs,
+    config: &Config,
+    commits: &[ParsedCommit],
+) -> Vec<Contributor> {
+    if !config.changelog_contributors {
+        return vec![];
+    }
+    let contributors = collect_contributors(commits);
+
+    match config.contributor_handles {
+        true => resolve_handles(vcs, contributors),
+        false => contributors,
+    }
+}
+
+/// Sets the GitHub handles of the `contributors` through the API.
+#[cfg(feature = "forge")]
+fn resolve_handles(vcs: &dyn Vcs, mut contributors: Vec<Contributor>) -> Vec<Contributor> {
+    match crate::GitHubUsers::from_env(vcs) {
+        Ok(users) => users.resolve(&mut contributors),
+        Err(error) => warn!(%error, "contributor handles not resolved"),
+    }
+
+    contributors
+}
+
+/// Leaves the `contributors` unchanged: the API is only called with the `forge` feature.
+#[cfg(not(feature = "forge"))]
+fn resolve_handles(_: &dyn Vcs, contributors: Vec<Contributor>) -> Vec<Contributor> {
+    warn!("contributor handles not resolved without the `forge` feature");
+    contributors
+}
+
+/// Renders the changelog section of the `(version, date)` release, the `Unreleased` one when
+/// `None`, following the layout and the links of `config`, `tags` being the `(previous, new)`
+/// tags the version is compared with.
+pub(crate) fn render_changelog_section(
+    vcs: &dyn Vcs,
+    config: &Config,
+    release: Option<(&SemanticVersion, &str)>,
+    commits: &[ParsedCommit],
+    tags: Option<(&str, &str)>,
+    contributors: &[Contributor],
+) -> Result<String, SemVerError> {
+    let links = config.repository_links(vcs);
+    let heading = match release {
+        Some((version, date)) => render_heading(
+            &String::from(version.clone()),
+            Some(date),
+            links.as_ref(),
+            tags,
+        ),
+        None => render_heading(UNRELEASED_TITLE, None, links.as_ref(), tags),
+    };
+    let mut section = match changelog_groups(vcs, config, commits)? {
+        Some(groups) => render_grouped_section(heading, &groups, links.as_ref()),
+        None => render_section(heading, commits, links.as_ref()),
+    };
+    section.push_str(&render_contributor_list(contributors));
+
+    Ok(section)
+}
+
+/// Groups the commits of the changelog following `changelog_layout`, `None` for the default
+/// layout by type. Commits are listed under every package they belong to.
+fn changelog_groups(
+    vcs: &dyn Vcs,
+    config: &Config,
+    commits: &[ParsedCommit],
+) -> Result<Option<Vec<ChangelogGroup>>, SemVerError> {
+    match config.changelog_layout {
+        ChangelogLayout::Type => Ok(None),
+        ChangelogLayout::Scope => Ok(Some(group_by_scope(commits))),
+        ChangelogLayout::Package => {
+            let packages = config
+                .packages
+                .keys()
+                .map(|name| config.package(name))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut groups = packages
+                .iter()
+                .map(|package| (package.name.clone(), vec![]))
+                .collect::<Vec<_>>();
+            let mut other = vec![];
+            for parsed in commits {
+                let paths = vcs.changed_paths(&parsed.commit.sha)?;
+                let mut included = false;
+                for (package, (_, group)) in packages.iter().zip(groups.iter_mut()) {
+                    if package.includes(parsed, &paths) {
+                        group.push(parsed.clone());
+                        included = true;
+                    }
+                }
+                if !included {
+                    other.push(parsed.clone());
+                }
+            }
+            groups.retain(|(_, group)| !group.is_empty());
+            if !other.is_empty() {
+                groups.push((OTHER_GROUP.to_string(), other));
+            }
+
+            Ok(Some(groups))
+        }
+    }
+}
+
+/// Returns the baseline of a release from the `published` version: its tag when reachable,
+/// otherwise the latest `tagged` version, whose commits since are released on top of
+/// `published`. An untagged baseline has no sha.
+#[cfg(feature = "forge")]
+fn published_baseline(
+    vcs: &dyn Vcs,
+    format: &TagFormat,
+    tagged: Option<(Tag, SemanticVersion)>,
+    published: SemanticVersion,
+) -> Result<(Tag, SemanticVersion), SemVerError> {
+    let name = format.render(&published);
+    if tagged.as_ref().map(|(tag, _)| &tag.name) != Some(&name) {
+        warn!(
+            published = %name,
+            tagged = tagged.as_ref().map_or("none", |(tag, _)| tag.name.as_str()),
+            "the published version and the tags drifted apart"
+        );
+    }
+    let tag = match vcs.tags()?.into_iter().find(|tag| tag.name == name) {
+        Some(tag) => tag,
+        None => tagged.map_or_else(|| Tag::new(name, ""), |(tag, _)| tag),
+    };
+
+    Ok((tag, published))
+}
+
+fn plan_single_release(
+    vcs: &dyn Vcs,
+    config: &Config,
+    options: &ReleaseOptions,
+) -> Result<ReleasePlan, SemVerError> {
+    let package = options
+        .package
+        .as_deref()
+        .map(|name| config.package(name))
+        .transpose()?;
+
+    let format = tag_format(config, package.as_ref())?;
+    let branch = match &options.branch {
+        Some(branch) => Some(branch.clone()),
+        None => vcs.current_branch()?,
+    };
+    let channel = config.channel(branch.as_deref());
+    if let Some((tag, version)) = head_version_tag(vcs, &format, channel)? {
+        info!(tag = %tag.name, "already released");
+        return Ok(ReleasePlan {
+            previous_tag: tag.name.clone(),
+            previous_version: version.release(),
+            version: version.release(),
+            commit_message: config.release_commit_message(&tag.name, &version),
+            tag: tag.name,
+            push: options.push.clone(),
+            forge: options.forge,
+            prerelease: version.pre,
+            already_released: true,
+            sign: options.sign,
+            ..Default::default()
+        });
+    }
+    let tagged = vcs.latest_version_tag_with_format(&format)?;
+    #[cfg(feature = "forge")]
+    let tagged = match &options.registry {
+        Some((registry, name)) => match registry.latest_version(name)? {
+            Some(published) => Some(published_baseline(vcs, &format, tagged, published)?),
+            None => tagged,
+        },
+        None => tagged,
+    };
+    let previous = tagged;
+    let first_release = previous.is_none();
+    let (previous_tag, previous_version) = previous.unwrap_or_default();
+    // a published version without any tag releases the whole history
+    let commits = match previous_tag.sha.is_empty() {
+        true => vcs.commits(None)?,
+        false => vcs.commits(Some(&format!("{}..HEAD", previous_tag.name)))?,
+    };
+    let (mut skipped, commits): (Vec<_>, Vec<_>) = commits
+        .into_iter()
+        .partition(|commit| config.is_skipped(commit));
+    for commit in &skipped {
+        debug!(sha = %commit.sha, subject = commit.subject(), "skipped commit");
+    }
+    let (mut commits, mut unparsed) = match options.cache {
+        true => {
+            let path = vcs.directory().join(CACHE_FILE_NAME);
+            let mut cache = ParseCache::load(&path, config);
+            let parsed = config.parse_commits_cached(&commits, &mut cache);
+            cache.save(&path)?;
+            parsed
+        }
+        false => config.parse_commits(&commits),
+    };
+    if let Some(package) = &package {
+        let mut kept = vec![];
+        for commit in skipped {
+            if touches(vcs, package, &commit)? {
+                kept.push(commit);
+            }
+        }
+        skipped = kept;
+
+        let mut kept = vec![];
+        for parsed in commits {
+            if package.includes(&parsed, &vcs.changed_paths(&parsed.commit.sha)?) {
+                kept.push(parsed);
+            }
+        }
+        commits = kept;
+
+        let mut kept = vec![];
+        for (commit, error) in unparsed {
+            if touches(vcs, package, &commit)? {
+                kept.push((commit, error));
+            }
+        }
+        unparsed = kept;
+    }
+
+    let kept = cancel_reverts(commits.clone());
+    let policy = config.bump_policy()?;
+    let mut decision = policy.decide(&kept, branch.as_deref());
+    let kept_shas = kept
+        .iter()
+        .map(|parsed| parsed.commit.sha.as_str())
+        .collect::<HashSet<_>>();
+    for parsed in commits
+        .iter()
+        .filter(|parsed| !kept_shas.contains(parsed.commit.sha.as_str()))
+    {
+        decision.ignore(&parsed.commit, "reverted");
+    }
+    for commit in &skipped {
+        decision.ignore(commit, "skip marker or release commit");
+    }
+    for (commit, error) in &unparsed {
+        let error = error.to_string();
+        let error = error.lines().next().unwrap_or_default().trim_end();
+        decision.ignore(commit, format!("unparseable: {}", error));
+    }
+    let commits = kept;
+    let bump = decision.bump;
+
+    let version = match first_release {
+        true => options
+            .initial_version
+            .clone()
+            .map_or_else(|| config.first_version(), Ok)?,
+        false => config.next_version(&previous_version, bump)?,
+    };
+    let components = tag_components(config, &format, &previous_tag.name);
+    let (tag, prerelease) = channel_tag(vcs, &format, channel, &version, components)?;
+    info!(
+        previous = %previous_tag.name,
+        commits = commits.len(),
+        %bump,
+        %tag,
+        "planned release"
+    );
+
+    let contributors = match bump {
+        BumpLevel::None => vec![],
+        _ => release_contributors(vcs, config, &commits),
+    };
+
+    let mut changes = vec![];
+    if bump != BumpLevel::None && package.is_none() && prerelease.is_none() {
+        changes = plan_updates(&config.updaters()?, &version)?;
+        if let Some(path) = &config.changelog {
+            let tags = Some((previous_tag.name.as_str(), tag.as_str())).filter(|_| !first_release);
+            let section = render_changelog_section(
+                vcs,
+                config,
+                Some((&version, &today())),
+                &commits,
+                tags,
+                &contributors,
+            )?;
+            changes.push(plan_changelog(path, &section)?);
+        }
+    }
+
+    let security = commits
+        .iter()
+        .any(|parsed| matches!(parsed.comment.semantic_type, SemanticType::Security(_)));
+    let tagged = SemanticVersion {
+        pre: prerelease.clone(),
+        ..version.clone()
+    };
+    let commit_message = config.release_commit_message(&tag, &tagged);
+
+    Ok(ReleasePlan {
+        previous_tag: previous_tag.name,
+        previous_version,
+        version,
+        bump,
+        commits,
+        unparsed: unparsed.into_iter().map(|(commit, _)| commit).collect(),
+        skipped,
+        changes,
+        commit_message,
+        tag,
+        push: options.push.clone(),
+        forge: options.forge,
+        cascade: vec![],
+        security,
+        first_release,
+        prerelease,
+        already_released: false,
+        contributors,
+        sign: options.sign,
+        decision,
+    })
+}
+
+/// Returns the greatest tag of `format` on HEAD released on `channel`: a stable version without
+/// channel, a `<channel>.N` prerelease otherwise.
+fn head_version_tag(
+    vcs: &dyn Vcs,
+    format: &TagFormat,
+    channel: Option<&str>,
+) -> Result<Option<(Tag, SemanticVersion)>, SemVerError> {
+    let head = vcs.commit("HEAD")?.sha;
+    let on_channel = |version: &SemanticVersion| match (channel, &version.pre) {
+        (None, pre) => pre.is_none(),
+        (Some(channel), Some(pre)) => pre
+            .strip_prefix(channel)
+            .and_then(|number| number.strip_prefix('.'))
+            .is_some_and(|number| number.parse::<u64>().is_ok()),
+        (Some(_), None) => false,
+    };
+
+    Ok(vcs
+        .tags()?
+        .into_iter()
+        .filter(|tag| tag.sha == head)
+        .filter_map(|tag| Some((format.parse(&tag.name)?, tag)))
+        .filter(|(version, _)| on_channel(version))
+        .max_by(|(l, _), (r, _)| l.cmp(r))
+        .map(|(version, tag)| (tag, version)))
+}
+
+/// Returns the number of components of the next tag: three, or as many as `previous_tag`
+/// holds when [`Config::version_components`] preserves them.
+fn tag_components(config: &Config, format: &TagFormat, previous_tag: &str) -> usize {
+    match config.version_components {
+        VersionComponents::Normalize => 3,
+        VersionComponents::Preserve => format.components(previous_tag).unwrap_or(3),
+    }
+}
+
+/// Returns the tag of `version` in `format` with at most `components` components, with the
+/// next prerelease of `channel` if any, e.g. `v2.0.0-rc.1`, along with that prerelease.
+fn channel_tag(
+    vcs: &dyn Vcs,
+    format: &TagFormat,
+    channel: Option<&str>,
+    version: &SemanticVersion,
+    components: usize,
+) -> Result<(String, Option<String>), SemVerError> {
+    let Some(channel) = channel else {
+        return Ok((format.render_components(version, components), None));
+    };
+    let prerelease = next_prerelease(&vcs.tag_names()?, format, version, channel);
+    let tagged = SemanticVersion {
+        pre: Some(prerelease.clone()),
+        ..version.clone()
+    };
+
+    Ok((
+        format.render_components(&tagged, components),
+        Some(prerelease),
+    ))
+}
+
+/// Returns the prerelease following the ones of `channel` already tagged for `version`,
+/// e.g. `rc.3` when `v2.0.0-rc.1` and `v2.0.0-rc.2` exist.
+fn next_prerelease(
+    tags: &[String],
+    format: &TagFormat,
+    version: &SemanticVersion,
+    channel: &str,
+) -> String {
+    let prefix = format!("{}.", channel);
+    let last = tags
+        .iter()
+        .filter_map(|name| format.parse(name))
+        .filter(|tagged| tagged.release() == *version)
+        .filter_map(|tagged| tagged.pre?.strip_prefix(&prefix)?.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+
+    format!("{}.{}", channel, last + 1)
+}
+
+/// [`execute_release`] performs the steps of the plan.
+pub fn execute_release(git: &Git, plan: &ReleasePlan) -> Result<(), SemVerError> {
+    if plan.is_empty() {
+        return Ok(());
+    }
+
+    resume_release(git, None, &mut ReleaseState::new(plan, None), None)
+}
+
+/// [`publish_release`] publishes the released tags to `forge`, with their changelog as notes.
+///
+/// The tags are created by the forge when they were not pushed.
+pub fn publish_release(
+    forge: &dyn Forge,
+    git: &Git,
+    plan: &ReleasePlan,
+) -> Result<(), SemVerError> {
+    if plan.is_empty() {
+        return Ok(());
+    }
+
+    let mut state = ReleaseState::new(plan, Some(forge));
+    publish_releases(forge, git, &mut state, &|_| Ok(()))
+}
+
+/// State of the last release, written at the root of the repository.
+pub const RELEASE_STATE_FILE_NAME: &str = ".semver-release.json";
+
+/// [`ReleaseState`] records the steps of a release as they are performed: the updated files, the
+/// release commit, the created tags, the push and the identifiers of the forge releases.
+///
+/// Saved after every step by [`resume_release`], it lets a failed release be resumed without
+/// performing a step twice, and remains as an audit of the last release.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseState {
+    pub version: String,
+    pub tag: String,
+    pub files: Vec<ReleasedFile>,
+    pub commit_message: String,
+    /// Sha of the released commit, set once the release commit is created.
+    pub commit: Option<String>,
+    /// Tags of the release and its cascade releases.
+    pub tags: Vec<String>,
+    pub created_tags: Vec<String>,
+    pub push: Option<String>,
+    pub pushed: bool,
+    /// Forge the releases are published to.
+    pub forge: Option<String>,
+    pub releases: Vec<PublishedRelease>,
+    /// Whether the release commit and tags are signed, see [`ReleaseOptions::sign`].
+    #[serde(default)]
+    pub sign: bool,
+}
+
+/// [`ReleasedFile`] holds a file updated by a release, with its contents before the release.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReleasedFile {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+/// [`PublishedRelease`] holds a release to publish to the forge, `id` is set once it is created.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublishedRelease {
+    pub tag: String,
+    pub name: String,
+    pub notes: String,
+    pub id: Option<String>,
+}
+
+impl ReleaseState {
+    /// [`new`] returns the state of `plan` before any step, with the releases published to
+    /// `forge` when given.
+    pub fn new(plan: &ReleasePlan, forge: Option<&dyn Forge>) -> Self {
+        let releases = forge
+            .map(|forge| {
+                let mut section =
+                    render_release_with_links(&plan.version, &today(), &plan.commits, Some(forge));
+                section.push_str(&render_contributor_list(&plan.contributors));
+                let mut releases = vec![PublishedRelease {
+                    tag: plan.tag.clone(),
+                    name: plan.tag.clone(),
+                    notes: match plan.first_release {
+                        true => release_notes(&section),
+                        false => format!(
+                            "{}\n\n[Full changelog]({})",
+                            release_notes(&section),
+                            forge.compare_url(&plan.previous_tag, &plan.tag)
+                        ),
+                    },
+                    id: None,
+                }];
+                releases.extend(plan.cascade.iter().map(|release| PublishedRelease {
+                    tag: release.tag.clone(),
+                    name: release.tag.clone(),
+                    notes: format!("Released with its dependency `{}`.", release.dependency),
+                    id: None,
+                }));
+                releases
+            })
+            .unwrap_or_default();
+
+        Self {
+            version: String::from(plan.version.clone()),
+            tag: plan.tag.clone(),
+            files: plan
+                .changes
+                .iter()
+                .map(|change| ReleasedFile {
+                    path: change.path.clone(),
+                    original: change.original.clone(),
+                    updated: change.updated.clone(),
+                })
+                .collect(),
+            commit_message: plan.commit_message.clone(),
+            tags: plan.tags().into_iter().map(String::from).collect(),
+            push: plan.push.clone(),
+            forge: forge.map(|forge| forge.name().to_string()),
+            releases,
+            sign: plan.sign,
+            ..Default::default()
+        }
+    }
+
+    /// [`load`] reads the state at `path`, `None` when there is none.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>, SemVerError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// [`save`] writes the state to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SemVerError> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    /// Whether every step of the release was performed.
+    pub fn is_complete(&self) -> bool {
+        self.commit.is_some()
+            && self.tags.iter().all(|tag| self.created_tags.contains(tag))
+            && (self.push.is_none() || self.pushed)
+            && self.releases.iter().all(|release| release.id.is_some())
+    }
+}
+
+/// [`resume_release`] performs the steps of `state` not performed yet, saving it to `path` after
+/// each one: a release failing halfway is finished by calling it again with the saved state.
+///
+/// The releases are published to `forge`, which must be given when some are left.
+pub fn resume_release(
+    git: &Git,
+    forge: Option<&dyn Forge>,
+    state: &mut ReleaseState,
+    path: Option<&Path>,
+) -> Result<(), SemVerError> {
+    let save = |state: &ReleaseState| match path {
+        Some(path) => state.save(path),
+        None => Ok(()),
+    };
+    if state.sign {
+        git.signing_key()?;
+    }
+
+    if state.commit.is_none() {
+        // the commit may have been created by a run which failed before saving the state
+        let committed = git.run(["log", "-1", "--format=%B"]).ok().as_deref()
+            == Some(state.commit_message.trim());
+        if !state.files.is_empty() && !committed {
+            for file in &state.files {
+                fs::write(&file.path, &file.updated)?;
+                info!(path = %file.path.display(), "updated file");
+            }
+            let paths = state
+                .files
+                .iter()
+                .map(|file| file.path.clone())
+                .collect::<Vec<_>>();
+            match state.sign {
+                true => git.commit_paths_signed(&paths, &state.commit_message)?,
+                false => git.commit_paths(&paths, &state.commit_message)?,
+            }
+        }
+        state.commit = Some(git.run(["rev-parse", "HEAD"])?);
+        save(state)?;
+    }
+
+    let existing = git.tag_names()?;
+    for tag in state.tags.clone() {
+        if state.created_tags.contains(&tag) {
+            continue;
+        }
+        // only left when the version is allowed to be the same, see `ReleaseOptions`
+        if !existing.contains(&tag) {
+            match state.sign {
+                true => git.create_signed_tag(&tag, &tag)?,
+                false => git.create_tag(&tag, &tag)?,
+            }
+        }
+        state.created_tags.push(tag);
+        save(state)?;
+    }
+
+    if let Some(remote) = state.push.as_ref().filter(|_| !state.pushed) {
+        let tags = state.tags.iter().map(String::as_str).collect::<Vec<_>>();
+        git.push(remote, &tags)?;
+        state.pushed = true;
+        save(state)?;
+    }
+
+    if state.releases.iter().any(|release| release.id.is_none()) {
+        let forge = forge.ok_or_else(|| {
+            SemVerError::ForgeError(format!(
+                "the releases must be published to {}",
+                state.forge.as_deref().unwrap_or("a forge")
+            ))
+        })?;
+        publish_releases(forge, git, state, &save)?;
+    }
+
+    Ok(())
+}
+
+/// [`rollback_release`] undoes the steps recorded in `state`, saving it to `path` after each
+/// one, and returns the steps performed: the created tags are deleted, the release commit is
+/// reverted, or the updated files restored when it was not created, and with `remote`, the pushed
+/// tags are deleted from it and the revert is pushed.
+///
+/// The forge releases cannot be deleted and are reported instead.
+pub fn rollback_release(
+    git: &Git,
+    state: &mut ReleaseState,
+    remote: Option<&str>,
+    path: Option<&Path>,
+) -> Result<Vec<String>, SemVerError> {
+    let save = |state: &ReleaseState| match path {
+        Some(path) => state.save(path),
+        None => Ok(()),
+    };
+    let mut steps = vec![];
+
+    let existing = git.tag_names()?;
+    while let Some(tag) = state.created_tags.pop() {
+        if existing.contains(&tag) {
+            git.delete_tag(&tag)?;
+            steps.push(format!("delete tag: {}", tag));
+        }
+        save(state)?;
+    }
+
+    match state.commit.take() {
+        Some(sha) if !state.files.is_empty() => {
+            git.revert(&sha)?;
+            steps.push(format!("revert commit: {}", &sha[..7.min(sha.len())]));
+        }
+        Some(_) => (),
+        None => {
+            for file in &state.files {
+                // left alone when the release did not get to write it
+                if fs::read_to_string(&file.path).ok().as_ref() == Some(&file.updated) {
+                    fs::write(&file.path, &file.original)?;
+                    info!(path = %file.path.display(), "restored file");
+                    steps.push(format!("restore file: {}", file.path.display()));
+                }
+            }
+        }
+    }
+    save(state)?;
+
+    if let Some(remote) = remote.filter(|_| state.pushed) {
+        let tags = state.tags.iter().map(String::as_str).collect::<Vec<_>>();
+        git.delete_remote_tags(remote, &tags)?;
+        steps.push(format!("delete from {}: {}", remote, tags.join(", ")));
+        git.push(remote, &[])?;
+        steps.push(format!("push revert to {}", remote));
+        state.pushed = false;
+        save(state)?;
+    }
+
+    for release in &state.releases {
+        if let Some(id) = &release.id {
+            steps.push(format!(
+                "left release {} of {} on {}",
+                id,
+                release.tag,
+                state.forge.as_deref().unwrap_or("the forge")
+            ));
+        }
+    }
+
+    Ok(steps)
+}
+
+/// Creates the releases of `state` left on `forge`, on the released commit or HEAD.
+fn publish_releases(
+    forge: &dyn Forge,
+    git: &Git,
+    state: &mut ReleaseState,
+    save: &dyn Fn(&ReleaseState) -> Result<(), SemVerError>,
+) -> Result<(), SemVerError> {
+    let sha = match &state.commit {
+        Some(sha) => sha.clone(),
+        None => git.run(["rev-parse", "HEAD"])?,
+    };
+    for index in 0..state.releases.len() {
+        let release = &state.releases[index];
+        if release.id.is_some() {
+            continue;
+        }
+        let id = forge.create_release(&ForgeRelease {
+            tag: release.tag.clone(),
+            sha: sha.clone(),
+            name: release.name.clone(),
+            notes: release.notes.clone(),
+        })?;
+        info!(tag = %release.tag, id = %id, "published release");
+        state.releases[index].id = Some(id);
+        save(state)?;
+    }
+
+    Ok(())
+}
+
+/// Strips the title of a changelog section, the forge shows the release name instead.
+fn release_notes(section: &str) -> String {
+    section
+        .split_once('\n')
+        .map(|(_, notes)| notes.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn serialize_display<S: serde::Serializer, T: std::fmt::Display>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn touches(vcs: &dyn Vcs, package: &Package, commit: &Commit) -> Result<bool, SemVerError> {
+    Ok(vcs
+        .changed_paths(&commit.sha)?
+        .iter()
+        .any(|path| package.contains(path)))
+}
+
+fn plan_changelog(path: &Path, section: &str) -> Result<FileChange, SemVerError> {
+    let original = match path.exists() {
+        true => fs::read_to_string(path)?,
+        false => String::new(),
+    };
+    let updated = insert_release(&original, section);
+
+    Ok(FileChange {
+        path: path.to_path_buf(),
+        original,
+        updated,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::{Cell, RefCell};
+
+    use crate::git::test::{commit, init_repository};
+
+    use super::*;
+
+    #[test]
+    fn test_release_updates_files_commits_and_tags() {
+        let git = init_repository("release");
+        commit(&git, "VERSION", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        fs::write(git.directory().join("VERSION"), "0.1.0\n").unwrap();
+        commit(&git, "a.txt", "fix: a fix.");
+        commit(&git, "a.txt", "feat(api): a feature.");
+        commit(&git, "a.txt", "not conventional.");
+        commit(
+            &git,
+            "a.txt",
+            "feat! not released yet.\n\nRelease-Note: none",
+        );
+        git.run(["remote", "add", "origin", "git@github.com:team/app.git"])
+            .unwrap();
+
+        let config = Config::from_toml_str(&format!(
+            "changelog = {:?}\n\n[[files]]\npath = {:?}\n",
+            git.directory().join("CHANGELOG.md"),
+            git.directory().join("VERSION"),
+        ))
+        .unwrap();
+
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.bump, BumpLevel::Minor);
+        assert_eq!(plan.tag, "v0.2.0");
+        assert_eq!(plan.commits.len(), 2);
+        assert_eq!(plan.unparsed.len(), 1);
+        assert_eq!(plan.skipped.len(), 1);
+        let json: serde_json::Value =
+            serde_json::from_str(&plan.as_json_string().unwrap()).unwrap();
+        assert_eq!(
+            json["skipped"][0]["message"],
+            "feat! not released yet.\n\nRelease-Note: none"
+        );
+
+        execute_release(&git, &plan).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(git.directory().join("VERSION")).unwrap(),
+            "0.2.0\n"
+        );
+        let changelog = fs::read_to_string(git.directory().join("CHANGELOG.md")).unwrap();
+        assert!(changelog.contains("- **api:** a feature. (["));
+        assert!(changelog
+            .contains("## [v0.2.0](https://github.com/team/app/compare/v0.1.0...v0.2.0) ("));
+        assert_eq!(git.latest_version_tag().unwrap().unwrap().0.name, "v0.2.0");
+        assert_eq!(
+            git.run(["log", "-1", "--format=%s"]).unwrap(),
+            "chore(release): v0.2.0 [skip ci]"
+        );
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[derive(Default)]
+    struct RecordingForge {
+        releases: RefCell<Vec<ForgeRelease>>,
+        unavailable: Cell<bool>,
+    }
+
+    impl Forge for RecordingForge {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn compare_url(&self, from: &str, to: &str) -> String {
+            format!("https://forge/compare/{}...{}", from, to)
+        }
+
+        fn commit_url(&self, sha: &str) -> String {
+            format!("https://forge/commit/{}", sha)
+        }
+
+        fn create_release(&self, release: &ForgeRelease) -> Result<String, SemVerError> {
+            if self.unavailable.get() {
+                return Err(SemVerError::ForgeError("unavailable".to_string()));
+            }
+            self.releases.borrow_mut().push(release.clone());
+            Ok(format!("r{}", self.releases.borrow().len()))
+        }
+
+        fn list_tags(&self) -> Result<Vec<crate::Tag>, SemVerError> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn test_plan_release_bootstraps_the_first_release() {
+        let git = init_repository("release-first");
+        commit(&git, "a.txt", "feat: first.");
+        commit(&git, "a.txt", "fix: second.");
+
+        let plan = plan_release(&git, &Config::default(), &ReleaseOptions::default()).unwrap();
+        assert!(plan.first_release);
+        assert_eq!(plan.commits.len(), 2);
+        assert_eq!(plan.tag, "v0.1.0");
+        assert!(plan
+            .as_json_string()
+            .unwrap()
+            .contains("\"first_release\":true"));
+
+        let config = Config::from_toml_str("initial_version = \"1.0.0\"\n").unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.tag, "v1.0.0");
+    }
+
+    #[test]
+    fn test_plan_release_numbers_the_prereleases_of_a_channel() {
+        let git = init_repository("release-channels");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v1.2.0"]).unwrap();
+        commit(&git, "a.txt", "feat!: breaking.");
+
+        let config =
+            Config::from_toml_str("[channels]\nmain = \"stable\"\nnext = \"rc\"\n").unwrap();
+        let next = ReleaseOptions {
+            branch: Some("next".to_string()),
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &config, &next).unwrap();
+        assert_eq!(plan.tag, "v2.0.0-rc.1");
+        assert_eq!(plan.prerelease.as_deref(), Some("rc.1"));
+        execute_release(&git, &plan).unwrap();
+
+        commit(&git, "a.txt", "fix: a fix.");
+        let plan = plan_release(&git, &config, &next).unwrap();
+        assert_eq!(plan.tag, "v2.0.0-rc.2");
+
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.tag, "v2.0.0");
+        assert_eq!(plan.prerelease, None);
+    }
+
+    #[test]
+    fn test_plan_release_reports_a_tagged_head_as_already_released() {
+        let git = init_repository("release-already");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v1.2.0"]).unwrap();
+        commit(&git, "a.txt", "feat!: breaking.");
+        git.run(["tag", "v2.0.0-rc.1"]).unwrap();
+
+        let config =
+            Config::from_toml_str("[channels]\nmain = \"stable\"\nnext = \"rc\"\n").unwrap();
+        let options = ReleaseOptions {
+            branch: Some("next".to_string()),
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &config, &options).unwrap();
+        assert!(plan.already_released);
+        assert!(plan.is_empty());
+        assert_eq!(plan.tag, "v2.0.0-rc.1");
+        assert_eq!(plan.prerelease.as_deref(), Some("rc.1"));
+        assert_eq!(
+            plan.steps(),
+            vec!["detect last tag: v2.0.0-rc.1, already released at HEAD"]
+        );
+
+        commit(&git, "a.txt", "fix: a fix.");
+        let plan = plan_release(&git, &config, &options).unwrap();
+        assert!(!plan.already_released);
+        assert_eq!(plan.tag, "v2.0.0-rc.2");
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_plan_release_releases_a_head_tagged_on_another_channel() {
+        let git = init_repository("release-other-channel");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v1.2.0"]).unwrap();
+        commit(&git, "a.txt", "feat!: breaking.");
+        git.run(["tag", "v2.0.0-rc.1"]).unwrap();
+
+        let config =
+            Config::from_toml_str("[channels]\nmain = \"stable\"\nnext = \"rc\"\n").unwrap();
+        let main = ReleaseOptions {
+            branch: Some("main".to_string()),
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &config, &main).unwrap();
+        assert!(!plan.already_released);
+        assert_eq!(plan.tag, "v2.0.0");
+        assert_eq!(plan.prerelease, None);
+
+        git.run(["tag", "v2.0.0"]).unwrap();
+        let plan = plan_release(&git, &config, &main).unwrap();
+        assert!(plan.already_released);
+        assert_eq!(plan.tag, "v2.0.0");
+
+        let beta = Config::from_toml_str("[channels]\nnext = \"beta\"\n").unwrap();
+        let next = ReleaseOptions {
+            branch: Some("next".to_string()),
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &beta, &next).unwrap();
+        assert!(!plan.already_released);
+        assert_eq!(plan.prerelease.as_deref(), Some("beta.1"));
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_plan_release_follows_the_configured_tag_format() {
+        let git = init_repository("release-tag-format");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "release/1.0.0"]).unwrap();
+        git.run(["tag", "v9.0.0"]).unwrap();
+        commit(&git, "a.txt", "feat: second.");
+
+        let config =
+            Config::from_toml_str("tag_format = \"release/{major}.{minor}.{patch}\"\n").unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.previous_tag, "release/1.0.0");
+        assert_eq!(plan.tag, "release/1.1.0");
+    }
+
+    #[test]
+    fn test_snapshot_tag_renders_the_next_version_date_time_and_sha() {
+        // 2024-06-01 12:34:56 UTC
+        let seconds = 1_717_245_296;
+        let format = TagFormat::default();
+        let release = ReleasePlan {
+            version: SemanticVersion::new(1, 3, 0),
+            bump: BumpLevel::Minor,
+            tag: "v1.3.0".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            release
+                .snapshot_tag(&format, DEFAULT_SNAPSHOT_TEMPLATE, seconds, "abc1234")
+                .unwrap(),
+            "v1.3.0-nightly.20240601+sha.abc1234"
+        );
+        assert_eq!(
+            release
+                .snapshot_tag(&format, "{version}-{date}{time}.{sha}", seconds, "abc1234")
+                .unwrap(),
+            "v1.3.0-20240601123456.abc1234"
+        );
+
+        let prerelease = ReleasePlan {
+            version: SemanticVersion::new(2, 0, 0),
+            bump: BumpLevel::Major,
+            tag: "api-v2.0.0-rc.3".to_string(),
+            prerelease: Some("rc.3".to_string()),
+            ..Default::default()
+        };
+        let api = format.with_prefix("api-").unwrap();
+        assert_eq!(
+            prerelease
+                .snapshot_tag(&api, DEFAULT_SNAPSHOT_TEMPLATE, seconds, "abc1234")
+                .unwrap(),
+            "api-v2.0.0-nightly.20240601+sha.abc1234"
+        );
+
+        let nothing_to_release = ReleasePlan {
+            version: SemanticVersion::new(1, 3, 0),
+            tag: "v1.3.0".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            nothing_to_release
+                .snapshot_tag(&format, DEFAULT_SNAPSHOT_TEMPLATE, seconds, "abc1234")
+                .unwrap(),
+            "v1.3.1-nightly.20240601+sha.abc1234"
+        );
+
+        let suffixed = TagFormat::new("v{major}.{minor}.{patch}{-pre}-linux").unwrap();
+        let prerelease = ReleasePlan {
+            version: SemanticVersion::new(1, 3, 0),
+            bump: BumpLevel::Minor,
+            tag: "v1.3.0-rc.1-linux".to_string(),
+            prerelease: Some("rc.1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            prerelease
+                .snapshot_tag(&suffixed, DEFAULT_SNAPSHOT_TEMPLATE, seconds, "abc1234")
+                .unwrap(),
+            "v1.3.0-linux-nightly.20240601+sha.abc1234"
+        );
+
+        let short = TagFormat::default().lenient().unwrap();
+        let nothing_to_release = ReleasePlan {
+            version: SemanticVersion::new(1, 3, 0),
+            tag: "v1.3".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            nothing_to_release
+                .snapshot_tag(&short, DEFAULT_SNAPSHOT_TEMPLATE, seconds, "abc1234")
+                .unwrap(),
+            "v1.3.1-nightly.20240601+sha.abc1234"
+        );
+    }
+
+    #[test]
+    fn test_plan_release_explains_the_bump_decision() {
+        let git = init_repository("release-decision");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v1.0.0"]).unwrap();
+        commit(&git, "a.txt", "feat(deps): bump serde.");
+        commit(&git, "a.txt", "fix: a fix.");
+        commit(&git, "a.txt", "feat: dropped later.");
+        commit(&git, "a.txt", "revert: feat: dropped later.");
+        commit(&git, "a.txt", "docs: a guide.");
+        commit(&git, "a.txt", "wip");
+
+        let config = Config::from_toml_str(
+            r#"
+types = ["feat", "fix", "docs"]
+
+[[policy.rules]]
+when = "scope == deps"
+bump = "patch"
+"#,
+        )
+        .unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        let decision = &plan.decision;
+        assert_eq!(plan.tag, "v1.0.1");
+        assert_eq!(decision.bump, BumpLevel::Patch);
+        let rules = decision
+            .drivers
+            .iter()
+            .map(|driver| (driver.subject.as_str(), driver.rule.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                (
+                    "feat(deps): bump serde.",
+                    "policy rule `scope == deps`".to_string()
+                ),
+                ("fix: a fix.", "default bump of the type".to_string()),
+            ]
+        );
+        let ignored = decision
+            .ignored
+            .iter()
+            .map(|ignored| (ignored.subject.as_str(), ignored.reason.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ignored,
+            vec![
+                ("docs: a guide.", "no bump, default bump of the type"),
+                ("feat: dropped later.", "reverted"),
+                ("revert: feat: dropped later.", "reverted"),
+                ("wip", "unparseable: The format provided is invalid!"),
+            ]
+        );
+        assert!(plan
+            .as_json_string()
+            .unwrap()
+            .contains(r#""rule":{"policy":"scope == deps"}"#));
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_plan_release_skips_the_release_commits() {
+        let git = init_repository("release-skips-release-commits");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "b.txt", "chore(release): api-v0.3.0 [skip ci]");
+        commit(&git, "a.txt", "fix: second.");
+
+        let config = Config::from_toml_str(
+            "release_commit_template = \"chore(release): {tag} [skip ci]\"\n",
+        )
+        .unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.skipped.len(), 1);
+        assert!(plan.unparsed.is_empty());
+        assert_eq!(plan.commit_message, "chore(release): v0.1.1 [skip ci]");
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_plan_release_reads_and_preserves_short_tags() {
+        let git = init_repository("release-short-tags");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v1.2"]).unwrap();
+        commit(&git, "a.txt", "feat: second.");
+
+        let config = Config::from_toml_str("lenient_versions = true\n").unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.previous_tag, "v1.2");
+        assert_eq!(plan.tag, "v1.3.0");
+
+        let config =
+            Config::from_toml_str("lenient_versions = true\nversion_components = \"preserve\"\n")
+                .unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.tag, "v1.3");
+
+        git.run(["tag", "v1.3"]).unwrap();
+        commit(&git, "a.txt", "fix: third.");
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.tag, "v1.3.1");
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_plan_release_refuses_existing_tags_and_lower_versions() {
+        let git = init_repository("release-monotonic");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        git.run(["checkout", "-q", "-b", "side"]).unwrap();
+        commit(&git, "b.txt", "fix: on the side.");
+        git.run(["tag", "v0.1.1"]).unwrap();
+        git.run(["checkout", "-q", "main"]).unwrap();
+        commit(&git, "a.txt", "fix: a fix.");
+
+        let options = ReleaseOptions::default();
+        assert_eq!(
+            plan_release(&git, &Config::default(), &options).unwrap_err(),
+            SemVerError::TagAlreadyExists("v0.1.1".to_string())
+        );
+        let options = ReleaseOptions {
+            allow_same_version: true,
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &Config::default(), &options).unwrap();
+        assert_eq!(plan.tag, "v0.1.1");
+        execute_release(&git, &plan).unwrap();
+
+        git.run(["tag", "v0.3.0", "side"]).unwrap();
+        commit(&git, "a.txt", "feat: a feature.");
+        assert_eq!(
+            plan_release(&git, &Config::default(), &options).unwrap_err(),
+            SemVerError::NonMonotonicVersion("v0.2.0".to_string(), "v0.3.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_publish_release_creates_a_release_with_the_changelog_as_notes() {
+        let git = init_repository("release-forge");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "a.txt", "fix(api): a fix.");
+
+        let options = ReleaseOptions {
+            forge: Some(ForgeKind::GitLab),
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &Config::default(), &options).unwrap();
+        assert!(plan
+            .steps()
+            .contains(&"publish v0.1.1 to gitlab".to_string()));
+
+        let forge = RecordingForge::default();
+        publish_release(&forge, &git, &plan).unwrap();
+
+        let releases = forge.releases.into_inner();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].tag, "v0.1.1");
+        assert_eq!(releases[0].sha, plan.commits[0].commit.sha);
+        let sha = &plan.commits[0].commit.sha;
+        assert_eq!(
+            releases[0].notes,
+            format!(
+                "### Bug Fixes\n\n- **api:** a fix. ([{}](https://forge/commit/{}))\n\n\
+                [Full changelog](https://forge/compare/v0.1.0...v0.1.1)",
+                &sha[..7],
+                sha
+            )
+        );
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_resume_release_finishes_a_failed_release_without_repeating_steps() {
+        let git = init_repository("release-resume");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "a.txt", "feat: second.");
+
+        let config = Config::from_toml_str(&format!(
+            "changelog = {:?}\n",
+            git.directory().join("CHANGELOG.md")
+        ))
+        .unwrap();
+        let options = ReleaseOptions {
+            forge: Some(ForgeKind::GitLab),
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &config, &options).unwrap();
+        let forge = RecordingForge::default();
+        forge.unavailable.set(true);
+        let path = git.directory().join(RELEASE_STATE_FILE_NAME);
+
+        let mut state = ReleaseState::new(&plan, Some(&forge));
+        assert!(resume_release(&git, Some(&forge), &mut state, Some(&path)).is_err());
+
+        let mut state = ReleaseState::load(&path).unwrap().unwrap();
+        assert!(!state.is_complete());
+        assert_eq!(state.created_tags, vec!["v0.2.0"]);
+        assert_eq!(state.releases[0].id, None);
+
+        forge.unavailable.set(false);
+        resume_release(&git, Some(&forge), &mut state, Some(&path)).unwrap();
+        resume_release(&git, Some(&forge), &mut state, Some(&path)).unwrap();
+
+        let state = ReleaseState::load(&path).unwrap().unwrap();
+        assert!(state.is_complete());
+        assert_eq!(state.releases[0].id.as_deref(), Some("r1"));
+        assert_eq!(forge.releases.borrow().len(), 1);
+        assert_eq!(git.run(["log", "--format=%s"]).unwrap().lines().count(), 3);
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_rollback_release_deletes_the_tag_and_reverts_the_release_commit() {
+        let git = init_repository("release-rollback");
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "a.txt", "fix: second.");
+        let changelog = git.directory().join("CHANGELOG.md");
+        fs::write(&changelog, "# Changelog\n").unwrap();
+        git.commit_paths(std::slice::from_ref(&changelog), "docs: changelog.")
+            .unwrap();
+
+        let config = Config::from_toml_str(&format!("changelog = {:?}\n", changelog)).unwrap();
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        let mut state = ReleaseState::new(&plan, None);
+        resume_release(&git, None, &mut state, None).unwrap();
+        assert_ne!(fs::read_to_string(&changelog).unwrap(), "# Changelog\n");
+
+        let steps = rollback_release(&git, &mut state, Some("origin"), None).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0], "delete tag: v0.1.1");
+        assert!(!git.tag_names().unwrap().contains(&"v0.1.1".to_string()));
+        assert_eq!(fs::read_to_string(&changelog).unwrap(), "# Changelog\n");
+        assert_eq!(
+            git.run(["log", "-1", "--format=%s"]).unwrap(),
+            "Revert \"chore(release): v0.1.1 [skip ci]\""
+        );
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_resume_release_signs_the_release_commit_and_tag() {
+        let git = init_repository("release-sign");
+        commit(&git, "a.txt", "feat: first.");
+        let changelog = git.directory().join("CHANGELOG.md");
+        let config = Config::from_toml_str(&format!("changelog = {:?}\n", changelog)).unwrap();
+        let options = ReleaseOptions {
+            sign: true,
+            ..Default::default()
+        };
+        let plan = plan_release(&git, &config, &options).unwrap();
+        assert!(plan
+            .steps()
+            .contains(&"create signed tag: v0.1.0".to_string()));
+
+        git.run(["config", "user.signingkey", ""]).unwrap();
+        let mut state = ReleaseState::new(&plan, None);
+        assert!(matches!(
+            resume_release(&git, None, &mut state, None),
+            Err(SemVerError::SigningKeyMissing)
+        ));
+        assert!(git.tag_names().unwrap().is_empty());
+
+        let key = git.directory().join("signing-key");
+        let generated = std::process::Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status();
+        if generated.is_ok_and(|status| status.success()) {
+            git.run(["config", "gpg.format", "ssh"]).unwrap();
+            git.run(["config", "user.signingkey", key.to_str().unwrap()])
+                .unwrap();
+            resume_release(&git, None, &mut state, None).unwrap();
+
+            assert!(git
+                .run(["cat-file", "-p", "v0.1.0"])
+                .unwrap()
+                .contains("-----BEGIN SSH SIGNATURE-----"));
+            assert!(git
+                .run(["cat-file", "-p", "HEAD"])
+                .unwrap()
+                .contains("-----BEGIN SSH SIGNATURE-----"));
+        }
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[test]
+    fn test_release_of_a_package_only_considers_its_commits_and_tags() {
+        let git = init_repository("release-package");
+        fs::create_dir_all(git.directory().join("api")).unwrap();
+        commit(&git, "api/lib.rs", "feat: first.");
+        git.run(["tag", "v1.0.0"]).unwrap();
+        git.run(["tag", "api-v0.1.0"]).unwrap();
+        git.run(["tag", "web-v2.0.0"]).unwrap();
+        commit(&git, "web.ts", "feat: web feature.");
+        commit(&git, "web.ts", "fix(api): scoped fix.");
+        commit(&git, "api/lib.rs", "not conventional.");
+
+        let config = Config::from_toml_str(&format!(
+            "changelog = {:?}\nchangelog_layout = \"package\"\n\n[packages]\napi = [\"api/**\"]\nweb = {{ paths = [\"web.ts\"], depends_on = [\"api\"] }}\n",
+            git.directory().join("CHANGELOG.md"),
+        ))
+        .unwrap();
+        let options = ReleaseOptions {
+            package: Some("api".to_string()),
+            ..Default::default()
+        };
+
+        let plan = plan_release(&git, &config, &options).unwrap();
+        assert_eq!(plan.previous_tag, "api-v0.1.0");
+        assert_eq!(plan.tag, "api-v0.1.1");
+        assert_eq!(plan.commits.len(), 1);
+        assert_eq!(plan.unparsed.len(), 1);
+        assert_eq!(plan.cascade.len(), 1);
+        assert_eq!(plan.cascade[0].dependency, "api");
+        assert_eq!(plan.cascade[0].bump, BumpLevel::Minor);
+        assert_eq!(plan.tags(), vec!["api-v0.1.1", "web-v2.1.0"]);
+
+        let plan = plan_release(&git, &config, &ReleaseOptions::default()).unwrap();
+        assert_eq!(plan.tag, "v1.1.0");
+        let changelog = &plan.changes.last().unwrap().updated;
+        assert!(changelog.contains("\n### api\n\n#### Bug Fixes\n\n- scoped fix. ("));
+        assert!(changelog.contains("\n### web\n\n#### Features\n\n- web feature. ("));
+        assert!(changelog.contains("\n#### Bug Fixes\n\n- **api:** scoped fix. ("));
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+
+    #[cfg(feature = "forge")]
+    #[test]
+    fn test_published_baseline_starts_from_the_tag_of_the_published_version() {
+        let git = init_repository("published");
+        let format = TagFormat::default();
+        commit(&git, "a.txt", "feat: first.");
+        git.run(["tag", "v0.1.0"]).unwrap();
+        commit(&git, "a.txt", "feat: second.");
+        git.run(["tag", "v0.2.0"]).unwrap();
+        let tagged = git.latest_version_tag().unwrap();
+
+        let (tag, version) =
+            published_baseline(&git, &format, tagged.clone(), SemanticVersion::new(0, 1, 0))
+                .unwrap();
+        assert_eq!(tag.name, "v0.1.0");
+        assert_eq!(version, SemanticVersion::new(0, 1, 0));
+
+        let (tag, version) =
+            published_baseline(&git, &format, tagged, SemanticVersion::new(0, 3, 0)).unwrap();
+        assert_eq!(tag.name, "v0.2.0");
+        assert_eq!(version, SemanticVersion::new(0, 3, 0));
+
+        let (tag, _) = published_baseline(
+            &git,
+            &format.with_prefix("api-").unwrap(),
+            None,
+            SemanticVersion::new(1, 0, 0),
+        )
+        .unwrap();
+        assert_eq!(tag.name, "api-v1.0.0");
+        assert!(tag.sha.is_empty());
+
+        fs::remove_dir_all(git.directory()).unwrap();
+    }
+}
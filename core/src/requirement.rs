@@ -0,0 +1,455 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use std::{cmp::Ordering, fmt, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::models::literal_regex;
+use crate::{ComparisonReason, SemVerError, SemanticVersion, VersionComparison};
+
+/// A single comparator, e.g. `>=1.2` or `~1.2.3-rc.1`.
+static COMPARATOR_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    literal_regex(
+        r"^(?P<op>=|>=|>|<=|<|~|\^)?\s*v?(?P<major>\*|[xX]|[0-9]+)(?:\.(?P<minor>\*|[xX]|[0-9]+))?(?:\.(?P<patch>\*|[xX]|[0-9]+))?(?:-(?P<pre>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?$",
+    )
+});
+
+/// An operator followed by whitespace, e.g. `>= 1.2`.
+static SPACED_OPERATOR_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| literal_regex(r"(=|>|<|~|\^)\s+"));
+
+/// [`VersionReq`] is a version requirement such as `^1.2`, `~1.2.3`, `>=1.0, <2`, `1.*`, or
+/// alternatives such as `^1.4 || ^2`.
+///
+/// Comparators follow the Cargo semantics: a bare version is a caret requirement, and a
+/// prerelease only matches the comparators naming its `major.minor.patch` with a prerelease.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let req = VersionReq::parse("^1.2").unwrap();
+///
+/// assert!(req.matches(&SemanticVersion::try_from("v1.4.0").unwrap()));
+/// assert!(!req.matches(&SemanticVersion::try_from("v2.0.0").unwrap()));
+/// assert!(!req.matches(&SemanticVersion::try_from("v1.4.0-rc.1").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    source: String,
+    /// Alternatives, each one holding the comparators a matching version satisfies together.
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+    Wildcard,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Option<String>,
+}
+
+impl VersionReq {
+    /// [`parse`] reads a requirement: comparators separated by commas or spaces, and
+    /// alternatives separated by `||`.
+    pub fn parse(source: &str) -> Result<Self, SemVerError> {
+        let invalid = || SemVerError::InvalidRequirement(source.to_string());
+        // glues the operators to their version, e.g. `>= 1.2` to `>=1.2`
+        let glued = SPACED_OPERATOR_PATTERN.replace_all(source, "$1");
+
+        let mut alternatives = vec![];
+        for alternative in glued.split("||") {
+            let mut comparators = vec![];
+            for comparator in alternative
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|c| !c.is_empty())
+            {
+                let captures = COMPARATOR_PATTERN
+                    .captures(comparator)
+                    .ok_or_else(invalid)?;
+                let number = |name: &str| -> Result<Option<u64>, SemVerError> {
+                    match captures.name(name).map(|m| m.as_str()) {
+                        None | Some("*") | Some("x") | Some("X") => Ok(None),
+                        Some(number) => Ok(Some(number.parse()?)),
+                    }
+                };
+                let (major, minor, patch) = (number("major")?, number("minor")?, number("patch")?);
+                let wildcard = ["major", "minor", "patch"].iter().any(|name| {
+                    matches!(
+                        captures.name(name).map(|m| m.as_str()),
+                        Some("*" | "x" | "X")
+                    )
+                });
+                if (major.is_none() && minor.is_some()) || (minor.is_none() && patch.is_some()) {
+                    return Err(invalid());
+                }
+                let Some(major) = major else {
+                    // `*` matches every release
+                    comparators.push(Comparator {
+                        op: Op::GreaterEq,
+                        major: 0,
+                        minor: Some(0),
+                        patch: Some(0),
+                        pre: None,
+                    });
+                    continue;
+                };
+
+                comparators.push(Comparator {
+                    op: match (captures.name("op").map(|m| m.as_str()), wildcard) {
+                        (None, true) => Op::Wildcard,
+                        (_, true) => return Err(invalid()),
+                        (None | Some("^"), false) => Op::Caret,
+                        (Some("="), _) => Op::Exact,
+                        (Some(">"), _) => Op::Greater,
+                        (Some(">="), _) => Op::GreaterEq,
+                        (Some("<"), _) => Op::Less,
+                        (Some("<="), _) => Op::LessEq,
+                        (Some(_), _) => Op::Tilde,
+                    },
+                    major,
+                    minor,
+                    patch,
+                    pre: captures.name("pre").map(|m| m.as_str().to_string()),
+                });
+            }
+            if comparators.is_empty() {
+                return Err(invalid());
+            }
+            alternatives.push(comparators);
+        }
+
+        Ok(VersionReq {
+            source: source.to_string(),
+            alternatives,
+        })
+    }
+
+    /// [`matches`] returns `true` when `version` satisfies the requirement.
+    pub fn matches(&self, version: &SemanticVersion) -> bool {
+        self.alternatives
+            .iter()
+            .any(|comparators| alternative_mismatch(comparators, version).is_none())
+    }
+
+    /// [`explain`] checks `version` like [`VersionReq::matches`], reporting for each alternative
+    /// the comparator it fails, if any.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let explanation = VersionReq::parse("^1.2 || >=3")
+    ///     .unwrap()
+    ///     .explain(&SemanticVersion::try_from("v2.0.0").unwrap());
+    ///
+    /// assert!(!explanation.satisfied);
+    /// assert_eq!(
+    ///     explanation.to_string(),
+    ///     "`^1.2` is not satisfied: `v2.0.0` is not below `2.0.0`\n`>=3` is not satisfied: `>=3` fails, major `2` < `3`"
+    /// );
+    /// ```
+    pub fn explain(&self, version: &SemanticVersion) -> RequirementExplanation {
+        let alternatives = self
+            .alternatives
+            .iter()
+            .map(|comparators| {
+                let source = comparators
+                    .iter()
+                    .map(Comparator::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (source, alternative_mismatch(comparators, version))
+            })
+            .collect::<Vec<_>>();
+
+        RequirementExplanation {
+            satisfied: alternatives.iter().any(|(_, mismatch)| mismatch.is_none()),
+            alternatives,
+        }
+    }
+}
+
+/// [`RequirementExplanation`] holds the result of [`VersionReq::explain`].
+///
+/// Its [`fmt::Display`] implementation describes each alternative on its own line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementExplanation {
+    pub satisfied: bool,
+    /// Each alternative, with why the version does not satisfy it or `None` when it does.
+    pub alternatives: Vec<(String, Option<String>)>,
+}
+
+impl fmt::Display for RequirementExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .alternatives
+            .iter()
+            .map(|(source, mismatch)| match mismatch {
+                Some(mismatch) => format!("`{}` is not satisfied: {}", source, mismatch),
+                None => format!("`{}` is satisfied", source),
+            });
+
+        write!(f, "{}", lines.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// Returns why `version` does not satisfy the comparators of an alternative together, `None`
+/// when it does.
+fn alternative_mismatch(comparators: &[Comparator], version: &SemanticVersion) -> Option<String> {
+    if let Some(mismatch) = comparators.iter().find_map(|c| c.mismatch(version)) {
+        return Some(mismatch);
+    }
+    if version.is_prerelease() && !comparators.iter().any(|c| c.allows_prerelease_of(version)) {
+        return Some(format!(
+            "no comparator names `{}` with a prerelease",
+            String::from(version.release())
+        ));
+    }
+
+    None
+}
+
+impl Comparator {
+    /// Returns `true` when the comparator names the release of the prerelease `version`.
+    fn allows_prerelease_of(&self, version: &SemanticVersion) -> bool {
+        self.pre.is_some()
+            && self.major == version.major
+            && self.minor == Some(version.minor)
+            && self.patch == Some(version.patch)
+    }
+
+    /// Compares `version` with the comparator, its missing components matching any value.
+    fn cmp_partial(&self, version: &SemanticVersion) -> VersionComparison {
+        let lower = SemanticVersion {
+            pre: self.pre.clone(),
+            ..SemanticVersion::new(self.major, self.minor.unwrap_or(0), self.patch.unwrap_or(0))
+        };
+        let comparison = version.explain_cmp(&lower);
+        let compared = match (self.minor, self.patch) {
+            (None, _) => matches!(comparison.reason, ComparisonReason::Major(..)),
+            (Some(_), None) => matches!(
+                comparison.reason,
+                ComparisonReason::Major(..) | ComparisonReason::Minor(..)
+            ),
+            (Some(_), Some(_)) => true,
+        };
+
+        match compared {
+            true => comparison,
+            false => VersionComparison {
+                ordering: Ordering::Equal,
+                reason: ComparisonReason::Equal,
+            },
+        }
+    }
+
+    /// Returns why `version` does not match the comparator, `None` when it does.
+    fn mismatch(&self, version: &SemanticVersion) -> Option<String> {
+        let comparison = self.cmp_partial(version);
+        let ordering = comparison.ordering;
+        let in_order = match self.op {
+            Op::Exact | Op::Wildcard => ordering == Ordering::Equal,
+            Op::Greater => ordering == Ordering::Greater,
+            Op::GreaterEq | Op::Tilde | Op::Caret => ordering != Ordering::Less,
+            Op::Less => ordering == Ordering::Less,
+            Op::LessEq => ordering != Ordering::Greater,
+        };
+        if !in_order {
+            return Some(format!("`{}` fails, {}", self, comparison));
+        }
+
+        // widened so that the bounds past `u64::MAX` cannot overflow
+        let upper: [u128; 3] = match (self.op, self.major, self.minor, self.patch) {
+            (Op::Tilde, major, Some(minor), _) => [major.into(), u128::from(minor) + 1, 0],
+            (Op::Tilde, major, None, _) => [u128::from(major) + 1, 0, 0],
+            (Op::Caret, 0, None, _) => [1, 0, 0],
+            (Op::Caret, 0, Some(0), None) => [0, 1, 0],
+            (Op::Caret, 0, Some(0), Some(patch)) => [0, 0, u128::from(patch) + 1],
+            (Op::Caret, 0, Some(minor), _) => [0, u128::from(minor) + 1, 0],
+            (Op::Caret, major, _, _) => [u128::from(major) + 1, 0, 0],
+            _ => return None,
+        };
+        let release = [version.major, version.minor, version.patch].map(u128::from);
+
+        match release < upper {
+            true => None,
+            false => {
+                let [major, minor, patch] = upper;
+                Some(format!(
+                    "`{}` is not below `{}.{}.{}`",
+                    String::from(version.clone()),
+                    major,
+                    minor,
+                    patch
+                ))
+            }
+        }
+    }
+}
+
+/// Renders the comparator as parsed, e.g. `^1.2` or `1.2.*`.
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Wildcard => "",
+        };
+        write!(f, "{}{}", op, self.major)?;
+        for component in [self.minor, self.patch] {
+            match (component, self.op) {
+                (Some(component), _) => write!(f, ".{}", component)?,
+                (None, Op::Wildcard) => return write!(f, ".*"),
+                (None, _) => break,
+            }
+        }
+        match &self.pre {
+            Some(pre) => write!(f, "-{}", pre),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl TryFrom<&str> for VersionReq {
+    type Error = SemVerError;
+
+    fn try_from(source: &str) -> Result<Self, Self::Error> {
+        Self::parse(source)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_version_req_matches_like_cargo() {
+        let cases = [
+            ("^1.2.3", "v1.9.0", true),
+            ("^1.2.3", "v1.2.2", false),
+            ("^0.2.3", "v0.2.9", true),
+            ("^0.2.3", "v0.3.0", false),
+            ("^0.0.3", "v0.0.4", false),
+            ("~1.2", "v1.2.7", true),
+            ("~1.2.3", "v1.3.0", false),
+            ("~1", "v1.9.9", true),
+            (">=1.0, <2", "v1.5.0", true),
+            (">= 1.0 < 2", "v2.0.0", false),
+            (">1.2", "v1.2.9", false),
+            (">1.2", "v1.3.0", true),
+            ("<=1.2", "v1.2.9", true),
+            ("=1.2.3", "v1.2.3", true),
+            ("1.2.*", "v1.2.5", true),
+            ("1.x", "v2.0.0", false),
+            ("*", "v0.0.1", true),
+            ("^1.4 || ^2", "v2.1.0", true),
+            ("^1.4 || ^2", "v1.3.0", false),
+            (">=2.0.0-rc.1", "v2.0.0-rc.2", true),
+            (">=2.0.0-rc.1", "v2.1.0-rc.1", false),
+            (">=1.0.0", "v2.0.0-rc.1", false),
+        ];
+
+        for (req, version, expected) in cases {
+            let version = SemanticVersion::try_from(version).unwrap();
+            assert_eq!(
+                VersionReq::parse(req).unwrap().matches(&version),
+                expected,
+                "{} {}",
+                req,
+                String::from(version)
+            );
+        }
+        for invalid in ["", "^", "1.*.3", ">=1.*", "abc"] {
+            assert!(VersionReq::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_version_req_never_panics() {
+        let max = SemanticVersion::new(u64::MAX, u64::MAX, u64::MAX);
+        let cases = [
+            ("^18446744073709551615", true),
+            ("~18446744073709551615", true),
+            ("~1.18446744073709551615", false),
+            ("~18446744073709551615.18446744073709551615", true),
+            ("^0.0.18446744073709551615", false),
+        ];
+        for (req, expected) in cases {
+            assert_eq!(
+                VersionReq::parse(req).unwrap().matches(&max),
+                expected,
+                "{}",
+                req
+            );
+        }
+        for invalid in [
+            "^18446744073709551616",
+            ">=1.2.99999999999999999999",
+            "^1.2.3-é",
+            "~ 1 || ü",
+        ] {
+            assert!(VersionReq::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_version_req_explains_each_alternative() {
+        let explain = |req: &str, version: &str| {
+            VersionReq::parse(req)
+                .unwrap()
+                .explain(&SemanticVersion::try_from(version).unwrap())
+        };
+
+        let explanation = explain("^1.2 || >=3", "v2.0.0");
+        assert!(!explanation.satisfied);
+        assert_eq!(
+            explanation.to_string(),
+            "`^1.2` is not satisfied: `v2.0.0` is not below `2.0.0`\n\
+             `>=3` is not satisfied: `>=3` fails, major `2` < `3`"
+        );
+
+        let explanation = explain(">=1.0, <2 || 1.4.*", "v1.4.2");
+        assert!(explanation.satisfied);
+        assert_eq!(
+            explanation.to_string(),
+            "`>=1.0, <2` is satisfied\n`1.4.*` is satisfied"
+        );
+
+        assert_eq!(
+            explain("=2.0.0-rc.2", "v2.0.0-rc.1").to_string(),
+            "`=2.0.0-rc.2` is not satisfied: `=2.0.0-rc.2` fails, prerelease `1` < `2` at identifier 2"
+        );
+        assert_eq!(
+            explain(">=1.0.0", "v2.0.0-rc.1").to_string(),
+            "`>=1.0.0` is not satisfied: no comparator names `v2.0.0` with a prerelease"
+        );
+    }
+}
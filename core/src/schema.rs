@@ -0,0 +1,492 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    comment_parser::{DEFAULT_TYPES, REVERT_TYPE, SECURITY_TYPE},
+    BumpLevel, Config, ScopePresence, SemVerError, DEFAULT_GITMOJI,
+};
+
+/// [`Conventions`] describes the commits a project accepts: its types, scopes and lint rules,
+/// e.g. for editor extensions to complete commit messages.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Conventions {
+    pub types: Vec<TypeConvention>,
+    /// Accepted scopes, any when empty.
+    pub scopes: Vec<String>,
+    pub rules: Vec<LintRule>,
+    /// Set when comments must follow Conventional Commits 1.0.0 exactly.
+    pub strict_spec: bool,
+    /// Regex matching a ticket, e.g. `PROJ-\d+`.
+    pub ticket_pattern: Option<String>,
+    /// Set when commits must reference a ticket.
+    pub ticket_required: bool,
+}
+
+/// [`TypeConvention`] is an accepted type with the bump it implies and its other spellings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeConvention {
+    pub name: String,
+    /// Bump of a non breaking commit of the type, before the `[policy]` rules.
+    pub bump: BumpLevel,
+    pub aliases: Vec<String>,
+    /// Gitmoji standing for the type, when `[gitmoji]` is enabled.
+    pub gitmoji: Vec<String>,
+}
+
+/// [`LintRule`] is a rule checked by `semver lint`, named after its commitlint equivalent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintRule {
+    pub name: &'static str,
+    /// `error` or `warning`, see [`crate::LintConfig::warnings`].
+    pub level: &'static str,
+    pub value: Value,
+}
+
+impl Conventions {
+    /// [`as_json_string`] returns json representation of the conventions.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+impl Config {
+    /// [`conventions`] describes the commits accepted by the configuration.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str(r#"
+    /// types = ["feat", "fix", "docs"]
+    /// aliases = { feature = "feat" }
+    ///
+    /// [lint]
+    /// scopes = ["api"]
+    /// header_max_length = 72
+    /// "#).unwrap();
+    /// let conventions = config.conventions();
+    ///
+    /// assert_eq!(conventions.types[0].name, "feat");
+    /// assert_eq!(conventions.types[0].bump, BumpLevel::Minor);
+    /// assert_eq!(conventions.types[0].aliases, vec!["feature"]);
+    /// assert_eq!(conventions.scopes, vec!["api"]);
+    /// assert_eq!(conventions.rules[2].name, "header-max-length");
+    /// ```
+    pub fn conventions(&self) -> Conventions {
+        let mut names = match self.types.is_empty() {
+            true => DEFAULT_TYPES.iter().map(|t| t.to_string()).collect(),
+            false => self.types.clone(),
+        };
+        // accepted whatever the configured types
+        for name in [REVERT_TYPE, SECURITY_TYPE] {
+            if !names.iter().any(|t| t == name) {
+                names.push(name.to_string());
+            }
+        }
+
+        let parser = self.comment_parser();
+        let gitmoji = match self.gitmoji.enabled {
+            true => DEFAULT_GITMOJI
+                .iter()
+                .filter(|(emoji, _)| !self.gitmoji.types.contains_key(*emoji))
+                .map(|(emoji, semantic_type)| (emoji.to_string(), semantic_type.to_string()))
+                .chain(self.gitmoji.types.clone())
+                .collect(),
+            false => vec![],
+        };
+        let types = names
+            .iter()
+            .map(|name| TypeConvention {
+                name: name.clone(),
+                bump: match name.as_str() {
+                    // a revert reads the reverted comment, e.g. `revert: feat: export.`
+                    REVERT_TYPE => BumpLevel::Patch,
+                    _ => parser
+                        .parse(&format!("{}: x", name))
+                        .map(|comment| comment.semantic_type.default_bump())
+                        .unwrap_or_default(),
+                },
+                aliases: self
+                    .aliases
+                    .iter()
+                    .filter(|(_, semantic_type)| semantic_type.eq_ignore_ascii_case(name))
+                    .map(|(alias, _)| alias.clone())
+                    .collect(),
+                gitmoji: gitmoji
+                    .iter()
+                    .filter(|(_, semantic_type)| semantic_type.trim_end_matches('!') == name)
+                    .map(|(emoji, _)| emoji.clone())
+                    .collect(),
+            })
+            .collect();
+
+        let scopes = self
+            .allowed_scopes()
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let level = |name: &str| match self.lint.warnings.iter().any(|rule| rule == name) {
+            true => "warning",
+            false => "error",
+        };
+        let mut rules = vec![LintRule {
+            name: "type-enum",
+            level: "error",
+            value: json!(names),
+        }];
+        if !scopes.is_empty() {
+            rules.push(LintRule {
+                name: "scope-enum",
+                level: level("scope-enum"),
+                value: json!(scopes),
+            });
+        }
+        if self.lint.scope != ScopePresence::Optional || !self.lint.scope_by_type.is_empty() {
+            rules.push(LintRule {
+                name: "scope-empty",
+                level: level("scope-empty"),
+                value: json!(names
+                    .iter()
+                    .map(|name| (name, self.lint.scope_presence(name)))
+                    .collect::<BTreeMap<_, _>>()),
+            });
+        }
+        for (name, max) in [
+            ("subject-max-length", self.lint.subject_max_length),
+            ("header-max-length", self.lint.header_max_length),
+            ("body-max-line-length", self.lint.body_max_line_length),
+        ] {
+            if let Some(max) = max {
+                rules.push(LintRule {
+                    name,
+                    level: level(name),
+                    value: json!(max),
+                });
+            }
+        }
+        let toggles = [
+            (
+                "subject-case",
+                self.lint.subject_case.map(|case| json!(case)),
+            ),
+            (
+                "subject-full-stop",
+                self.lint
+                    .subject_full_stop
+                    .map(|full_stop| json!(full_stop)),
+            ),
+            (
+                "subject-leading-whitespace",
+                Some(json!(true)).filter(|_| self.lint.subject_no_leading_whitespace),
+            ),
+            (
+                "subject-imperative",
+                Some(json!(true)).filter(|_| self.lint.subject_imperative),
+            ),
+            (
+                "subject-blocklist",
+                Some(json!(self.lint.subject_blocklist))
+                    .filter(|_| !self.lint.subject_blocklist.is_empty()),
+            ),
+        ];
+        for (name, value) in toggles {
+            if let Some(value) = value {
+                rules.push(LintRule {
+                    name,
+                    level: level(name),
+                    value,
+                });
+            }
+        }
+
+        Conventions {
+            types,
+            scopes,
+            rules,
+            strict_spec: self.strict_spec,
+            ticket_pattern: self.tickets.pattern.clone(),
+            ticket_required: self.tickets.required,
+        }
+    }
+}
+
+/// [`config_schema`] returns the JSON Schema of `semver.toml`, e.g. for editors to validate and
+/// complete it through taplo or the Even Better TOML extension.
+pub fn config_schema() -> Value {
+    let string = json!({ "type": "string" });
+    let boolean = json!({ "type": "boolean" });
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let length = json!({ "type": "integer", "minimum": 0 });
+    let bump = json!({ "enum": ["none", "patch", "minor", "major"] });
+    let described = |schema: &Value, description: &str| {
+        let mut schema = schema.clone();
+        schema["description"] = json!(description);
+        schema
+    };
+    let table = |properties: Value| json!({ "type": "object", "properties": properties, "additionalProperties": false });
+
+    let files = json!({
+        "description": "Version files updated by `semver bump`.",
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "path": string,
+                "pattern": described(&string, "Regex whose `version` named group, or first group, holds the version."),
+            },
+            "required": ["path"],
+            "additionalProperties": false,
+        },
+    });
+    let policy = table(json!({
+        "rules": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "when": described(&string, "Condition, e.g. `type in [docs, chore]`."),
+                    "bump": bump,
+                },
+                "required": ["when", "bump"],
+                "additionalProperties": false,
+            },
+        },
+        "examples": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": { "commit": string, "branch": string, "expect": bump },
+                "required": ["commit", "expect"],
+                "additionalProperties": false,
+            },
+        },
+    }));
+    let packages = json!({
+        "description": "Packages of a monorepo, by path globs or as a table.",
+        "type": "object",
+        "additionalProperties": {
+            "oneOf": [
+                strings,
+                {
+                    "type": "object",
+                    "properties": {
+                        "paths": strings,
+                        "depends_on": strings,
+                        "tag_prefix": described(&string, "Prefix of the tags, `<name>-` by default."),
+                    },
+                    "required": ["paths"],
+                    "additionalProperties": false,
+                },
+            ],
+        },
+    });
+    let history = table(json!({
+        "skip_merges": { "type": "boolean", "default": true },
+        "bots": described(&strings, "Authors of bot commits, e.g. `dependabot`."),
+        "bot_type": described(&string, "Type given to bot commits that are not conventional."),
+        "skip_markers": {
+            "description": "Markers excluding a commit from releases.",
+            "type": "array",
+            "items": string,
+            "default": ["[skip release]", "Release-Note: none"],
+        },
+    }));
+    let presence = json!({ "enum": ["optional", "required", "forbidden"] });
+    let lint = table(json!({
+        "scopes": described(&strings, "Accepted scopes (`scope-enum`)."),
+        "subject_max_length": described(&length, "Maximum number of characters of the subject (`subject-max-length`)."),
+        "header_max_length": described(&length, "Maximum number of characters of the first line (`header-max-length`)."),
+        "body_max_line_length": described(&length, "Maximum number of characters of the body lines (`body-max-line-length`)."),
+        "warnings": {
+            "description": "Rules reported as warnings rather than failures.",
+            "type": "array",
+            "items": {
+                "enum": [
+                    "scope-enum",
+                    "scope-empty",
+                    "subject-max-length",
+                    "header-max-length",
+                    "body-max-line-length",
+                    "subject-case",
+                    "subject-leading-whitespace",
+                    "subject-imperative",
+                    "subject-blocklist",
+                    "subject-full-stop",
+                ],
+            },
+        },
+        "scope": {
+            "description": "Whether the comments carry a scope (`scope-empty`).",
+            "enum": ["optional", "required", "forbidden"],
+            "default": "optional",
+        },
+        "scope_by_type": {
+            "description": "Overrides of `scope` for some types, e.g. `{ chore = \"forbidden\" }`.",
+            "type": "object",
+            "additionalProperties": presence,
+        },
+        "subject_case": {
+            "description": "Case of the first letter of the subject (`subject-case`).",
+            "enum": ["lower", "sentence"],
+        },
+        "subject_no_leading_whitespace": described(&boolean, "Rejects the subjects separated from the type by more than one space (`subject-leading-whitespace`)."),
+        "subject_imperative": described(&boolean, "Rejects the subjects starting with e.g. `added` or `fixes` (`subject-imperative`)."),
+        "subject_blocklist": described(&strings, "Words the subjects must not hold (`subject-blocklist`)."),
+        "subject_full_stop": described(&boolean, "Requires, or with `false` forbids, a period at the end of the subjects (`subject-full-stop`)."),
+    }));
+    let links = table(json!({
+        "enabled": { "type": "boolean", "default": true },
+        "repository": described(&string, "Web url of the repository, read from the `origin` remote when unset."),
+        "kind": { "enum": ["github", "gitlab", "gitea"] },
+        "commit": described(&string, "Template of the commit pages, with `{sha}`."),
+        "compare": described(&string, "Template of the pages comparing two tags."),
+        "issue": described(&string, "Template of the issue pages."),
+        "pull_request": described(&string, "Template of the pull and merge request pages."),
+    }));
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "semver.toml",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "files": files,
+            "refactor_keyword": {
+                "description": "Spelling of refactorings when rendering comments.",
+                "enum": ["refactor", "refact"],
+                "default": "refactor",
+            },
+            "types": described(&strings, "Accepted semantic types, defaults to `feat`, `fix` and `refactor`."),
+            "policy": policy,
+            "changelog": described(&string, "Changelog updated by `semver release`, e.g. `CHANGELOG.md`."),
+            "changelog_contributors": described(&boolean, "Ends the changelog sections with the authors of the release."),
+            "contributor_handles": described(&boolean, "Resolves the GitHub handles of the contributors, with `$GITHUB_TOKEN`."),
+            "changelog_layout": {
+                "description": "Grouping of the changelog entries.",
+                "enum": ["type", "scope", "package"],
+                "default": "type",
+            },
+            "packages": packages,
+            "versioning": {
+                "description": "Whether the packages are versioned independently or share one version.",
+                "enum": ["independent", "lockstep"],
+                "default": "independent",
+            },
+            "expand_squash_merges": described(&boolean, "Parses the bullet lines of squash merge bodies as separate comments."),
+            "history": history,
+            "tickets": table(json!({
+                "pattern": described(&string, "Regex matching a ticket, e.g. `PROJ-\\d+`."),
+                "required": described(&boolean, "Makes `semver lint` reject commits without any ticket."),
+            })),
+            "gitmoji": table(json!({
+                "enabled": boolean,
+                "types": {
+                    "description": "Emoji and the type they stand for, a trailing `!` marking a breaking change.",
+                    "type": "object",
+                    "additionalProperties": string,
+                },
+            })),
+            "initial_version": {
+                "description": "Version of the first release, when no version is tagged yet.",
+                "type": "string",
+                "default": "v0.1.0",
+            },
+            "strict_spec": described(&boolean, "Enforces Conventional Commits 1.0.0 exactly."),
+            "aliases": {
+                "description": "Other spellings of the types, e.g. `feature = \"feat\"`.",
+                "type": "object",
+                "additionalProperties": string,
+            },
+            "channels": {
+                "description": "Prerelease channels of the branches, e.g. `next = \"rc\"`.",
+                "type": "object",
+                "additionalProperties": string,
+            },
+            "snapshot_template": described(&string, "Template of `semver next --snapshot`."),
+            "release_commit_template": {
+                "description": "Template of the release commit messages, with `{version}` and `{tag}`.",
+                "type": "string",
+                "default": "chore(release): v{version} [skip ci]",
+            },
+            "scheme": {
+                "description": "Versioning scheme.",
+                "enum": ["semver", "calver"],
+                "default": "semver",
+            },
+            "calver_format": {
+                "description": "Format of the calendar versions.",
+                "type": "string",
+                "default": "YYYY.MM.MICRO",
+            },
+            "tag_format": {
+                "description": "Template of the release tags.",
+                "type": "string",
+                "default": "v{major}.{minor}.{patch}{-pre}{+build}",
+            },
+            "lenient_versions": described(&boolean, "Reads the tags missing their patch, or minor and patch components."),
+            "version_components": {
+                "description": "Component count of the release tags.",
+                "enum": ["normalize", "preserve"],
+                "default": "normalize",
+            },
+            "lint": lint,
+            "scopes": {
+                "description": "Scopes and the path globs they are inferred from.",
+                "type": "object",
+                "additionalProperties": strings,
+            },
+            "links": links,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns the fields `Config::from_toml_str` expects in `section`, listed by the error on an
+    /// unknown one.
+    fn expected_fields(section: Option<&str>) -> Vec<String> {
+        let toml = match section {
+            Some(section) => format!("[{}]\nunknown_field = 0\n", section),
+            None => "unknown_field = 0\n".to_string(),
+        };
+        let error = Config::from_toml_str(&toml).unwrap_err().to_string();
+        let (_, expected) = error.rsplit_once("expected ").unwrap();
+        // every other piece is quoted, e.g. "one of `a`, `b`" or "`a` or `b`"
+        let mut fields = expected
+            .split('`')
+            .skip(1)
+            .step_by(2)
+            .map(String::from)
+            .collect::<Vec<_>>();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn test_config_schema_lists_every_field_of_the_config() {
+        let schema = config_schema();
+        let properties = |schema: &Value| {
+            let mut keys = schema["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+
+        assert_eq!(properties(&schema), expected_fields(None));
+        for section in ["policy", "history", "tickets", "gitmoji", "lint", "links"] {
+            assert_eq!(
+                properties(&schema["properties"][section]),
+                expected_fields(Some(section)),
+                "{}",
+                section
+            );
+        }
+    }
+}
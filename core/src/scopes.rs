@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::{packages::glob_to_regex, Config, SemVerError};
+
+/// [`ScopeMap`] maps path globs to the scopes of the `[scopes]` section, so that a scope is
+/// inferred from the paths a commit touches.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let config = Config::from_toml_str(r#"
+/// [scopes]
+/// api = ["services/api/**", "proto/*.proto"]
+/// web = ["web"]
+/// "#).unwrap();
+/// let scopes = config.scope_map().unwrap();
+///
+/// assert_eq!(scopes.infer(&["services/api/src/main.rs", "web/index.ts", "proto/api.proto"]), Some("api"));
+/// assert_eq!(scopes.infer(&["README.md"]), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScopeMap {
+    scopes: Vec<(String, Vec<Regex>)>,
+}
+
+impl ScopeMap {
+    /// [`new`] compiles the path globs of each scope, with the syntax of [`crate::Package::new`].
+    pub fn new<'a, I, S>(scopes: I) -> Result<Self, SemVerError>
+    where
+        I: IntoIterator<Item = (&'a str, &'a [S])>,
+        S: AsRef<str> + 'a,
+    {
+        let scopes = scopes
+            .into_iter()
+            .map(|(scope, globs)| {
+                let globs = globs
+                    .iter()
+                    .map(|glob| {
+                        Regex::new(&glob_to_regex(glob.as_ref())).map_err(|e| {
+                            SemVerError::InvalidConfig(format!("glob `{}`: {}", glob.as_ref(), e))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((scope.to_string(), globs))
+            })
+            .collect::<Result<_, SemVerError>>()?;
+
+        Ok(Self { scopes })
+    }
+
+    /// Returns the scopes `path`, relative to the repository root, belongs to.
+    pub fn scopes_of(&self, path: impl AsRef<Path>) -> impl Iterator<Item = &str> {
+        let path = path.as_ref().to_string_lossy().replace('\\', "/");
+
+        self.scopes
+            .iter()
+            .filter(move |(_, globs)| globs.iter().any(|glob| glob.is_match(&path)))
+            .map(|(scope, _)| scope.as_str())
+    }
+
+    /// [`infer`] returns the scope of the most `paths`, the first one on a tie (alphabetically
+    /// for the `[scopes]` section), or `None` when no path belongs to a scope. Paths outside
+    /// every scope are left out.
+    pub fn infer<P: AsRef<Path>>(&self, paths: &[P]) -> Option<&str> {
+        let mut counts = self
+            .scopes
+            .iter()
+            .map(|(scope, _)| (scope.as_str(), 0))
+            .collect::<Vec<_>>();
+        for path in paths {
+            for scope in self.scopes_of(path) {
+                if let Some((_, count)) = counts.iter_mut().find(|(s, _)| *s == scope) {
+                    *count += 1;
+                }
+            }
+        }
+
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map(|(scope, _)| scope)
+    }
+}
+
+impl Config {
+    /// [`scope_map`] compiles the `[scopes]` section.
+    pub fn scope_map(&self) -> Result<ScopeMap, SemVerError> {
+        ScopeMap::new(
+            self.scopes
+                .iter()
+                .map(|(scope, globs)| (scope.as_str(), globs.as_slice())),
+        )
+    }
+
+    /// [`allowed_scopes`] returns the scopes accepted by the `scope-enum` rule: the `[lint]
+    /// scopes` and the scopes of the `[scopes]` section, any when both are empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_toml_str(r#"
+    /// [lint]
+    /// scopes = ["deps"]
+    ///
+    /// [scopes]
+    /// api = ["services/api"]
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(config.allowed_scopes(), vec!["deps", "api"]);
+    /// assert!(config.lint("fix(api): a fix.").is_ok());
+    /// assert!(config.lint("fix(web): a fix.").is_err());
+    /// ```
+    pub fn allowed_scopes(&self) -> Vec<&str> {
+        let mut scopes = self
+            .lint
+            .scopes
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        for scope in self.scopes.keys() {
+            if !scopes.contains(&scope.as_str()) {
+                scopes.push(scope);
+            }
+        }
+
+        scopes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_infer_prefers_the_scope_of_most_paths_then_the_first_declared() {
+        let scopes = ScopeMap::new([
+            ("api", &["services/api"][..]),
+            ("web", &["web/**/*.ts"][..]),
+            ("shared", &["services/**"][..]),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            scopes.infer(&["web/a.ts", "web/b/c.ts", "services/api/lib.rs"]),
+            Some("web")
+        );
+        assert_eq!(scopes.infer(&["services/api/lib.rs"]), Some("api"));
+        assert_eq!(scopes.infer(&["services/db/lib.rs"]), Some("shared"));
+        assert_eq!(scopes.infer(&[] as &[&str]), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::{fs, path::Path};
+
+use serde_json::Value;
+
+use crate::{
+    comment_parser::CONVENTIONAL_TYPES, BumpLevel, Config, PolicyRule, SemVerError, STABLE_CHANNEL,
+};
+
+/// semantic-release configuration files read by [`Config::discover`] when there is no
+/// `semver.toml`. `release.config.js` is only read when it exports a plain object.
+pub const SEMANTIC_RELEASE_FILE_NAMES: [&str; 3] =
+    [".releaserc.json", ".releaserc", "release.config.js"];
+
+impl Config {
+    /// [`from_semantic_release_str`] maps a semantic-release configuration, in its JSON, YAML or
+    /// `module.exports = {...}` form, onto a configuration: `tagFormat` to the tag format, the
+    /// prerelease `branches` to channels and, when the commit analyzer is configured, its
+    /// `releaseRules` to policy rules, accepting the conventional types and the preset `types`.
+    /// Other settings are left out.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let config = Config::from_semantic_release_str(r#"{
+    ///   "tagFormat": "release-${version}",
+    ///   "branches": ["main", { "name": "beta", "prerelease": true }]
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     config.tag_format.as_deref(),
+    ///     Some("release-{major}.{minor}.{patch}{-pre}{+build}")
+    /// );
+    /// assert_eq!(config.channel(Some("beta")), Some("beta"));
+    /// assert_eq!(config.channel(Some("main")), None);
+    /// ```
+    pub fn from_semantic_release_str(contents: &str) -> Result<Self, SemVerError> {
+        let contents = contents.trim();
+        let contents = contents
+            .strip_prefix("module.exports")
+            .and_then(|rest| rest.trim_start().strip_prefix('='))
+            .map(|rest| rest.trim().trim_end_matches(';'))
+            .unwrap_or(contents);
+        // YAML is a superset of JSON and accepts the unquoted keys of JavaScript objects
+        let release: Value = serde_yaml::from_str(contents)
+            .map_err(|e| SemVerError::InvalidConfig(format!("semantic-release: {}", e)))?;
+        let mut config = Config::default();
+
+        if let Some(tag_format) = release.get("tagFormat").and_then(Value::as_str) {
+            config.tag_format =
+                Some(tag_format.replace("${version}", "{major}.{minor}.{patch}{-pre}{+build}"));
+        }
+
+        for branch in release
+            .get("branches")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let (name, channel) = match branch {
+                Value::String(name) => (name.as_str(), STABLE_CHANNEL),
+                branch => {
+                    let Some(name) = branch.get("name").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    match branch.get("prerelease") {
+                        Some(Value::String(channel)) => (name, channel.as_str()),
+                        Some(Value::Bool(true)) => (name, name),
+                        _ => (name, STABLE_CHANNEL),
+                    }
+                }
+            };
+            config
+                .channels
+                .insert(name.to_string(), channel.to_string());
+        }
+
+        for (plugin, options) in plugins(&release) {
+            if plugin != "@semantic-release/commit-analyzer" {
+                continue;
+            }
+            config.types = CONVENTIONAL_TYPES.iter().map(|t| t.to_string()).collect();
+            let preset_types = options
+                .pointer("/presetConfig/types")
+                .and_then(Value::as_array);
+            for commit_type in preset_types.into_iter().flatten() {
+                if let Some(name) = commit_type.get("type").and_then(Value::as_str) {
+                    add_type(&mut config, name);
+                }
+            }
+
+            let rules = options.get("releaseRules").and_then(Value::as_array);
+            for rule in rules.into_iter().flatten() {
+                if let Some(rule) = policy_rule(rule) {
+                    config.policy.rules.push(rule);
+                }
+                if let Some(name) = rule.get("type").and_then(Value::as_str) {
+                    add_type(&mut config, name);
+                }
+            }
+        }
+        config.tag_format()?;
+        config.bump_policy()?;
+
+        Ok(config)
+    }
+
+    /// [`load_semantic_release`] reads the semantic-release configuration file at `path`,
+    /// see [`Config::from_semantic_release_str`].
+    pub fn load_semantic_release(path: impl AsRef<Path>) -> Result<Self, SemVerError> {
+        Self::from_semantic_release_str(&fs::read_to_string(path)?)
+    }
+}
+
+/// Plugins given by name or as `[name, options]`.
+fn plugins(release: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    let plugins = release.get("plugins").and_then(Value::as_array);
+    plugins
+        .into_iter()
+        .flatten()
+        .filter_map(|plugin| match plugin {
+            Value::String(name) => Some((name.as_str(), &Value::Null)),
+            Value::Array(entry) => match entry.as_slice() {
+                [Value::String(name), options, ..] => Some((name.as_str(), options)),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+fn add_type(config: &mut Config, name: &str) {
+    if !config.types.iter().any(|t| t == name) {
+        config.types.push(name.to_string());
+    }
+}
+
+/// Maps a release rule, e.g. `{ "type": "docs", "scope": "readme", "release": "patch" }`.
+fn policy_rule(rule: &Value) -> Option<PolicyRule> {
+    let bump = match rule.get("release")? {
+        Value::Bool(false) => BumpLevel::None,
+        Value::String(release) => match release.as_str() {
+            "major" => BumpLevel::Major,
+            "minor" => BumpLevel::Minor,
+            "patch" => BumpLevel::Patch,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let mut conditions = Vec::new();
+    for field in ["type", "scope"] {
+        match rule.get(field) {
+            Some(Value::String(value)) => conditions.push(format!("{} == {:?}", field, value)),
+            Some(_) => return None,
+            None => {}
+        }
+    }
+    match rule.get("breaking") {
+        Some(Value::Bool(true)) => conditions.push("breaking".to_string()),
+        Some(Value::Bool(false)) | None => conditions.push("!breaking".to_string()),
+        Some(_) => return None,
+    }
+
+    Some(PolicyRule {
+        when: conditions.join(" && "),
+        bump,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Commit;
+
+    use super::*;
+
+    #[test]
+    fn test_config_from_release_config_js_maps_the_release_rules() {
+        let config = Config::from_semantic_release_str(
+            r#"module.exports = {
+                branches: ['main', { name: 'next', prerelease: 'rc' }],
+                plugins: [
+                    ['@semantic-release/commit-analyzer', {
+                        preset: 'conventionalcommits',
+                        releaseRules: [
+                            { type: 'docs', scope: 'readme', release: 'patch' },
+                            { type: 'refactor', release: false }
+                        ]
+                    }],
+                    '@semantic-release/github'
+                ]
+            };"#,
+        )
+        .unwrap();
+        let bump = |message| {
+            let commits = vec![Commit::new("a1", message)];
+            config.analyze(&commits, None).unwrap().bump
+        };
+
+        assert_eq!(config.channel(Some("next")), Some("rc"));
+        assert!(config.types.contains(&"docs".to_string()));
+        assert_eq!(
+            bump("docs(readme): fix the install section."),
+            BumpLevel::Patch
+        );
+        assert_eq!(bump("refactor: split the parser."), BumpLevel::None);
+        assert_eq!(bump("feat: add a command."), BumpLevel::Minor);
+    }
+}
@@ -0,0 +1,89 @@
+//! Conversions with the types of the `semver` crate, behind the `semver` feature.
+
+use crate::{SemVerError, SemanticVersion, VersionReq};
+
+/// Converts to a [`semver::Version`], failing on identifiers the `semver` crate rejects,
+/// e.g. numeric prerelease identifiers with leading zeros.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let version = semver::Version::try_from(SemanticVersion::try_from("v1.2.3-rc.1").unwrap()).unwrap();
+///
+/// assert_eq!(version.to_string(), "1.2.3-rc.1");
+/// ```
+impl TryFrom<SemanticVersion> for semver::Version {
+    type Error = SemVerError;
+
+    fn try_from(version: SemanticVersion) -> Result<Self, Self::Error> {
+        let invalid = || SemVerError::InvalidVersionFormat(String::from(version.clone()));
+
+        Ok(semver::Version {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre: match &version.pre {
+                Some(pre) => semver::Prerelease::new(pre).map_err(|_| invalid())?,
+                None => semver::Prerelease::EMPTY,
+            },
+            build: match &version.build {
+                Some(build) => semver::BuildMetadata::new(build).map_err(|_| invalid())?,
+                None => semver::BuildMetadata::EMPTY,
+            },
+        })
+    }
+}
+
+/// Converts from a [`semver::Version`], whose components are `u64` as well.
+impl From<semver::Version> for SemanticVersion {
+    fn from(version: semver::Version) -> Self {
+        let identifiers = |value: &str| (!value.is_empty()).then(|| value.to_string());
+
+        SemanticVersion {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre: identifiers(version.pre.as_str()),
+            build: identifiers(version.build.as_str()),
+        }
+    }
+}
+
+/// Converts to a [`semver::VersionReq`], failing on what the `semver` crate does not support,
+/// e.g. `||` alternatives.
+impl TryFrom<VersionReq> for semver::VersionReq {
+    type Error = SemVerError;
+
+    fn try_from(req: VersionReq) -> Result<Self, Self::Error> {
+        semver::VersionReq::parse(&req.to_string())
+            .map_err(|_| SemVerError::InvalidRequirement(req.to_string()))
+    }
+}
+
+/// Converts from a [`semver::VersionReq`].
+impl TryFrom<semver::VersionReq> for VersionReq {
+    type Error = SemVerError;
+
+    fn try_from(req: semver::VersionReq) -> Result<Self, Self::Error> {
+        VersionReq::parse(&req.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_conversions_round_trip_with_the_semver_crate() {
+        let version = SemanticVersion::try_from("v2.0.0-rc.1+sha.abc1234").unwrap();
+        let converted = semver::Version::try_from(version.clone()).unwrap();
+        assert_eq!(SemanticVersion::from(converted), version);
+
+        let req = semver::VersionReq::try_from(VersionReq::parse(">=1.2, <2").unwrap()).unwrap();
+        assert!(req.matches(&semver::Version::new(1, 4, 0)));
+        assert!(VersionReq::try_from(req)
+            .unwrap()
+            .matches(&SemanticVersion::new(1, 4, 0)));
+        assert!(semver::VersionReq::try_from(VersionReq::parse("^1 || ^2").unwrap()).is_err());
+    }
+}
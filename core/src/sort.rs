@@ -0,0 +1,55 @@
+use crate::SemanticVersion;
+
+/// [`sort_versions`] parses the versions of `lines`, the `v` being optional, and sorts them by
+/// precedence, lowest first. Other lines, e.g. tags which are not versions, are left out, and
+/// every version keeps its original spelling.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let versions = sort_versions("v1.10.0\nlatest\n1.2.0\nv1.10.0-rc.1\n".lines());
+///
+/// assert_eq!(
+///     versions.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+///     vec!["1.2.0", "v1.10.0-rc.1", "v1.10.0"]
+/// );
+/// ```
+pub fn sort_versions<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Vec<(&'a str, SemanticVersion)> {
+    let mut versions = lines
+        .into_iter()
+        .map(str::trim)
+        .filter_map(|line| {
+            let version = format!("v{}", line.strip_prefix('v').unwrap_or(line));
+            Some((line, SemanticVersion::try_from(version.as_str()).ok()?))
+        })
+        .collect::<Vec<_>>();
+    versions.sort_by(|(_, left), (_, right)| left.cmp(right));
+
+    versions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sort_versions_orders_by_precedence_and_keeps_the_spelling() {
+        let lines = "v1.10.0\n  latest\n1.2.0\nv1.10.0-rc.1\n\nv1.10.0-rc.1+sha.a\nv1.9.0\nv1.x\n";
+        let versions = sort_versions(lines.lines());
+
+        assert_eq!(
+            versions.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+            vec![
+                "1.2.0",
+                "v1.9.0",
+                "v1.10.0-rc.1",
+                "v1.10.0-rc.1+sha.a",
+                "v1.10.0"
+            ]
+        );
+        assert_eq!(versions[0].1, SemanticVersion::new(1, 2, 0));
+        assert!(sort_versions(["latest", ""]).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{split_releases, Commit, Config, SemVerError, Tag};
+
+/// Number of scopes listed by [`Stats::top_scopes`].
+const TOP_SCOPES: usize = 5;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// [`Stats`] summarizes the history of a repository, see [`Config::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Stats {
+    pub commits: usize,
+    /// Number of parsed comments by type, e.g. `feat`.
+    pub types: BTreeMap<String, usize>,
+    /// Most used scopes with their number of comments, most used first.
+    pub top_scopes: Vec<(String, usize)>,
+    /// Number of semantic version tags in the history.
+    pub releases: usize,
+    /// Average number of commits shipped by a release, unreleased commits left out.
+    pub average_commits_per_release: Option<f64>,
+    /// Average number of days between two consecutive releases.
+    pub average_days_between_releases: Option<f64>,
+}
+
+impl Config {
+    /// [`stats`] summarizes `history` (oldest first) released by `tags`: commit frequency by
+    /// type, top scopes and release cadence.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let history = vec![
+    ///     Commit::new("a1", "feat(api): a.").with_timestamp(0),
+    ///     Commit::new("b2", "fix(api): b.").with_timestamp(86_400),
+    ///     Commit::new("c3", "feat(web): c.").with_timestamp(4 * 86_400),
+    /// ];
+    /// let tags = vec![Tag::new("v0.1.0", "a1"), Tag::new("v0.2.0", "c3")];
+    ///
+    /// let stats = Config::default().stats(&history, &tags).unwrap();
+    /// assert_eq!(stats.types["feat"], 2);
+    /// assert_eq!(stats.top_scopes[0], ("api".to_string(), 2));
+    /// assert_eq!(stats.average_commits_per_release, Some(1.5));
+    /// assert_eq!(stats.average_days_between_releases, Some(4.0));
+    /// ```
+    pub fn stats(&self, history: &[Commit], tags: &[Tag]) -> Result<Stats, SemVerError> {
+        let analysis = self.analyze(history, None)?;
+
+        let mut top_scopes = analysis.scopes.into_iter().collect::<Vec<_>>();
+        top_scopes.sort_by(|(l_scope, l_count), (r_scope, r_count)| {
+            r_count.cmp(l_count).then(l_scope.cmp(r_scope))
+        });
+        top_scopes.truncate(TOP_SCOPES);
+
+        let releases = split_releases(history, tags)
+            .into_iter()
+            .filter(|release| release.version.is_some())
+            .collect::<Vec<_>>();
+        let released = releases.iter().map(|r| r.commits.len()).sum::<usize>();
+        let dates = releases
+            .iter()
+            .filter_map(|release| release.commits.last()?.timestamp)
+            .collect::<Vec<_>>();
+
+        Ok(Stats {
+            commits: analysis.commits,
+            types: analysis.types,
+            top_scopes,
+            releases: releases.len(),
+            average_commits_per_release: match releases.is_empty() {
+                true => None,
+                false => Some(released as f64 / releases.len() as f64),
+            },
+            average_days_between_releases: match dates.len() {
+                0 | 1 => None,
+                n => Some((dates[n - 1] - dates[0]) as f64 / SECONDS_PER_DAY / (n - 1) as f64),
+            },
+        })
+    }
+}
+
+impl Stats {
+    /// [`report`] renders the statistics as a plain text table.
+    pub fn report(&self) -> String {
+        let average = |value: Option<f64>| match value {
+            Some(value) => format!("{:.1}", value),
+            None => "-".to_string(),
+        };
+        let mut report = format!(
+            "commits: {}\nreleases: {}\ncommits per release: {}\ndays between releases: {}\n",
+            self.commits,
+            self.releases,
+            average(self.average_commits_per_release),
+            average(self.average_days_between_releases)
+        );
+        if !self.types.is_empty() {
+            report.push_str("types:\n");
+        }
+        for (name, count) in &self.types {
+            report.push_str(&format!("  {:<12} {}\n", name, count));
+        }
+        if !self.top_scopes.is_empty() {
+            report.push_str("top scopes:\n");
+        }
+        for (name, count) in &self.top_scopes {
+            report.push_str(&format!("  {:<12} {}\n", name, count));
+        }
+
+        report
+    }
+
+    /// [`as_json_string`] returns json representation of the statistics.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
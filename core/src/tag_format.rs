@@ -0,0 +1,404 @@
+#![deny(
+    clippy::indexing_slicing,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic
+)]
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::LazyLock};
+
+use crate::models::{group, literal_regex};
+use crate::{SemVerError, SemanticVersion};
+
+/// Format of the release tags unless configured, see [`TagFormat`].
+pub const DEFAULT_TAG_FORMAT: &str = "v{major}.{minor}.{patch}{-pre}{+build}";
+
+/// [`TagFormat`] renders versions as tags and parses them back, following a template such as
+/// `v{major}.{minor}.{patch}{-pre}{+build}` or `release/{major}.{minor}.{patch}`.
+///
+/// `{-pre}` and `{+build}` render the prerelease and build metadata with their separator, or
+/// nothing; a template without them gets them appended.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let format = TagFormat::new("release/{major}.{minor}.{patch}").unwrap();
+/// let version = SemanticVersion::try_from("v1.4.0-rc.1").unwrap();
+///
+/// assert_eq!(format.render(&version), "release/1.4.0-rc.1");
+/// assert_eq!(format.parse("release/1.4.0-rc.1"), Some(version));
+/// assert_eq!(format.parse("v1.4.0"), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagFormat {
+    template: String,
+    pattern: Regex,
+    lenient: bool,
+}
+
+/// [`VersionComponents`] tells how many components the release tags are written with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionComponents {
+    /// Every tag holds the three components, e.g. `v1.3.0`.
+    #[default]
+    Normalize,
+    /// A tag holds as many components as the latest one when the dropped ones are zero,
+    /// e.g. `v1.3` follows `v1.2`, see [`TagFormat::lenient`].
+    Preserve,
+}
+
+const PLACEHOLDERS: [(&str, &str); 5] = [
+    ("{major}", r"(?P<major>[0-9]+)"),
+    ("{minor}", r"(?P<minor>[0-9]+)"),
+    ("{patch}", r"(?P<patch>[0-9]+)"),
+    (
+        "{-pre}",
+        r"(?:-(?P<pre>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?",
+    ),
+    (
+        "{+build}",
+        r"(?:\+(?P<build>[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?",
+    ),
+];
+
+/// Components a lenient format may leave out, along with their separator.
+const OPTIONAL_COMPONENTS: [(&str, &str); 2] = [
+    (".{patch}", r"(?:\.(?P<patch>[0-9]+))?"),
+    (".{minor}", r"(?:\.(?P<minor>[0-9]+))?"),
+];
+
+impl TagFormat {
+    /// [`new`] compiles `template`, which must hold `{major}`, `{minor}` and `{patch}`.
+    pub fn new(template: &str) -> Result<Self, SemVerError> {
+        Self::compile(template, false)
+    }
+
+    /// [`lenient`] returns the format also reading the tags missing their patch, or minor and
+    /// patch components, e.g. `v1.2` or `v1`, which stand for `v1.2.0` and `v1.0.0`. Only the
+    /// components following a `.` in the template may be missing.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let format = TagFormat::default().lenient().unwrap();
+    ///
+    /// assert_eq!(format.parse("v1.2"), Some(SemanticVersion::new(1, 2, 0)));
+    /// assert_eq!(format.parse("v1-rc.1"), SemanticVersion::try_from("v1.0.0-rc.1").ok());
+    /// assert_eq!(format.components("v1"), Some(1));
+    /// assert_eq!(TagFormat::default().parse("v1.2"), None);
+    /// ```
+    pub fn lenient(&self) -> Result<Self, SemVerError> {
+        Self::compile(&self.template, true)
+    }
+
+    fn compile(template: &str, lenient: bool) -> Result<Self, SemVerError> {
+        for required in ["{major}", "{minor}", "{patch}"] {
+            if template.matches(required).count() != 1 {
+                return Err(SemVerError::InvalidConfig(format!(
+                    "tag format `{}` must hold {} once",
+                    template, required
+                )));
+            }
+        }
+        let mut template = template.to_string();
+        for optional in ["{-pre}", "{+build}"] {
+            if !template.contains(optional) {
+                template.push_str(optional);
+            }
+        }
+
+        let pattern = Regex::new(&Self::pattern(&template, lenient))
+            .map_err(|e| SemVerError::InvalidConfig(e.to_string()))?;
+
+        Ok(TagFormat {
+            template,
+            pattern,
+            lenient,
+        })
+    }
+
+    /// Returns the regex source matching the tags of a complete `template`.
+    fn pattern(template: &str, lenient: bool) -> String {
+        let mut pattern = regex::escape(template);
+        if lenient {
+            for (component, group) in OPTIONAL_COMPONENTS {
+                pattern = pattern.replace(&regex::escape(component), group);
+            }
+        }
+        for (placeholder, group) in PLACEHOLDERS {
+            pattern = pattern.replace(&regex::escape(placeholder), group);
+        }
+        format!("^{}$", pattern)
+    }
+
+    /// [`with_prefix`] returns the format of the tags starting with `prefix`, e.g. the
+    /// `api-` tags of a package. It fails when `prefix` holds a placeholder.
+    pub fn with_prefix(&self, prefix: &str) -> Result<Self, SemVerError> {
+        Self::compile(&format!("{}{}", prefix, self.template), self.lenient)
+    }
+
+    /// [`render`] returns the tag of `version`.
+    pub fn render(&self, version: &SemanticVersion) -> String {
+        Self::render_template(&self.template, version)
+    }
+
+    /// [`render_components`] returns the tag of `version` with at most `components` numeric
+    /// components, leaving out the trailing ones when they are zero and follow a `.`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let format = TagFormat::default();
+    ///
+    /// assert_eq!(format.render_components(&SemanticVersion::new(1, 3, 0), 2), "v1.3");
+    /// assert_eq!(format.render_components(&SemanticVersion::new(2, 0, 0), 1), "v2");
+    /// assert_eq!(format.render_components(&SemanticVersion::new(1, 2, 1), 2), "v1.2.1");
+    /// ```
+    pub fn render_components(&self, version: &SemanticVersion, components: usize) -> String {
+        let mut template = self.template.clone();
+        let dropped = [(2, version.patch), (1, version.minor)];
+        for ((component, _), (kept, value)) in OPTIONAL_COMPONENTS.into_iter().zip(dropped) {
+            if components > kept || value != 0 || !template.contains(component) {
+                break;
+            }
+            template = template.replace(component, "");
+        }
+
+        Self::render_template(&template, version)
+    }
+
+    fn render_template(template: &str, version: &SemanticVersion) -> String {
+        let separated = |separator: &str, value: &Option<String>| match value {
+            Some(value) => format!("{}{}", separator, value),
+            None => String::new(),
+        };
+
+        template
+            .replace("{major}", &version.major.to_string())
+            .replace("{minor}", &version.minor.to_string())
+            .replace("{patch}", &version.patch.to_string())
+            .replace("{-pre}", &separated("-", &version.pre))
+            .replace("{+build}", &separated("+", &version.build))
+    }
+
+    /// [`parse`] returns the version of `tag`, `None` when it does not follow the format.
+    pub fn parse(&self, tag: &str) -> Option<SemanticVersion> {
+        let captures = self.pattern.captures(tag)?;
+        let component = |name: &str| match captures.name(name) {
+            Some(number) => number.as_str().parse().ok(),
+            None => Some(0),
+        };
+
+        Some(SemanticVersion {
+            major: group(&captures, "major").parse().ok()?,
+            minor: component("minor")?,
+            patch: component("patch")?,
+            pre: captures.name("pre").map(|pre| pre.as_str().to_string()),
+            build: captures
+                .name("build")
+                .map(|build| build.as_str().to_string()),
+        })
+    }
+
+    /// [`components`] returns the number of numeric components of `tag`, below three only for
+    /// the short tags read by a [`lenient`](TagFormat::lenient) format.
+    pub fn components(&self, tag: &str) -> Option<usize> {
+        let captures = self.pattern.captures(tag)?;
+
+        Some(
+            1 + ["minor", "patch"]
+                .into_iter()
+                .filter(|name| captures.name(name).is_some())
+                .count(),
+        )
+    }
+}
+
+impl Default for TagFormat {
+    fn default() -> Self {
+        TagFormat {
+            template: DEFAULT_TAG_FORMAT.to_string(),
+            pattern: literal_regex(&Self::pattern(DEFAULT_TAG_FORMAT, false)),
+            lenient: false,
+        }
+    }
+}
+
+impl PartialEq for TagFormat {
+    fn eq(&self, other: &Self) -> bool {
+        self.template == other.template && self.lenient == other.lenient
+    }
+}
+
+/// A tag ending with a version, e.g. `pkg/v1.2.3-rc.1`, split after its prefix.
+static VERSION_TAG_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    literal_regex(
+        r"^(?P<prefix>[^{}]*?)[0-9]+\.[0-9]+\.[0-9]+(?:-[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*)?(?:\+[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*)?$",
+    )
+});
+
+/// [`DetectedFormat`] is a tag format in use in a repository, see [`detect_tag_formats`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetectedFormat {
+    /// Template of the tags, e.g. `v{major}.{minor}.{patch}`.
+    pub template: String,
+    /// Number of tags following the template.
+    pub tags: usize,
+    /// The greatest tag following the template, releases before prereleases.
+    pub example: String,
+}
+
+/// [`DetectedFormats`] holds the tag formats in use in a repository, the most common first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DetectedFormats {
+    pub formats: Vec<DetectedFormat>,
+}
+
+impl DetectedFormats {
+    /// [`suggested`] returns the most common format, the one to configure.
+    pub fn suggested(&self) -> Option<&DetectedFormat> {
+        self.formats.first()
+    }
+
+    /// [`report`] returns the formats as `semver.toml` lines, the suggested one first and the
+    /// others commented out, e.g. for the package tags.
+    pub fn report(&self) -> String {
+        self.formats
+            .iter()
+            .enumerate()
+            .map(|(index, format)| {
+                format!(
+                    "{}tag_format = \"{}\"  # {} tag(s), e.g. {}\n",
+                    if index == 0 { "" } else { "# " },
+                    format.template,
+                    format.tags,
+                    format.example
+                )
+            })
+            .collect()
+    }
+
+    /// [`as_json_string`] returns json representation of the formats.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+/// [`detect_tag_formats`] infers the formats of `tags` from their prefix, the most common
+/// first; the tags which do not end with a version are left out.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let tags = ["1.0.0", "v1.1.0", "v1.2.0-rc.1", "pkg/v0.3.0", "nightly"].map(String::from);
+/// let detected = detect_tag_formats(&tags);
+///
+/// let suggested = detected.suggested().unwrap();
+/// assert_eq!(suggested.template, "v{major}.{minor}.{patch}");
+/// assert_eq!(suggested.tags, 2);
+/// assert_eq!(suggested.example, "v1.1.0");
+/// assert_eq!(detected.formats.len(), 3);
+/// assert!(detected.report().starts_with("tag_format = \"v{major}.{minor}.{patch}\"  # 2 tag(s), e.g. v1.1.0\n# "));
+/// ```
+pub fn detect_tag_formats(tags: &[String]) -> DetectedFormats {
+    let mut formats: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for tag in tags {
+        if let Some(captures) = VERSION_TAG_PATTERN.captures(tag) {
+            let template = format!(
+                "{}{{major}}.{{minor}}.{{patch}}",
+                group(&captures, "prefix")
+            );
+            formats.entry(template).or_default().push(tag);
+        }
+    }
+
+    let mut formats = formats
+        .into_iter()
+        .filter_map(|(template, tags)| {
+            let format = TagFormat::new(&template).ok()?;
+            let example = tags
+                .iter()
+                .filter_map(|tag| Some((format.parse(tag)?, *tag)))
+                .max_by_key(|(version, _)| (!version.is_prerelease(), version.clone()))
+                .map(|(_, tag)| tag.to_string())?;
+            Some(DetectedFormat {
+                template,
+                tags: tags.len(),
+                example,
+            })
+        })
+        .collect::<Vec<_>>();
+    formats.sort_by_key(|format| std::cmp::Reverse(format.tags));
+
+    DetectedFormats { formats }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tag_format_escapes_the_literal_parts_of_the_template() {
+        let format = TagFormat::default().with_prefix("api.").unwrap();
+
+        assert_eq!(
+            format.parse("api.v1.2.3+sha.abc1234"),
+            Some(SemanticVersion {
+                build: Some("sha.abc1234".to_string()),
+                ..SemanticVersion::new(1, 2, 3)
+            })
+        );
+        assert_eq!(format.parse("apixv1.2.3"), None);
+        assert!(TagFormat::new("{major}.{minor}").is_err());
+    }
+
+    #[test]
+    fn test_detect_tag_formats_groups_tags_by_prefix_most_common_first() {
+        let tags = [
+            "v1.0.0",
+            "v1.10.0",
+            "v2.0.0-rc.1",
+            "pkg/v0.4.0-beta.1",
+            "pkg/v0.4.0-beta.2",
+            "1.0.0",
+            "nightly",
+            "release-2024",
+        ]
+        .map(String::from);
+
+        let detected = detect_tag_formats(&tags);
+        assert_eq!(
+            detected.formats,
+            vec![
+                DetectedFormat {
+                    template: "v{major}.{minor}.{patch}".to_string(),
+                    tags: 3,
+                    example: "v1.10.0".to_string(),
+                },
+                DetectedFormat {
+                    template: "pkg/v{major}.{minor}.{patch}".to_string(),
+                    tags: 2,
+                    example: "pkg/v0.4.0-beta.2".to_string(),
+                },
+                DetectedFormat {
+                    template: "{major}.{minor}.{patch}".to_string(),
+                    tags: 1,
+                    example: "1.0.0".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            detected.report(),
+            "tag_format = \"v{major}.{minor}.{patch}\"  # 3 tag(s), e.g. v1.10.0\n\
+             # tag_format = \"pkg/v{major}.{minor}.{patch}\"  # 2 tag(s), e.g. pkg/v0.4.0-beta.2\n\
+             # tag_format = \"{major}.{minor}.{patch}\"  # 1 tag(s), e.g. 1.0.0\n"
+        );
+        assert_eq!(
+            detect_tag_formats(&["nightly".to_string()]).suggested(),
+            None
+        );
+    }
+}
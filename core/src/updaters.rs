@@ -0,0 +1,696 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use regex::Regex;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use toml_edit::{DocumentMut, Item};
+use tracing::{debug, info};
+
+use crate::models::literal_regex;
+use crate::{CargoManifest, Config, SemVerError, SemanticVersion, VersionFileConfig, Versioning};
+
+/// Tables of a `pyproject.toml` that may hold the version, in order.
+const PYPROJECT_TABLES: [&[&str]; 2] = [&["project"], &["tool", "poetry"]];
+
+/// `"version": "..."` members of a `package.json`, at any depth.
+static PACKAGE_VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| literal_regex(r#""version"\s*:\s*"(?P<version>[^"\\]*)""#));
+
+/// Top level `version:` of a Helm `Chart.yaml`, quoted or not.
+static CHART_VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| literal_regex(r#"(?m)^version:[ \t]*["']?(?P<version>[^"'\s#]+)["']?"#));
+
+/// [`VersionFileUpdater`] rewrites the version held by a file.
+///
+/// Implementations only compute the new contents, writing is left to [`apply_changes`]
+/// so that every configured file is updated or none is.
+pub trait VersionFileUpdater {
+    /// Path of the file to update.
+    fn path(&self) -> &Path;
+
+    /// Returns `contents` with the version replaced by `version`.
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError>;
+
+    /// Returns the version held by `contents`.
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        let _ = contents;
+        Err(update_failed(
+            self.path(),
+            "reading the version is not supported",
+        ))
+    }
+}
+
+/// [`FileChange`] holds the original and updated contents of a file.
+///
+/// It serializes as its path and [`FileChange::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+impl FileChange {
+    /// [`diff`] returns the removed (`-`) and added (`+`) lines.
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let change = FileChange {
+    ///     path: "VERSION".into(),
+    ///     original: "name\n1.2.3\n".to_string(),
+    ///     updated: "name\n1.3.0\n".to_string(),
+    /// };
+    /// assert_eq!(change.diff(), vec!["-1.2.3", "+1.3.0"]);
+    /// ```
+    pub fn diff(&self) -> Vec<String> {
+        let original = self.original.lines().collect::<Vec<_>>();
+        let updated = self.updated.lines().collect::<Vec<_>>();
+
+        let prefix = original
+            .iter()
+            .zip(&updated)
+            .take_while(|(o, u)| o == u)
+            .count();
+        let suffix = original[prefix..]
+            .iter()
+            .rev()
+            .zip(updated[prefix..].iter().rev())
+            .take_while(|(o, u)| o == u)
+            .count();
+        let original = &original[prefix..original.len() - suffix];
+        let updated = &updated[prefix..updated.len() - suffix];
+
+        // longest common subsequence of the differing middle part
+        let mut lengths = vec![vec![0usize; updated.len() + 1]; original.len() + 1];
+        for (i, o) in original.iter().enumerate().rev() {
+            for (j, u) in updated.iter().enumerate().rev() {
+                lengths[i][j] = match o == u {
+                    true => lengths[i + 1][j + 1] + 1,
+                    false => lengths[i + 1][j].max(lengths[i][j + 1]),
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        let mut diff = vec![];
+        while i < original.len() || j < updated.len() {
+            if i < original.len() && j < updated.len() && original[i] == updated[j] {
+                i += 1;
+                j += 1;
+            } else if i < original.len()
+                && (j == updated.len() || lengths[i + 1][j] >= lengths[i][j + 1])
+            {
+                diff.push(format!("-{}", original[i]));
+                i += 1;
+            } else {
+                diff.push(format!("+{}", updated[j]));
+                j += 1;
+            }
+        }
+
+        diff
+    }
+}
+
+impl Serialize for FileChange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut change = serializer.serialize_struct("FileChange", 2)?;
+        change.serialize_field("path", &self.path)?;
+        change.serialize_field("diff", &self.diff())?;
+        change.end()
+    }
+}
+
+/// [`BumpPlan`] holds the version computed by `semver bump` and the changes it implies.
+#[derive(Debug, Clone, Serialize)]
+pub struct BumpPlan {
+    pub version: SemanticVersion,
+    pub changes: Vec<FileChange>,
+}
+
+impl BumpPlan {
+    /// Describes every step of the bump, in order.
+    pub fn steps(&self) -> Vec<String> {
+        let mut steps = vec![format!(
+            "compute next version: {}",
+            String::from(self.version.clone())
+        )];
+        steps.extend(
+            self.changes
+                .iter()
+                .map(|change| format!("update {}", change.path.display())),
+        );
+        steps
+    }
+
+    /// Writes every change, all or nothing.
+    pub fn apply(&self) -> Result<(), SemVerError> {
+        apply_changes(&self.changes)
+    }
+
+    /// [`as_json_string`] returns json representation of the plan, including its steps.
+    pub fn as_json_string(&self) -> Result<String, SemVerError> {
+        Ok(serde_json::to_string(&PlanOutput {
+            steps: self.steps(),
+            plan: self,
+        })?)
+    }
+}
+
+/// Serialized form of a plan, with its human-readable steps.
+#[derive(Serialize)]
+pub(crate) struct PlanOutput<'a, T: Serialize> {
+    pub(crate) steps: Vec<String>,
+    #[serde(flatten)]
+    pub(crate) plan: &'a T,
+}
+
+/// Updates `Cargo.toml` `[package] version`.
+pub struct CargoUpdater {
+    pub path: PathBuf,
+    /// Crates released in lockstep, the requirements on them are updated as well.
+    pub lockstep: Vec<String>,
+}
+
+/// Updates the top level `"version"` of a `package.json`.
+pub struct PackageJsonUpdater {
+    pub path: PathBuf,
+}
+
+/// Updates `[project] version` or `[tool.poetry] version` of a `pyproject.toml`.
+pub struct PyprojectUpdater {
+    pub path: PathBuf,
+}
+
+/// Updates the top level `version:` of a helm `Chart.yaml`.
+pub struct ChartUpdater {
+    pub path: PathBuf,
+}
+
+/// Updates a file whose only content is the version, e.g. `VERSION`.
+pub struct PlainVersionUpdater {
+    pub path: PathBuf,
+}
+
+/// Updates the `version` named group (or the first group) of every match of `pattern`.
+pub struct RegexUpdater {
+    pub path: PathBuf,
+    pub pattern: Regex,
+}
+
+impl VersionFileUpdater for CargoUpdater {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError> {
+        let mut manifest = CargoManifest::parse(&self.path, contents)?;
+        // a virtual workspace manifest has no version, only requirements
+        if self.lockstep.is_empty() || manifest.package_name().is_ok() {
+            manifest.set_version(version)?;
+        }
+        for name in &self.lockstep {
+            manifest.set_dependency_requirement(name, version);
+        }
+
+        Ok(manifest.to_string())
+    }
+
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        CargoManifest::parse(&self.path, contents)?.version()
+    }
+}
+
+impl VersionFileUpdater for PackageJsonUpdater {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError> {
+        let package: serde_json::Value =
+            serde_json::from_str(contents).map_err(|e| update_failed(&self.path, e.to_string()))?;
+        let current = package
+            .get("version")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| update_failed(&self.path, "missing top level `version`"))?;
+
+        // the first member holding the top level version, nested ones may come before it
+        let group = PACKAGE_VERSION_PATTERN
+            .captures_iter(contents)
+            .filter_map(|captures| captures.name("version"))
+            .find(|group| group.as_str() == current)
+            .ok_or_else(|| update_failed(&self.path, "version not found"))?;
+
+        Ok(format!(
+            "{}{}{}",
+            &contents[..group.start()],
+            render_like(current, version),
+            &contents[group.end()..]
+        ))
+    }
+
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        let package: serde_json::Value =
+            serde_json::from_str(contents).map_err(|e| update_failed(&self.path, e.to_string()))?;
+        let current = package
+            .get("version")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| update_failed(&self.path, "missing top level `version`"))?;
+
+        parse_like(current)
+    }
+}
+
+impl VersionFileUpdater for PyprojectUpdater {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError> {
+        let mut document = contents
+            .parse::<DocumentMut>()
+            .map_err(|e| update_failed(&self.path, e.to_string()))?;
+
+        let table = PYPROJECT_TABLES
+            .iter()
+            .find(|keys| {
+                keys.iter()
+                    .try_fold(document.as_item(), |item, key| item.get(key))
+                    .and_then(|table| table.get("version"))
+                    .is_some_and(Item::is_str)
+            })
+            .ok_or_else(|| update_failed(&self.path, "missing `[project] version`"))?;
+        let item = table
+            .iter()
+            .try_fold(document.as_item_mut(), |item, key| {
+                item.as_table_like_mut().and_then(|t| t.get_mut(key))
+            })
+            .and_then(Item::as_table_like_mut)
+            .and_then(|t| t.get_mut("version"))
+            .ok_or_else(|| update_failed(&self.path, "missing `[project] version`"))?;
+
+        let current = item.as_str().unwrap_or_default().to_string();
+        let decor = item.as_value().map(|v| v.decor().clone());
+        *item = toml_edit::value(render_like(&current, version));
+        if let (Some(decor), Some(v)) = (decor, item.as_value_mut()) {
+            *v.decor_mut() = decor;
+        }
+
+        Ok(document.to_string())
+    }
+
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        let document = contents
+            .parse::<DocumentMut>()
+            .map_err(|e| update_failed(&self.path, e.to_string()))?;
+        let current = PYPROJECT_TABLES
+            .iter()
+            .find_map(|keys| {
+                keys.iter()
+                    .try_fold(document.as_item(), |item, key| item.get(key))
+                    .and_then(|table| table.get("version"))
+                    .and_then(Item::as_str)
+            })
+            .ok_or_else(|| update_failed(&self.path, "missing `[project] version`"))?;
+
+        parse_like(current)
+    }
+}
+
+impl VersionFileUpdater for ChartUpdater {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError> {
+        replace_group(&self.path, &CHART_VERSION_PATTERN, contents, version, 1)
+    }
+
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        find_group(&self.path, &CHART_VERSION_PATTERN, contents)
+    }
+}
+
+impl VersionFileUpdater for PlainVersionUpdater {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError> {
+        let current = contents.trim();
+        let trailing = &contents[contents.trim_end().len()..];
+
+        Ok(format!("{}{}", render_like(current, version), trailing))
+    }
+
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        parse_like(contents.trim())
+    }
+}
+
+impl RegexUpdater {
+    pub fn new(path: impl Into<PathBuf>, pattern: &str) -> Result<Self, SemVerError> {
+        let pattern = Regex::new(pattern).map_err(|e| SemVerError::InvalidConfig(e.to_string()))?;
+        if pattern.captures_len() < 2 {
+            return Err(SemVerError::InvalidConfig(format!(
+                "pattern `{}` must capture the version",
+                pattern
+            )));
+        }
+
+        Ok(Self {
+            path: path.into(),
+            pattern,
+        })
+    }
+}
+
+impl VersionFileUpdater for RegexUpdater {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, contents: &str, version: &SemanticVersion) -> Result<String, SemVerError> {
+        replace_group(&self.path, &self.pattern, contents, version, usize::MAX)
+    }
+
+    fn version(&self, contents: &str) -> Result<SemanticVersion, SemVerError> {
+        find_group(&self.path, &self.pattern, contents)
+    }
+}
+
+/// [`updater_for`] builds the updater matching the configured file.
+pub fn updater_for(file: &VersionFileConfig) -> Result<Box<dyn VersionFileUpdater>, SemVerError> {
+    let path = file.path.clone();
+    if let Some(pattern) = &file.pattern {
+        return Ok(Box::new(RegexUpdater::new(path, pattern)?));
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    Ok(match file_name {
+        "Cargo.toml" => Box::new(CargoUpdater {
+            path,
+            lockstep: vec![],
+        }),
+        "package.json" => Box::new(PackageJsonUpdater { path }),
+        "pyproject.toml" => Box::new(PyprojectUpdater { path }),
+        "Chart.yaml" => Box::new(ChartUpdater { path }),
+        "VERSION" => Box::new(PlainVersionUpdater { path }),
+        _ => {
+            return Err(SemVerError::InvalidConfig(format!(
+                "no updater for `{}`, declare a `pattern`",
+                path.display()
+            )))
+        }
+    })
+}
+
+impl Config {
+    /// Returns the updaters for the configured version files.
+    ///
+    /// With [`Versioning::Lockstep`], the manifests also update the requirements on each other.
+    pub fn updaters(&self) -> Result<Vec<Box<dyn VersionFileUpdater>>, SemVerError> {
+        if self.versioning == Versioning::Independent {
+            return self.files.iter().map(updater_for).collect();
+        }
+
+        let is_manifest = |file: &VersionFileConfig| {
+            file.pattern.is_none()
+                && file.path.file_name().and_then(|name| name.to_str()) == Some("Cargo.toml")
+        };
+        let mut lockstep = vec![];
+        for file in self.files.iter().filter(|file| is_manifest(file)) {
+            if let Ok(name) = CargoManifest::load(&file.path)?.package_name() {
+                lockstep.push(name.to_string());
+            }
+        }
+
+        self.files
+            .iter()
+            .map(|file| match is_manifest(file) {
+                true => Ok(Box::new(CargoUpdater {
+                    path: file.path.clone(),
+                    lockstep: lockstep.clone(),
+                }) as Box<dyn VersionFileUpdater>),
+                false => updater_for(file),
+            })
+            .collect()
+    }
+}
+
+/// [`plan_updates`] reads every file and computes its new contents without writing anything.
+pub fn plan_updates(
+    updaters: &[Box<dyn VersionFileUpdater>],
+    version: &SemanticVersion,
+) -> Result<Vec<FileChange>, SemVerError> {
+    updaters
+        .iter()
+        .map(|updater| {
+            let original = fs::read_to_string(updater.path())
+                .map_err(|e| update_failed(updater.path(), e.to_string()))?;
+            let updated = updater.update(&original, version)?;
+            debug!(path = %updater.path().display(), changed = original != updated, "planned update");
+
+            Ok(FileChange {
+                path: updater.path().to_path_buf(),
+                original,
+                updated,
+            })
+        })
+        .collect()
+}
+
+/// [`apply_changes`] writes every change, restoring the already written files if one fails.
+pub fn apply_changes(changes: &[FileChange]) -> Result<(), SemVerError> {
+    for (index, change) in changes.iter().enumerate() {
+        if let Err(e) = fs::write(&change.path, &change.updated) {
+            for written in &changes[..index] {
+                let _ = fs::write(&written.path, &written.original);
+            }
+            return Err(update_failed(&change.path, e.to_string()));
+        }
+        info!(path = %change.path.display(), "updated file");
+    }
+
+    Ok(())
+}
+
+/// [`run_updaters`] updates every file to `version`, all or nothing.
+pub fn run_updaters(
+    updaters: &[Box<dyn VersionFileUpdater>],
+    version: &SemanticVersion,
+) -> Result<Vec<FileChange>, SemVerError> {
+    let changes = plan_updates(updaters, version)?;
+    apply_changes(&changes)?;
+
+    Ok(changes)
+}
+
+/// Renders `version` without prefix, e.g. `1.2.3`.
+pub(crate) fn bare_version(version: &SemanticVersion) -> String {
+    format!("{}.{}.{}", version.major, version.minor, version.patch)
+}
+
+/// Renders `version` keeping the `v` prefix of `current` if it has one.
+fn render_like(current: &str, version: &SemanticVersion) -> String {
+    match current.starts_with('v') {
+        true => String::from(version.clone()),
+        false => bare_version(version),
+    }
+}
+
+/// Parses a version written with or without the `v` prefix, e.g. `1.2.3`.
+fn parse_like(current: &str) -> Result<SemanticVersion, SemVerError> {
+    SemanticVersion::try_from(format!("v{}", current.trim_start_matches('v')).as_str())
+}
+
+/// Returns the version held by the version group of the first match of `pattern`.
+fn find_group(
+    path: &Path,
+    pattern: &Regex,
+    contents: &str,
+) -> Result<SemanticVersion, SemVerError> {
+    let current = pattern
+        .captures(contents)
+        .and_then(|captures| captures.name("version").or_else(|| captures.get(1)))
+        .ok_or_else(|| update_failed(path, "version not found"))?;
+
+    parse_like(current.as_str())
+}
+
+/// Replaces the version group of at most `limit` matches of `pattern`.
+fn replace_group(
+    path: &Path,
+    pattern: &Regex,
+    contents: &str,
+    version: &SemanticVersion,
+    limit: usize,
+) -> Result<String, SemVerError> {
+    let mut updated = String::with_capacity(contents.len());
+    let mut last = 0;
+    let mut found = false;
+
+    for captures in pattern.captures_iter(contents).take(limit) {
+        let Some(group) = captures.name("version").or_else(|| captures.get(1)) else {
+            continue;
+        };
+        updated.push_str(&contents[last..group.start()]);
+        updated.push_str(&render_like(group.as_str(), version));
+        last = group.end();
+        found = true;
+    }
+    if !found {
+        return Err(update_failed(path, "version not found"));
+    }
+    updated.push_str(&contents[last..]);
+
+    Ok(updated)
+}
+
+fn update_failed(path: &Path, reason: impl Into<String>) -> SemVerError {
+    SemVerError::VersionFileUpdateFailed(path.display().to_string(), reason.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const VERSION: SemanticVersion = SemanticVersion::new(1, 3, 0);
+
+    #[test]
+    fn test_builtin_updaters_replace_only_the_version() {
+        let cases: Vec<(Box<dyn VersionFileUpdater>, &str, &str)> = vec![
+            (
+                Box::new(PackageJsonUpdater {
+                    path: "package.json".into(),
+                }),
+                "{\n  \"name\": \"app\",\n  \"version\": \"1.2.3\",\n  \"dependencies\": { \"x\": \"1.2.3\" }\n}\n",
+                "{\n  \"name\": \"app\",\n  \"version\": \"1.3.0\",\n  \"dependencies\": { \"x\": \"1.2.3\" }\n}\n",
+            ),
+            (
+                Box::new(PyprojectUpdater {
+                    path: "pyproject.toml".into(),
+                }),
+                "[tool.poetry]\nname = \"app\"\nversion = \"1.2.3\" # bumped\n",
+                "[tool.poetry]\nname = \"app\"\nversion = \"1.3.0\" # bumped\n",
+            ),
+            (
+                Box::new(ChartUpdater {
+                    path: "Chart.yaml".into(),
+                }),
+                "apiVersion: v2\nversion: \"1.2.3\"\nappVersion: 1.2.3\n",
+                "apiVersion: v2\nversion: \"1.3.0\"\nappVersion: 1.2.3\n",
+            ),
+            (
+                Box::new(PlainVersionUpdater {
+                    path: "VERSION".into(),
+                }),
+                "v1.2.3\n",
+                "v1.3.0\n",
+            ),
+            (
+                Box::new(RegexUpdater::new("version.rs", r#"VERSION: &str = "([^"]+)""#).unwrap()),
+                "pub const VERSION: &str = \"1.2.3\";\n",
+                "pub const VERSION: &str = \"1.3.0\";\n",
+            ),
+        ];
+
+        for (updater, contents, expected) in cases {
+            assert_eq!(updater.update(contents, &VERSION).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_updater_returns_error_when_version_is_not_found() {
+        let updater = ChartUpdater {
+            path: "Chart.yaml".into(),
+        };
+
+        assert_eq!(
+            updater.update("apiVersion: v2\n", &VERSION).unwrap_err(),
+            SemVerError::VersionFileUpdateFailed(
+                "Chart.yaml".to_string(),
+                "version not found".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_updaters_does_not_write_anything_when_one_updater_fails() {
+        let directory = std::env::temp_dir().join("semver-updaters-test");
+        fs::create_dir_all(&directory).unwrap();
+        let version_file = directory.join("VERSION");
+        let chart_file = directory.join("Chart.yaml");
+        fs::write(&version_file, "1.2.3\n").unwrap();
+        fs::write(&chart_file, "name: app\n").unwrap();
+
+        let updaters: Vec<Box<dyn VersionFileUpdater>> = vec![
+            Box::new(PlainVersionUpdater {
+                path: version_file.clone(),
+            }),
+            Box::new(ChartUpdater { path: chart_file }),
+        ];
+
+        assert!(run_updaters(&updaters, &VERSION).is_err());
+        assert_eq!(fs::read_to_string(&version_file).unwrap(), "1.2.3\n");
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_lockstep_updaters_update_versions_and_requirements_together() {
+        let directory = std::env::temp_dir().join("semver-lockstep-test");
+        fs::create_dir_all(&directory).unwrap();
+        let root = directory.join("Cargo.toml");
+        let core = directory.join("core").join("Cargo.toml");
+        fs::create_dir_all(core.parent().unwrap()).unwrap();
+        fs::write(
+            &root,
+            "[workspace]\n\n[workspace.dependencies]\ncore = \"1.2.3\"\n",
+        )
+        .unwrap();
+        fs::write(&core, "[package]\nname = \"core\"\nversion = \"1.2.3\"\n").unwrap();
+
+        let config = Config::from_toml_str(&format!(
+            "versioning = \"lockstep\"\n\n[[files]]\npath = {:?}\n\n[[files]]\npath = {:?}\n",
+            root, core
+        ))
+        .unwrap();
+        let changes = plan_updates(&config.updaters().unwrap(), &VERSION).unwrap();
+
+        assert_eq!(
+            changes.iter().map(FileChange::diff).collect::<Vec<_>>(),
+            vec![
+                vec!["-core = \"1.2.3\"", "+core = \"1.3.0\""],
+                vec!["-version = \"1.2.3\"", "+version = \"1.3.0\""],
+            ]
+        );
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_bump_plan_serializes_steps_and_diffs() {
+        let plan = BumpPlan {
+            version: VERSION,
+            changes: vec![FileChange {
+                path: "Chart.yaml".into(),
+                original: "name: app\nversion: 1.2.3\nappVersion: 1.2.3\n".to_string(),
+                updated: "name: app\nversion: 1.3.0\nappVersion: 1.2.3\n".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            plan.as_json_string().unwrap(),
+            r#"{"steps":["compute next version: v1.3.0","update Chart.yaml"],"version":"v1.3.0","changes":[{"path":"Chart.yaml","diff":["-version: 1.2.3","+version: 1.3.0"]}]}"#
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::{Commit, Git, Jujutsu, Mercurial, SemVerError, SemanticVersion, Tag, TagFormat};
+
+/// [`Vcs`] reads the history of a repository and tags its releases, so that the version
+/// calculation and the changelog work with other version control systems than git, see
+/// [`crate::Mercurial`].
+///
+/// Revisions are written the git way whatever the backend: `HEAD` is the checked out revision
+/// and `from..to` the revisions reachable from `to` but not from `from`.
+pub trait Vcs {
+    /// Root directory of the working copy.
+    fn directory(&self) -> &Path;
+
+    /// Returns the name of the checked out branch, `None` when there is none.
+    fn current_branch(&self) -> Result<Option<String>, SemVerError>;
+
+    /// Returns the tags reachable from `HEAD`, pointing to the commit they tag.
+    fn tags(&self) -> Result<Vec<Tag>, SemVerError>;
+
+    /// Returns the names of every tag of the repository, reachable from `HEAD` or not.
+    fn tag_names(&self) -> Result<Vec<String>, SemVerError>;
+
+    /// Returns the commits of `range` (e.g. `v1.0.0..HEAD`, all of `HEAD` when `None`), oldest
+    /// first.
+    fn commits(&self, range: Option<&str>) -> Result<Vec<Commit>, SemVerError>;
+
+    /// Returns the commit `revision`, e.g. `HEAD` or a tag, with its message.
+    fn commit(&self, revision: &str) -> Result<Commit, SemVerError>;
+
+    /// Returns the paths, relative to the repository root, changed by the commit `revision`.
+    fn changed_paths(&self, revision: &str) -> Result<Vec<PathBuf>, SemVerError>;
+
+    /// Returns the paths, relative to the repository root, of the changes about to be committed:
+    /// the staged ones for git, the working copy ones otherwise.
+    fn staged_paths(&self) -> Result<Vec<PathBuf>, SemVerError>;
+
+    /// Tags `HEAD` with `name`, annotated with `message` where supported.
+    fn create_tag(&self, name: &str, message: &str) -> Result<(), SemVerError>;
+
+    /// Returns the url of the default remote, `origin` for git, if any.
+    fn remote_url(&self) -> Option<String>;
+
+    /// Returns `true` when the history stops at a shallow boundary, `false` by default.
+    fn is_shallow(&self) -> Result<bool, SemVerError> {
+        Ok(false)
+    }
+
+    /// Fetches the tags and the history missing from a shallow clone, nothing by default.
+    fn fetch_history(&self) -> Result<(), SemVerError> {
+        Ok(())
+    }
+
+    /// Returns the greatest tag of `format` reachable from `HEAD`, prereleases left out.
+    ///
+    /// A shallow history without any is [`SemVerError::ShallowHistory`], its tag being likely
+    /// beyond the boundary.
+    fn latest_version_tag_with_format(
+        &self,
+        format: &TagFormat,
+    ) -> Result<Option<(Tag, SemanticVersion)>, SemVerError> {
+        let latest = self
+            .tags()?
+            .into_iter()
+            .filter_map(|tag| {
+                let version = format.parse(&tag.name).filter(|v| !v.is_prerelease())?;
+                Some((tag, version))
+            })
+            .max_by(|(_, l), (_, r)| l.cmp(r));
+        match &latest {
+            Some((tag, _)) => debug!(tag = %tag.name, "latest version tag"),
+            None if self.is_shallow()? => return Err(SemVerError::ShallowHistory),
+            None => debug!("no version tag"),
+        }
+
+        Ok(latest)
+    }
+}
+
+/// [`open_vcs`] returns the backend of the working copy at `directory`: [`Jujutsu`] when it
+/// holds a `.jj` directory, even colocated with git, [`Mercurial`] for a `.hg` directory and
+/// [`Git`] otherwise.
+///
+/// A colocated repository is read with jj, git seeing a detached `HEAD` and no branch there.
+pub fn open_vcs(directory: impl Into<PathBuf>) -> Box<dyn Vcs> {
+    let directory = directory.into();
+    if directory.join(".jj").is_dir() {
+        Box::new(Jujutsu::new(directory))
+    } else if directory.join(".hg").is_dir() {
+        Box::new(Mercurial::new(directory))
+    } else {
+        Box::new(Git::new(directory))
+    }
+}
+
+impl Vcs for Git {
+    fn directory(&self) -> &Path {
+        Git::directory(self)
+    }
+
+    fn current_branch(&self) -> Result<Option<String>, SemVerError> {
+        Git::current_branch(self)
+    }
+
+    fn tags(&self) -> Result<Vec<Tag>, SemVerError> {
+        Git::tags(self)
+    }
+
+    fn tag_names(&self) -> Result<Vec<String>, SemVerError> {
+        Git::tag_names(self)
+    }
+
+    fn commits(&self, range: Option<&str>) -> Result<Vec<Commit>, SemVerError> {
+        Git::commits(self, range)
+    }
+
+    fn commit(&self, revision: &str) -> Result<Commit, SemVerError> {
+        Git::commit(self, revision)
+    }
+
+    fn changed_paths(&self, revision: &str) -> Result<Vec<PathBuf>, SemVerError> {
+        Git::changed_paths(self, revision)
+    }
+
+    fn staged_paths(&self) -> Result<Vec<PathBuf>, SemVerError> {
+        Git::staged_paths(self)
+    }
+
+    fn create_tag(&self, name: &str, message: &str) -> Result<(), SemVerError> {
+        Git::create_tag(self, name, message)
+    }
+
+    fn remote_url(&self) -> Option<String> {
+        self.run(["remote", "get-url", "origin"]).ok()
+    }
+
+    fn is_shallow(&self) -> Result<bool, SemVerError> {
+        Git::is_shallow(self)
+    }
+
+    fn fetch_history(&self) -> Result<(), SemVerError> {
+        Git::fetch_history(self)
+    }
+}
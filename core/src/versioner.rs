@@ -1,23 +1,33 @@
-use crate::{SemVerError, SemanticComment, SemanticVersion, SemanticType};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use crate::{
+    BumpLevel, Commit, ParsedCommit, SemVerError, SemanticComment, SemanticType, SemanticVersion,
+};
 
 /// [`calculate_version`] calculates the next semantic version given the semantic comment.
 /// Expected semantic version format
 /// **`v<major>.<minor>.<patch>`**
 /// ## Rules for calculation
-/// - `fix`     increments `<patch>`, for non breaking change.
-/// - `refact`  increments `<patch>`, for non breaking change.
-/// - `feat`    increments `<minor>`, for non breaking change.
-/// - for breaking changes: `feat`, `refact` and `fix` changes `<major>`.
+/// - `fix`      increments `<patch>`, for non breaking change.
+/// - `refactor` increments `<patch>`, for non breaking change.
+/// - `feat`     increments `<minor>`, for non breaking change.
+/// - for breaking changes: `feat`, `refactor` and `fix` changes `<major>`.
+/// - other types, e.g. `docs`, do not change the version unless breaking.
 /// - Every time most significant number in version increments, the numbers below will zero.
 /// ### Rules - Example
 /// Given the current version: `v1.2.3`
 /// #### If `incomming_commit_comment` is non breaking, for a:
 /// - fix:      `v1.2.4`
-/// - refact:   `v1.2.4`
+/// - refactor: `v1.2.4`
 /// - feat:     `v1.3.0`
 /// #### If `incomming_commit_comment` is a breaking comment, for a:
 /// - fix:      `v2.0.0`
-/// - refact:   `v2.0.0`
+/// - refactor: `v2.0.0`
 /// - feat:     `v2.0.0`
 /// # Example
 /// ```
@@ -27,7 +37,7 @@ use crate::{SemVerError, SemanticComment, SemanticVersion, SemanticType};
 /// assert_eq!(calculate_version("v2.3.5", "feat: this is a new feature.".try_into().unwrap()).unwrap(), "v2.4.0");
 /// assert_eq!(calculate_version("v30.3.5", "fix! this is a breaking fix.".try_into().unwrap()).unwrap(), "v31.0.0");
 /// assert_eq!(calculate_version("v2.3.5", "feat! this is a breaking feature.".try_into().unwrap()).unwrap(), "v3.0.0");
-/// assert_eq!(calculate_version("v2.3.5", "refact: this is a refactor.".try_into().unwrap()).unwrap(), "v2.3.6");
+/// assert_eq!(calculate_version("v2.3.5", "refactor: this is a refactor.".try_into().unwrap()).unwrap(), "v2.3.6");
 /// ```
 pub fn calculate_version(
     current_version: &str,
@@ -35,27 +45,709 @@ pub fn calculate_version(
 ) -> Result<String, SemVerError> {
     let mut semantic_version: SemanticVersion = current_version.try_into()?;
 
-    match incomming_commit_comment.semantic_type {
-        SemanticType::Fix(meta) if !meta.is_breaking => semantic_version.patch += 1,
-        SemanticType::Refactoring(meta) if !meta.is_breaking => semantic_version.patch += 1,
-        SemanticType::Feature(meta) if !meta.is_breaking => {
-            semantic_version.minor += 1;
-            semantic_version.patch = 0;
+    semantic_version.bump(&incomming_commit_comment.semantic_type)?;
+
+    Ok(semantic_version.into())
+}
+
+/// [`calculate_version_with_decision`] calculates the next version like [`calculate_version`],
+/// along with the [`BumpDecision`] explaining it.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let (version, decision) =
+///     calculate_version_with_decision("v2.3.5", "fix: a fix.".try_into().unwrap()).unwrap();
+///
+/// assert_eq!(version, "v2.3.6");
+/// assert_eq!(decision.bump, BumpLevel::Patch);
+/// assert_eq!(decision.drivers[0].rule, BumpRule::DefaultBump);
+/// ```
+pub fn calculate_version_with_decision(
+    current_version: &str,
+    incomming_commit_comment: SemanticComment,
+) -> Result<(String, BumpDecision), SemVerError> {
+    let mut semantic_version: SemanticVersion = current_version.try_into()?;
+    let commit = ParsedCommit {
+        commit: Commit::new("-", incomming_commit_comment.to_commit_string()),
+        comment: incomming_commit_comment,
+    };
+    let decision = BumpPolicy::default().decide(&[commit], None);
+
+    semantic_version.apply_bump(decision.bump)?;
+
+    Ok((semantic_version.into(), decision))
+}
+
+impl SemanticVersion {
+    /// [`bump`] increments the version in place following the rules of [`calculate_version`].
+    /// # Example
+    /// ```
+    /// use core::*;
+    ///
+    /// let mut version = SemanticVersion::new(1, 2, 3);
+    /// version.bump(&SemanticType::Feature(SemanticTypeMetadata::new(false))).unwrap();
+    /// assert_eq!(version, SemanticVersion::new(1, 3, 0));
+    /// ```
+    pub fn bump(&mut self, semantic_type: &SemanticType) -> Result<(), SemVerError> {
+        self.apply_bump(semantic_type.default_bump())
+    }
+
+    /// [`apply_bump`] increments the component matching `level`, zeroing the ones below it.
+    ///
+    /// A prerelease is released by the bump it announces, e.g. a minor bump releases
+    /// `v1.3.0-rc.2` as `v1.3.0`; the prerelease and build metadata are dropped either way.
+    /// It fails with [`SemVerError::VersionOverflow`], leaving the version untouched, when the
+    /// component is already `u64::MAX`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let mut version = SemanticVersion::try_from("v1.3.0-rc.2").unwrap();
+    /// version.apply_bump(BumpLevel::Minor).unwrap();
+    /// assert_eq!(version, SemanticVersion::new(1, 3, 0));
+    ///
+    /// let mut version = SemanticVersion::new(1, u64::MAX, 0);
+    /// assert!(version.apply_bump(BumpLevel::Minor).is_err());
+    /// assert_eq!(version, SemanticVersion::new(1, u64::MAX, 0));
+    /// ```
+    pub fn apply_bump(&mut self, level: BumpLevel) -> Result<(), SemVerError> {
+        if level == BumpLevel::None {
+            return Ok(());
+        }
+        let overflow = || SemVerError::VersionOverflow(String::from(self.clone()));
+        let next = |component: u64| component.checked_add(1).ok_or_else(overflow);
+        let announced = match &self.pre {
+            Some(_) => match level {
+                BumpLevel::Major => self.minor == 0 && self.patch == 0,
+                BumpLevel::Minor => self.patch == 0,
+                _ => true,
+            },
+            None => false,
+        };
+        let released = match level {
+            _ if announced => self.release(),
+            BumpLevel::None => self.release(),
+            BumpLevel::Patch => SemanticVersion::new(self.major, self.minor, next(self.patch)?),
+            BumpLevel::Minor => SemanticVersion::new(self.major, next(self.minor)?, 0),
+            BumpLevel::Major => SemanticVersion::new(next(self.major)?, 0, 0),
+        };
+        *self = released;
+
+        Ok(())
+    }
+}
+
+/// [`PolicyRule`] maps a condition to the bump it implies, as declared in `semver.toml`:
+/// ```toml
+/// [[policy.rules]]
+/// when = "breaking && branch == main"
+/// bump = "major"
+///
+/// [[policy.rules]]
+/// when = "type in [docs, chore]"
+/// bump = "none"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    pub when: String,
+    pub bump: BumpLevel,
+}
+
+/// [`PolicyExample`] is a commit with its expected bump, checked by `semver policy test`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyExample {
+    pub commit: String,
+    pub branch: Option<String>,
+    pub expect: BumpLevel,
+}
+
+/// [`BumpPolicy`] evaluates [`PolicyRule`]s against commits.
+///
+/// Rules are evaluated in order and the first matching rule decides the bump, falling back
+/// to [`SemanticType::default_bump`] when none matches.
+///
+/// # Condition language
+/// - `breaking`, `true` and `false` are booleans.
+/// - `type`, `scope` and `branch` compare with `==`, `!=` and `in [a, b]`, values may be quoted.
+/// - `&&`, `||`, `!` and parentheses combine conditions.
+///
+/// # Example
+/// ```
+/// use core::*;
+///
+/// let policy = BumpPolicy::new(&[
+///     PolicyRule { when: "breaking && branch != main".to_string(), bump: BumpLevel::Minor },
+///     PolicyRule { when: "type in [docs, chore]".to_string(), bump: BumpLevel::None },
+/// ]).unwrap();
+///
+/// let comment: SemanticComment = "feat! breaking feature.".try_into().unwrap();
+/// assert_eq!(policy.evaluate(&comment, Some("develop")), BumpLevel::Minor);
+/// assert_eq!(policy.evaluate(&comment, Some("main")), BumpLevel::Major);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BumpPolicy {
+    rules: Vec<(String, Condition, BumpLevel)>,
+}
+
+impl BumpPolicy {
+    /// [`new`] compiles the rules, failing on the first invalid condition.
+    pub fn new(rules: &[PolicyRule]) -> Result<Self, SemVerError> {
+        let rules = rules
+            .iter()
+            .map(|rule| Ok((rule.when.clone(), Condition::parse(&rule.when)?, rule.bump)))
+            .collect::<Result<_, SemVerError>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// [`evaluate`] returns the bump implied by the comment on `branch`.
+    pub fn evaluate(&self, comment: &SemanticComment, branch: Option<&str>) -> BumpLevel {
+        self.explain(comment, branch).0
+    }
+
+    /// [`explain`] returns the bump implied by the comment on `branch` along with the rule
+    /// deciding it.
+    pub fn explain(
+        &self,
+        comment: &SemanticComment,
+        branch: Option<&str>,
+    ) -> (BumpLevel, BumpRule) {
+        match self
+            .rules
+            .iter()
+            .find(|(_, condition, _)| condition.matches(comment, branch))
+        {
+            Some((when, _, bump)) => {
+                trace!(comment = %comment.comment, rule = %when, %bump, "policy rule matched");
+                (*bump, BumpRule::Policy(when.clone()))
+            }
+            None => {
+                let bump = comment.semantic_type.default_bump();
+                trace!(comment = %comment.comment, %bump, "default bump of the type");
+                (bump, BumpRule::DefaultBump)
+            }
+        }
+    }
+
+    /// [`evaluate_commits`] returns the highest bump of `commits` on `branch`, evaluated on the
+    /// rayon thread pool with the `parallel` feature.
+    pub fn evaluate_commits(&self, commits: &[ParsedCommit], branch: Option<&str>) -> BumpLevel {
+        #[cfg(feature = "parallel")]
+        let commits = commits.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let commits = commits.iter();
+
+        commits
+            .map(|parsed| self.evaluate(&parsed.comment, branch))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// [`decide`] returns the highest bump of `commits` on `branch` like
+    /// [`BumpPolicy::evaluate_commits`], along with the commits driving it and the rules
+    /// deciding their bumps.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let policy = BumpPolicy::new(&[
+    ///     PolicyRule { when: "scope == deps".to_string(), bump: BumpLevel::Patch },
+    ///     PolicyRule { when: "type == refactor".to_string(), bump: BumpLevel::None },
+    /// ]).unwrap();
+    /// let commits = [("a1", "feat(deps): bump serde."), ("b2", "fix: a fix."), ("c3", "refactor: tidy.")]
+    ///     .map(|(sha, message)| ParsedCommit {
+    ///         commit: Commit::new(sha, message),
+    ///         comment: message.try_into().unwrap(),
+    ///     });
+    ///
+    /// let decision = policy.decide(&commits, None);
+    /// assert_eq!(decision.bump, BumpLevel::Patch);
+    /// assert_eq!(decision.drivers[0].rule, BumpRule::Policy("scope == deps".to_string()));
+    /// assert_eq!(decision.drivers[1].rule, BumpRule::DefaultBump);
+    /// assert_eq!(decision.ignored[0].sha, "c3");
+    /// ```
+    pub fn decide(&self, commits: &[ParsedCommit], branch: Option<&str>) -> BumpDecision {
+        #[cfg(feature = "parallel")]
+        let evaluated = commits.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let evaluated = commits.iter();
+        let evaluated = evaluated
+            .map(|parsed| self.explain(&parsed.comment, branch))
+            .collect::<Vec<_>>();
+
+        let mut decision = BumpDecision {
+            bump: evaluated
+                .iter()
+                .map(|(bump, _)| *bump)
+                .max()
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        for (parsed, (bump, rule)) in commits.iter().zip(evaluated) {
+            if bump == BumpLevel::None {
+                decision.ignore(&parsed.commit, format!("no bump, {}", rule));
+                continue;
+            }
+            let commit_bump = CommitBump {
+                sha: parsed.commit.sha.clone(),
+                subject: parsed.commit.subject().to_string(),
+                bump,
+                rule,
+            };
+            match bump == decision.bump {
+                true => decision.drivers.push(commit_bump),
+                false => decision.outranked.push(commit_bump),
+            }
+        }
+
+        decision
+    }
+}
+
+/// [`BumpRule`] is what decided the bump of a commit, see [`BumpPolicy::explain`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BumpRule {
+    /// The first `[[policy.rules]]` matching the commit, by its condition.
+    Policy(String),
+    /// No policy rule matched: the bump is the default one of the type, see
+    /// [`SemanticType::default_bump`].
+    DefaultBump,
+}
+
+impl fmt::Display for BumpRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BumpRule::Policy(when) => write!(f, "policy rule `{}`", when),
+            BumpRule::DefaultBump => write!(f, "default bump of the type"),
+        }
+    }
+}
+
+/// [`CommitBump`] is the bump a commit implies, with the rule deciding it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitBump {
+    pub sha: String,
+    pub subject: String,
+    pub bump: BumpLevel,
+    pub rule: BumpRule,
+}
+
+/// [`IgnoredCommit`] is a commit which did not count in a bump, with the reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IgnoredCommit {
+    pub sha: String,
+    pub subject: String,
+    /// Why the commit did not count, e.g. `no bump, default bump of the type`.
+    pub reason: String,
+}
+
+/// [`BumpDecision`] explains a bump, see [`BumpPolicy::decide`]: the commits driving it, the
+/// ones implying a lower bump and the ones which did not count.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BumpDecision {
+    pub bump: BumpLevel,
+    /// Commits implying `bump`, which decided it.
+    pub drivers: Vec<CommitBump>,
+    /// Commits implying a lower bump than `bump`.
+    pub outranked: Vec<CommitBump>,
+    /// Commits implying no bump, or left out before, e.g. reverted or unparseable ones.
+    pub ignored: Vec<IgnoredCommit>,
+}
+
+impl BumpDecision {
+    /// [`ignore`] records that `commit` did not count in the bump, because of `reason`.
+    pub fn ignore(&mut self, commit: &Commit, reason: impl Into<String>) {
+        self.ignored.push(IgnoredCommit {
+            sha: commit.sha.clone(),
+            subject: commit.subject().to_string(),
+            reason: reason.into(),
+        });
+    }
+}
+
+impl fmt::Display for BumpDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let short = |sha: &str| sha.chars().take(7).collect::<String>();
+
+        write!(f, "bump: {}", self.bump)?;
+        for (label, commits) in [("drove", &self.drivers), ("outranked", &self.outranked)] {
+            for commit in commits {
+                write!(
+                    f,
+                    "\n  {:<9} {:<7} {} ({}, {})",
+                    label,
+                    short(&commit.sha),
+                    commit.subject,
+                    commit.bump,
+                    commit.rule
+                )?;
+            }
         }
-        _ => {
-            semantic_version.major += 1;
-            semantic_version.minor = 0;
-            semantic_version.patch = 0;
+        for commit in &self.ignored {
+            write!(
+                f,
+                "\n  {:<9} {:<7} {} ({})",
+                "ignored",
+                short(&commit.sha),
+                commit.subject,
+                commit.reason
+            )?;
         }
+
+        Ok(())
     }
+}
+
+/// [`calculate_version_with_policy`] calculates the next version like [`calculate_version`],
+/// letting `policy` decide the bump.
+pub fn calculate_version_with_policy(
+    current_version: &str,
+    incomming_commit_comment: &SemanticComment,
+    policy: &BumpPolicy,
+    branch: Option<&str>,
+) -> Result<String, SemVerError> {
+    let mut semantic_version: SemanticVersion = current_version.try_into()?;
+
+    semantic_version.apply_bump(policy.evaluate(incomming_commit_comment, branch))?;
 
     Ok(semantic_version.into())
 }
 
+/// [`AggregateBump`] collects semantic comments into the bump they imply together, the highest
+/// of theirs, evaluated by their type or by a [`BumpPolicy`].
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let comments = ["fix: a fix.", "feat: a feature."].map(|c| SemanticComment::try_from(c).unwrap());
+///
+/// let bump: AggregateBump = comments.iter().collect();
+/// assert_eq!(bump.bump, BumpLevel::Minor);
+/// assert_eq!(bump.next_version(&SemanticVersion::new(1, 2, 3)).unwrap(), SemanticVersion::new(1, 3, 0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AggregateBump {
+    /// Highest bump of the comments.
+    pub bump: BumpLevel,
+    /// Number of comments collected.
+    pub comments: usize,
+    /// Number of breaking comments collected.
+    pub breaking: usize,
+}
+
+impl AggregateBump {
+    /// [`add`] collects `comment`, bumped by its type.
+    pub fn add(&mut self, comment: &SemanticComment) {
+        self.record(comment, comment.semantic_type.default_bump());
+    }
+
+    /// [`add_with_policy`] collects `comment`, bumped as `policy` decides on `branch`.
+    pub fn add_with_policy(
+        &mut self,
+        comment: &SemanticComment,
+        policy: &BumpPolicy,
+        branch: Option<&str>,
+    ) {
+        self.record(comment, policy.evaluate(comment, branch));
+    }
+
+    /// [`next_version`] returns `version` bumped by the collected comments.
+    pub fn next_version(&self, version: &SemanticVersion) -> Result<SemanticVersion, SemVerError> {
+        let mut version = version.clone();
+        version.apply_bump(self.bump)?;
+        Ok(version)
+    }
+
+    fn record(&mut self, comment: &SemanticComment, bump: BumpLevel) {
+        self.bump = self.bump.max(bump);
+        self.comments += 1;
+        if comment.semantic_type.metadata().is_breaking {
+            self.breaking += 1;
+        }
+    }
+}
+
+impl Extend<SemanticComment> for AggregateBump {
+    fn extend<I: IntoIterator<Item = SemanticComment>>(&mut self, comments: I) {
+        comments.into_iter().for_each(|comment| self.add(&comment));
+    }
+}
+
+impl<'a> Extend<&'a SemanticComment> for AggregateBump {
+    fn extend<I: IntoIterator<Item = &'a SemanticComment>>(&mut self, comments: I) {
+        comments.into_iter().for_each(|comment| self.add(comment));
+    }
+}
+
+impl FromIterator<SemanticComment> for AggregateBump {
+    fn from_iter<I: IntoIterator<Item = SemanticComment>>(comments: I) -> Self {
+        let mut aggregate = Self::default();
+        aggregate.extend(comments);
+        aggregate
+    }
+}
+
+impl<'a> FromIterator<&'a SemanticComment> for AggregateBump {
+    fn from_iter<I: IntoIterator<Item = &'a SemanticComment>>(comments: I) -> Self {
+        let mut aggregate = Self::default();
+        aggregate.extend(comments);
+        aggregate
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Type,
+    Scope,
+    Branch,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Bool(bool),
+    Breaking,
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Equal(Field, String),
+    NotEqual(Field, String),
+    In(Field, Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    And,
+    Or,
+    Not,
+    Equal,
+    NotEqual,
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+}
+
+impl Condition {
+    fn parse(expression: &str) -> Result<Self, SemVerError> {
+        let tokens = tokenize(expression)?;
+        let mut parser = ConditionParser {
+            expression,
+            tokens: &tokens,
+            position: 0,
+        };
+        let condition = parser.or()?;
+
+        match parser.tokens.get(parser.position) {
+            None => Ok(condition),
+            Some(token) => Err(parser.error(&format!("unexpected {:?}", token))),
+        }
+    }
+
+    fn matches(&self, comment: &SemanticComment, branch: Option<&str>) -> bool {
+        let value = |field: &Field| match field {
+            Field::Type => comment.semantic_type.keyword(Default::default()),
+            Field::Scope => comment.scope.as_deref().unwrap_or_default(),
+            Field::Branch => branch.unwrap_or_default(),
+        };
+
+        match self {
+            Self::Bool(b) => *b,
+            Self::Breaking => comment.semantic_type.metadata().is_breaking,
+            Self::Not(c) => !c.matches(comment, branch),
+            Self::And(l, r) => l.matches(comment, branch) && r.matches(comment, branch),
+            Self::Or(l, r) => l.matches(comment, branch) || r.matches(comment, branch),
+            Self::Equal(field, expected) => value(field) == expected,
+            Self::NotEqual(field, expected) => value(field) != expected,
+            Self::In(field, expected) => expected.iter().any(|e| e == value(field)),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, SemVerError> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            ',' => Token::Comma,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Equal,
+            '!' if chars.next_if_eq(&'=').is_some() => Token::NotEqual,
+            '!' => Token::Not,
+            '"' | '\'' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some(v) => value.push(v),
+                        None => {
+                            return Err(SemVerError::InvalidPolicy(format!(
+                                "unterminated string in `{}`",
+                                expression
+                            )))
+                        }
+                    }
+                }
+                Token::Quoted(value)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(w) = chars.next_if(|w| is_word_char(*w)) {
+                    word.push(w);
+                }
+                Token::Word(word)
+            }
+            c => {
+                return Err(SemVerError::InvalidPolicy(format!(
+                    "unexpected `{}` in `{}`",
+                    c, expression
+                )))
+            }
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')
+}
+
+struct ConditionParser<'a> {
+    expression: &'a str,
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> ConditionParser<'a> {
+    fn error(&self, reason: &str) -> SemVerError {
+        SemVerError::InvalidPolicy(format!("{} in `{}`", reason, self.expression))
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        let found = self.tokens.get(self.position) == Some(expected);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Condition, SemVerError> {
+        let mut condition = self.and()?;
+        while self.eat(&Token::Or) {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, SemVerError> {
+        let mut condition = self.unary()?;
+        while self.eat(&Token::And) {
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition, SemVerError> {
+        if self.eat(&Token::Not) {
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::OpenParen) {
+            let condition = self.or()?;
+            if !self.eat(&Token::CloseParen) {
+                return Err(self.error("expected `)`"));
+            }
+            return Ok(condition);
+        }
+
+        let field = match self.next() {
+            Some(Token::Word(word)) => match word.as_str() {
+                "breaking" => return Ok(Condition::Breaking),
+                "true" => return Ok(Condition::Bool(true)),
+                "false" => return Ok(Condition::Bool(false)),
+                "type" => Field::Type,
+                "scope" => Field::Scope,
+                "branch" => Field::Branch,
+                word => return Err(self.error(&format!("unknown identifier `{}`", word))),
+            },
+            _ => return Err(self.error("expected a condition")),
+        };
+
+        match self.next() {
+            Some(Token::Equal) => Ok(Condition::Equal(field, self.value()?)),
+            Some(Token::NotEqual) => Ok(Condition::NotEqual(field, self.value()?)),
+            Some(Token::Word(word)) if word == "in" => {
+                if !self.eat(&Token::OpenBracket) {
+                    return Err(self.error("expected `[`"));
+                }
+                let mut values = vec![self.value()?];
+                while self.eat(&Token::Comma) {
+                    values.push(self.value()?);
+                }
+                if !self.eat(&Token::CloseBracket) {
+                    return Err(self.error("expected `]`"));
+                }
+                Ok(Condition::In(field, values))
+            }
+            _ => Err(self.error("expected `==`, `!=` or `in`")),
+        }
+    }
+
+    fn value(&mut self) -> Result<String, SemVerError> {
+        match self.next() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => Ok(value.clone()),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
 
+    #[test]
+    fn test_apply_bump_reports_overflow_and_keeps_the_version() {
+        let max = u64::MAX;
+        let cases = [
+            (SemanticVersion::new(max, 0, 0), BumpLevel::Major),
+            (SemanticVersion::new(1, max, 0), BumpLevel::Minor),
+            (SemanticVersion::new(1, 2, max), BumpLevel::Patch),
+        ];
+
+        for (version, level) in cases {
+            let mut bumped = version.clone();
+            assert_eq!(
+                bumped.apply_bump(level),
+                Err(SemVerError::VersionOverflow(String::from(version.clone())))
+            );
+            assert_eq!(bumped, version);
+        }
+
+        let mut version = SemanticVersion::new(1, max, max);
+        version.apply_bump(BumpLevel::Major).unwrap();
+        assert_eq!(version, SemanticVersion::new(2, 0, 0));
+    }
+
     #[test]
     fn test_calculate_version_calculates_new_version_according_to_expected() {
         let (comment, current_version, expected_version) =
@@ -70,4 +762,92 @@ mod test {
             "v2.3.6"
         )
     }
+
+    #[test]
+    fn test_bump_policy_first_matching_rule_wins() {
+        let policy = BumpPolicy::new(&[
+            PolicyRule {
+                when: "breaking && branch == main".to_string(),
+                bump: BumpLevel::Major,
+            },
+            PolicyRule {
+                when: "breaking".to_string(),
+                bump: BumpLevel::Minor,
+            },
+            PolicyRule {
+                when: "type in [docs, 'chore'] || (type == fix && scope == \"deps\")".to_string(),
+                bump: BumpLevel::None,
+            },
+        ])
+        .unwrap();
+        let parser = CommentParser::builder()
+            .types(["feat", "fix", "docs", "chore"])
+            .build();
+
+        let cases = vec![
+            ("feat! breaking.", Some("main"), BumpLevel::Major),
+            ("feat! breaking.", Some("next"), BumpLevel::Minor),
+            ("docs: readme.", None, BumpLevel::None),
+            ("fix(deps): bump regex.", None, BumpLevel::None),
+            ("fix(parser): fix.", None, BumpLevel::Patch),
+            ("feat: feature.", None, BumpLevel::Minor),
+        ];
+
+        for (comment, branch, expected) in cases {
+            let comment = parser.parse(comment).unwrap();
+            assert_eq!(policy.evaluate(&comment, branch), expected);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_bump_collects_a_comment_stream_with_a_policy() {
+        let policy = BumpPolicy::new(&[PolicyRule {
+            when: "type == docs".to_string(),
+            bump: BumpLevel::Patch,
+        }])
+        .unwrap();
+        let parser = CommentParser::builder()
+            .types(["feat", "fix", "docs"])
+            .build();
+        let log = "docs: readme.\nnot conventional\nfix! drop v1.\n";
+
+        let mut aggregate = AggregateBump::default();
+        let mut errors = 0;
+        for comment in parser.parse_comments(log.lines()) {
+            match comment {
+                Ok(comment) => aggregate.add_with_policy(&comment, &policy, None),
+                Err(_) => errors += 1,
+            }
+        }
+
+        assert_eq!(
+            aggregate,
+            AggregateBump {
+                bump: BumpLevel::Major,
+                comments: 2,
+                breaking: 1,
+            }
+        );
+        assert_eq!(errors, 1);
+    }
+
+    #[test]
+    fn test_bump_policy_returns_error_for_invalid_condition() {
+        let cases = vec![
+            "breaking &&",
+            "type = docs",
+            "author == me",
+            "type in [docs",
+        ];
+
+        for when in cases {
+            let error = BumpPolicy::new(&[PolicyRule {
+                when: when.to_string(),
+                bump: BumpLevel::None,
+            }])
+            .unwrap_err();
+
+            assert!(matches!(error, SemVerError::InvalidPolicy(_)));
+        }
+    }
 }
@@ -0,0 +1,114 @@
+//! JavaScript bindings, behind the `wasm` feature, so that PR bots and browser tooling parse
+//! commits and compute versions exactly like the CLI. They are built into the
+//! `@guiln/semver-wasm` package from the `wasm` crate.
+//!
+//! ```js
+//! import { parseComment, calculateVersion } from "@guiln/semver-wasm";
+//!
+//! parseComment("feat(api): export.").scope;                  // "api"
+//! calculateVersion("v1.2.3", "feat: export.").version;       // "v1.3.0"
+//! calculateVersion("v1.2.3", "fix: a fix.", 'scheme = "calver"');
+//! ```
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{BumpLevel, Config, SemVerError, SemanticComment, SemanticVersion};
+
+/// [`VersionCalculation`] holds the version following `previous` for a comment, returned to
+/// JavaScript by [`calculate_version_js`].
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionCalculation {
+    pub previous: String,
+    pub version: String,
+    pub bump: BumpLevel,
+    pub comment: SemanticComment,
+}
+
+/// Parses `comment` with the configuration `config`, the contents of a `semver.toml`, or the
+/// default one. Returns the semantic comment as an object, throws when it does not parse.
+#[wasm_bindgen(js_name = parseComment)]
+pub fn parse_comment_js(comment: &str, config: Option<String>) -> Result<JsValue, JsError> {
+    to_js(parse_comment(comment, config.as_deref()))
+}
+
+/// Computes the version following `currentVersion` for `comment`, evaluating the bump policy
+/// and version scheme of `config`, calendar versions following the date of JavaScript.
+/// Returns `{ previous, version, bump, comment }`.
+#[wasm_bindgen(js_name = calculateVersion)]
+pub fn calculate_version_js(
+    current_version: &str,
+    comment: &str,
+    config: Option<String>,
+) -> Result<JsValue, JsError> {
+    let now = js_sys::Date::new_0();
+    let month = (i64::from(now.get_utc_full_year()), now.get_utc_month() + 1);
+
+    to_js(calculate(
+        current_version,
+        comment,
+        config.as_deref(),
+        month,
+    ))
+}
+
+fn load_config(config: Option<&str>) -> Result<Config, SemVerError> {
+    config.map_or_else(|| Ok(Config::default()), Config::from_toml_str)
+}
+
+fn parse_comment(comment: &str, config: Option<&str>) -> Result<SemanticComment, SemVerError> {
+    load_config(config)?.comment_parser().parse(comment)
+}
+
+/// Computes the version as released in `(year, month)`: the system clock of WebAssembly panics.
+fn calculate(
+    current_version: &str,
+    comment: &str,
+    config: Option<&str>,
+    month: (i64, u32),
+) -> Result<VersionCalculation, SemVerError> {
+    let config = load_config(config)?;
+    let comment = config.comment_parser().parse(comment)?;
+    let previous = SemanticVersion::try_from(current_version)?;
+    let bump = config.bump_policy()?.evaluate(&comment, None);
+
+    Ok(VersionCalculation {
+        previous: previous.clone().into(),
+        version: config.next_version_in(&previous, bump, month)?.into(),
+        bump,
+        comment,
+    })
+}
+
+fn to_js<T: Serialize>(result: Result<T, SemVerError>) -> Result<JsValue, JsError> {
+    let value = result.map_err(|e| JsError::new(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_calculate_follows_the_given_configuration() {
+        let month = (2024, 6);
+        let calculation = calculate("v1.2.3", "feat(api): export.", None, month).unwrap();
+        assert_eq!(calculation.version, "v1.3.0");
+        assert_eq!(calculation.bump, BumpLevel::Minor);
+        assert_eq!(calculation.comment.scope.as_deref(), Some("api"));
+
+        let config = "[[policy.rules]]\nwhen = \"scope == api\"\nbump = \"major\"\n";
+        let calculation = calculate("v1.2.3", "feat(api): export.", Some(config), month).unwrap();
+        assert_eq!(calculation.version, "v2.0.0");
+
+        let calver = Some("scheme = \"calver\"");
+        let calculation = calculate("v2024.6.1", "fix: a fix.", calver, month).unwrap();
+        assert_eq!(calculation.version, "v2024.6.2");
+        let calculation = calculate("v2024.5.3", "fix: a fix.", calver, month).unwrap();
+        assert_eq!(calculation.version, "v2024.6.0");
+
+        assert!(parse_comment("wip", None).is_err());
+        assert!(calculate("latest", "fix: a fix.", None, month).is_err());
+    }
+}
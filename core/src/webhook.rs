@@ -0,0 +1,292 @@
+//! Conventional commit gate for GitHub webhooks, behind the `webhook` feature: the commits of
+//! push events and the titles of pull requests are linted, and the result is posted back as a
+//! commit status.
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tracing::info;
+
+use crate::{forge::send, Commit, Config, SemVerError};
+
+/// Header holding the HMAC-SHA256 of the payload, `sha256=<hex>`.
+pub const GITHUB_SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+/// Header naming the event of the payload, e.g. `push`.
+pub const GITHUB_EVENT_HEADER: &str = "X-GitHub-Event";
+/// Context of the posted commit statuses.
+pub const GITHUB_STATUS_CONTEXT: &str = "semver/lint";
+
+/// [`verify_github_signature`] checks the `signature` header of `payload` against the webhook
+/// `secret`, in constant time.
+///
+/// # Example
+/// ```
+/// # use core::*;
+/// let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+///
+/// assert!(verify_github_signature(b"It's a Secret to Everybody", b"Hello, World!", signature));
+/// assert!(!verify_github_signature(b"another secret", b"Hello, World!", signature));
+/// ```
+pub fn verify_github_signature(secret: &[u8], payload: &[u8], signature: &str) -> bool {
+    let Some(expected) = signature.strip_prefix("sha256=").and_then(decode_hex) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret) else {
+        return false;
+    };
+    mac.update(payload);
+
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// [`CommitCheck`] is the result of linting an event, posted as the status of `sha`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitCheck {
+    /// Repository of the event, `owner/name`.
+    pub repository: String,
+    pub sha: String,
+    /// Number of messages linted: the pushed commits, or the title of the pull request.
+    pub checked: usize,
+    /// Errors of the messages failing the lint, e.g. `a1b2c3d: invalid format`.
+    pub failures: Vec<String>,
+}
+
+impl CommitCheck {
+    /// State of the commit status, `success` or `failure`.
+    pub fn state(&self) -> &'static str {
+        match self.failures.is_empty() {
+            true => "success",
+            false => "failure",
+        }
+    }
+
+    /// Description of the commit status, limited by GitHub to 140 characters.
+    pub fn description(&self) -> String {
+        let description = match self.failures.first() {
+            None => format!("{} conventional message(s)", self.checked),
+            Some(failure) => format!(
+                "{} of {} failed: {}",
+                self.failures.len(),
+                self.checked,
+                failure
+            ),
+        };
+
+        description.chars().take(140).collect()
+    }
+}
+
+impl Config {
+    /// [`check_github_event`] lints the commits of a `push` event, or the title of a
+    /// `pull_request` event when it is opened, edited, reopened or synchronized, filtered like
+    /// in [`Config::lint_commits`]. Other events, e.g. `ping`, return `None`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::*;
+    /// let payload = serde_json::json!({
+    ///     "action": "opened",
+    ///     "repository": { "full_name": "team/app" },
+    ///     "pull_request": { "title": "add the export", "head": { "sha": "a1" } },
+    /// });
+    ///
+    /// let check = Config::default().check_github_event("pull_request", &payload).unwrap().unwrap();
+    /// assert_eq!(check.state(), "failure");
+    /// assert!(Config::default().check_github_event("ping", &payload).unwrap().is_none());
+    /// ```
+    pub fn check_github_event(
+        &self,
+        event: &str,
+        payload: &Value,
+    ) -> Result<Option<CommitCheck>, SemVerError> {
+        let field = |pointer: &str| {
+            payload
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    SemVerError::ForgeError(format!("the {} event has no `{}`", event, pointer))
+                })
+        };
+
+        let check = match event {
+            "push" if payload.pointer("/deleted") == Some(&Value::Bool(true)) => None,
+            "push" => {
+                let commits = payload
+                    .pointer("/commits")
+                    .and_then(Value::as_array)
+                    .map(|commits| {
+                        commits
+                            .iter()
+                            .filter_map(|commit| {
+                                Some(Commit::new(
+                                    commit.get("id")?.as_str()?,
+                                    commit.get("message")?.as_str()?,
+                                ))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                let failures = self
+                    .lint_commits(&commits)
+                    .into_iter()
+                    .map(|(commit, error)| {
+                        let sha = commit.sha.chars().take(7).collect::<String>();
+                        format!("{}: {}", sha, error)
+                    })
+                    .collect();
+
+                Some(CommitCheck {
+                    repository: field("/repository/full_name")?,
+                    sha: field("/after")?,
+                    checked: commits.len(),
+                    failures,
+                })
+            }
+            "pull_request" => {
+                let action = payload.pointer("/action").and_then(Value::as_str);
+                if !matches!(
+                    action,
+                    Some("opened" | "edited" | "reopened" | "synchronize")
+                ) {
+                    return Ok(None);
+                }
+                let title = field("/pull_request/title")?;
+
+                Some(CommitCheck {
+                    repository: field("/repository/full_name")?,
+                    sha: field("/pull_request/head/sha")?,
+                    checked: 1,
+                    failures: self
+                        .lint(&title)
+                        .err()
+                        .map(|e| e.to_string())
+                        .into_iter()
+                        .collect(),
+                })
+            }
+            _ => None,
+        };
+        if let Some(check) = &check {
+            info!(
+                event,
+                repository = %check.repository,
+                sha = %check.sha,
+                state = check.state(),
+                "checked event"
+            );
+        }
+
+        Ok(check)
+    }
+}
+
+/// [`post_github_status`] posts `check` as the status of its commit, through the API at
+/// `api_url`, e.g. `https://api.github.com`, authenticated with `token`.
+pub fn post_github_status(
+    check: &CommitCheck,
+    api_url: &str,
+    token: &str,
+) -> Result<(), SemVerError> {
+    let url = format!(
+        "{}/repos/{}/statuses/{}",
+        api_url.trim_end_matches('/'),
+        check.repository,
+        check.sha
+    );
+    let request = ureq::post(&url)
+        .set("Authorization", &format!("Bearer {}", token))
+        .set("Accept", "application/vnd.github+json");
+
+    send(
+        "GitHub",
+        request,
+        Some(json!({
+            "state": check.state(),
+            "description": check.description(),
+            "context": GITHUB_STATUS_CONTEXT,
+        })),
+    )?;
+
+    Ok(())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_github_event_lints_the_pushed_commits() {
+        let config = Config::default();
+        let payload = json!({
+            "after": "c3",
+            "repository": { "full_name": "team/app" },
+            "commits": [
+                { "id": "a1b2c3d4e5", "message": "feat: export." },
+                { "id": "b2c3d4e5f6", "message": "wip" },
+            ],
+        });
+
+        let check = config
+            .check_github_event("push", &payload)
+            .unwrap()
+            .unwrap();
+        assert_eq!(check.sha, "c3");
+        assert_eq!(check.checked, 2);
+        assert_eq!(check.failures.len(), 1);
+        assert!(check.failures[0].starts_with("b2c3d4e: "));
+        assert!(check.description().starts_with("1 of 2 failed: b2c3d4e: "));
+        assert!(check.description().chars().count() <= 140);
+
+        let payload = json!({
+            "after": "c3",
+            "repository": { "full_name": "team/app" },
+            "commits": [{ "id": "a1b2c3éd5", "message": "wip" }],
+        });
+        let check = config
+            .check_github_event("push", &payload)
+            .unwrap()
+            .unwrap();
+        assert!(check.failures[0].starts_with("a1b2c3é: "));
+
+        let deleted = json!({ "deleted": true, "commits": [] });
+        assert_eq!(config.check_github_event("push", &deleted).unwrap(), None);
+        assert!(config
+            .check_github_event("push", &json!({ "commits": [] }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_github_event_lints_the_title_of_pull_requests() {
+        let config = Config::default();
+        let mut payload = json!({
+            "action": "edited",
+            "repository": { "full_name": "team/app" },
+            "pull_request": { "title": "fix(api): escape the ids.", "head": { "sha": "a1" } },
+        });
+
+        let check = config
+            .check_github_event("pull_request", &payload)
+            .unwrap()
+            .unwrap();
+        assert_eq!((check.state(), check.sha.as_str()), ("success", "a1"));
+
+        payload["action"] = json!("closed");
+        assert_eq!(
+            config.check_github_event("pull_request", &payload).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use std::env;
+
+fn main() {
+    let directory = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", directory)).unwrap();
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/lib.rs", directory))
+        .generate()
+        .expect("unable to generate the C header")
+        .write_to_file(format!("{}/include/semver.h", directory));
+}
@@ -0,0 +1,50 @@
+#ifndef SEMVER_H
+#define SEMVER_H
+
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result code of every function.
+typedef enum SemverStatus {
+  SEMVER_STATUS_OK = 0,
+  // A pointer is null or a string is not UTF-8.
+  SEMVER_STATUS_INVALID_ARGUMENT = 1,
+  // The comment does not follow the configured format or rules.
+  SEMVER_STATUS_INVALID_COMMENT = 2,
+  // The version is not a semantic version.
+  SEMVER_STATUS_INVALID_VERSION = 3,
+  // The configuration does not parse.
+  SEMVER_STATUS_INVALID_CONFIG = 4,
+  SEMVER_STATUS_ERROR = 5,
+} SemverStatus;
+
+// Parses `comment` with `config`, the contents of a `semver.toml` or null for the default
+// configuration, and returns the semantic comment as json in `out`.
+//
+// # Safety
+// `comment` and `config` must be null or NUL-terminated strings, `out` null or writable.
+enum SemverStatus semver_parse_comment(const char *comment, const char *config, char **out);
+
+// Computes the version following `current_version` for `comment`, evaluating the bump policy
+// and version scheme of `config`, null for the default configuration, and returns it in `out`.
+//
+// # Safety
+// `current_version`, `comment` and `config` must be null or NUL-terminated strings, `out` null
+// or writable.
+enum SemverStatus semver_next_version(const char *current_version,
+                                      const char *comment,
+                                      const char *config,
+                                      char **out);
+
+// Releases a string returned by the library, null is ignored.
+//
+// # Safety
+// `string` must be null or returned through the `out` of a function of this library, and not
+// released yet.
+void semver_string_free(char *string);
+
+#endif  /* SEMVER_H */
@@ -0,0 +1,234 @@
+//! C ABI of the parser and versioner, so that CI agents and editors written in other languages
+//! embed the same logic as the CLI. The declarations are generated into `include/semver.h`.
+//!
+//! Strings are NUL-terminated UTF-8. The strings returned through `out` are allocated by the
+//! library and released with [`semver_string_free`]; on failure `out` holds the error message.
+//!
+//! ```c
+//! #include "semver.h"
+//!
+//! char *version = NULL;
+//! if (semver_next_version("v1.2.3", "feat: export.", NULL, &version) == SEMVER_STATUS_OK) {
+//!     printf("%s\n", version); // v1.3.0
+//! }
+//! semver_string_free(version);
+//! ```
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr,
+};
+
+use core::{Config, SemVerError, SemanticVersion};
+
+/// Result code of every function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemverStatus {
+    Ok = 0,
+    /// A pointer is null or a string is not UTF-8.
+    InvalidArgument = 1,
+    /// The comment does not follow the configured format or rules.
+    InvalidComment = 2,
+    /// The version is not a semantic version.
+    InvalidVersion = 3,
+    /// The configuration does not parse.
+    InvalidConfig = 4,
+    Error = 5,
+}
+
+impl From<&SemVerError> for SemverStatus {
+    fn from(error: &SemVerError) -> Self {
+        match error {
+            SemVerError::InvalidCommentFormat
+            | SemVerError::UnexpectedSemanticType(_)
+            | SemVerError::RuleViolation(_, _)
+            | SemVerError::MissingTicket => Self::InvalidComment,
+            SemVerError::InvalidVersionFormat(_)
+            | SemVerError::ErrorWhenConvertingVersionNumber => Self::InvalidVersion,
+            SemVerError::InvalidConfig(_) | SemVerError::InvalidPolicy(_) => Self::InvalidConfig,
+            _ => Self::Error,
+        }
+    }
+}
+
+/// Parses `comment` with `config`, the contents of a `semver.toml` or null for the default
+/// configuration, and returns the semantic comment as json in `out`.
+///
+/// # Safety
+/// `comment` and `config` must be null or NUL-terminated strings, `out` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn semver_parse_comment(
+    comment: *const c_char,
+    config: *const c_char,
+    out: *mut *mut c_char,
+) -> SemverStatus {
+    let result = (|| {
+        let comment = read(comment)?.ok_or(SemverStatus::InvalidArgument)?;
+        let comment = load_config(config)?
+            .comment_parser()
+            .parse(comment)
+            .map_err(|e| failure(&e))?;
+
+        comment.as_json_string().map_err(|e| failure(&e))
+    })();
+
+    respond(result, out)
+}
+
+/// Computes the version following `current_version` for `comment`, evaluating the bump policy
+/// and version scheme of `config`, null for the default configuration, and returns it in `out`.
+///
+/// # Safety
+/// `current_version`, `comment` and `config` must be null or NUL-terminated strings, `out` null
+/// or writable.
+#[no_mangle]
+pub unsafe extern "C" fn semver_next_version(
+    current_version: *const c_char,
+    comment: *const c_char,
+    config: *const c_char,
+    out: *mut *mut c_char,
+) -> SemverStatus {
+    let result = (|| {
+        let current_version = read(current_version)?.ok_or(SemverStatus::InvalidArgument)?;
+        let comment = read(comment)?.ok_or(SemverStatus::InvalidArgument)?;
+        let config = load_config(config)?;
+
+        let next = (|| {
+            let comment = config.comment_parser().parse(comment)?;
+            let bump = config.bump_policy()?.evaluate(&comment, None);
+            config.next_version(&SemanticVersion::try_from(current_version)?, bump)
+        })();
+        next.map(String::from).map_err(|e| failure(&e))
+    })();
+
+    respond(result, out)
+}
+
+/// Releases a string returned by the library, null is ignored.
+///
+/// # Safety
+/// `string` must be null or returned through the `out` of a function of this library, and not
+/// released yet.
+#[no_mangle]
+pub unsafe extern "C" fn semver_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// A failed call: its status and the message returned in `out`.
+type Failure = (SemverStatus, String);
+
+impl From<SemverStatus> for Failure {
+    fn from(status: SemverStatus) -> Self {
+        (status, format!("{:?}", status))
+    }
+}
+
+fn failure(error: &SemVerError) -> Failure {
+    (error.into(), error.to_string())
+}
+
+/// Reads the string at `pointer`, `None` when it is null.
+unsafe fn read<'a>(pointer: *const c_char) -> Result<Option<&'a str>, Failure> {
+    if pointer.is_null() {
+        return Ok(None);
+    }
+
+    CStr::from_ptr(pointer)
+        .to_str()
+        .map(Some)
+        .map_err(|e| (SemverStatus::InvalidArgument, e.to_string()))
+}
+
+unsafe fn load_config(config: *const c_char) -> Result<Config, Failure> {
+    match read(config)? {
+        Some(contents) => Config::from_toml_str(contents).map_err(|e| failure(&e)),
+        None => Ok(Config::default()),
+    }
+}
+
+/// Writes the value or the error message of `result` to `out` and returns its status.
+unsafe fn respond(result: Result<String, Failure>, out: *mut *mut c_char) -> SemverStatus {
+    let (status, message) = match result {
+        Ok(value) => (SemverStatus::Ok, value),
+        Err(failure) => failure,
+    };
+    if !out.is_null() {
+        // interior NULs cannot come from UTF-8 input, they would only truncate the message
+        *out = CString::new(message)
+            .map(CString::into_raw)
+            .unwrap_or(ptr::null_mut());
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    unsafe fn call(f: impl FnOnce(*mut *mut c_char) -> SemverStatus) -> (SemverStatus, String) {
+        let mut out = ptr::null_mut();
+        let status = f(&mut out);
+        let value = CStr::from_ptr(out).to_str().unwrap().to_string();
+        semver_string_free(out);
+
+        (status, value)
+    }
+
+    #[test]
+    fn test_next_version_returns_the_version_or_the_error() {
+        let version = CString::new("v1.2.3").unwrap();
+        let feature = CString::new("feat(api): export.").unwrap();
+        let invalid = CString::new("wip").unwrap();
+        let config =
+            CString::new("[[policy.rules]]\nwhen = \"scope == api\"\nbump = \"major\"\n").unwrap();
+
+        unsafe {
+            assert_eq!(
+                call(|out| semver_next_version(
+                    version.as_ptr(),
+                    feature.as_ptr(),
+                    ptr::null(),
+                    out
+                )),
+                (SemverStatus::Ok, "v1.3.0".to_string())
+            );
+            assert_eq!(
+                call(|out| semver_next_version(
+                    version.as_ptr(),
+                    feature.as_ptr(),
+                    config.as_ptr(),
+                    out
+                )),
+                (SemverStatus::Ok, "v2.0.0".to_string())
+            );
+            assert_eq!(
+                call(|out| semver_next_version(
+                    version.as_ptr(),
+                    invalid.as_ptr(),
+                    ptr::null(),
+                    out
+                ))
+                .0,
+                SemverStatus::InvalidComment
+            );
+            assert_eq!(
+                semver_next_version(ptr::null(), feature.as_ptr(), ptr::null(), ptr::null_mut()),
+                SemverStatus::InvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_comment_returns_json() {
+        let comment = CString::new("fix(api): a fix.").unwrap();
+
+        let (status, json) =
+            unsafe { call(|out| semver_parse_comment(comment.as_ptr(), ptr::null(), out)) };
+        assert_eq!(status, SemverStatus::Ok);
+        assert!(json.contains("\"scope\":\"api\""));
+    }
+}
@@ -0,0 +1,18 @@
+#![no_main]
+
+use core::{CommentParser, Config};
+use libfuzzer_sys::fuzz_target;
+
+// The parsers must reject any message without panicking, whatever its bytes.
+fuzz_target!(|message: &str| {
+    let configured = Config::from_toml_str("types = [\"feat\", \"fix\", \"docs\"]\n")
+        .unwrap()
+        .comment_parser();
+
+    for parser in [CommentParser::default(), configured] {
+        let _ = parser.parse(message);
+        if let Ok(comment) = parser.parse_message(message) {
+            let _ = comment.co_authors();
+        }
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use core::{CommentParser, SemanticComment};
+use libfuzzer_sys::fuzz_target;
+
+// Rendered comments of every type parse back without panicking, breaking or not.
+fuzz_target!(|comment: SemanticComment| {
+    let rendered = comment.to_commit_string();
+
+    if let Ok(parsed) = CommentParser::default().parse(&rendered) {
+        assert_eq!(
+            parsed.semantic_type.metadata().is_breaking,
+            comment.semantic_type.metadata().is_breaking
+        );
+    }
+});
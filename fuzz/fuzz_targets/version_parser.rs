@@ -0,0 +1,18 @@
+#![no_main]
+
+use core::{SemanticVersion, TagFormat, VersionReq};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|version: &str| {
+    if let Ok(parsed) = SemanticVersion::try_from(version) {
+        let rendered = String::from(parsed.clone());
+        assert_eq!(
+            SemanticVersion::try_from(rendered.as_str()).unwrap(),
+            parsed
+        );
+    }
+    if let Ok(requirement) = VersionReq::parse(version) {
+        let _ = requirement.matches(&SemanticVersion::new(1, 2, 3));
+    }
+    let _ = TagFormat::default().parse(version);
+});
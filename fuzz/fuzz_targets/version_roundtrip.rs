@@ -0,0 +1,14 @@
+#![no_main]
+
+use core::SemanticVersion;
+use libfuzzer_sys::fuzz_target;
+
+// Every version renders to a string parsing back to it.
+fuzz_target!(|version: SemanticVersion| {
+    let rendered = String::from(version.clone());
+
+    assert_eq!(
+        SemanticVersion::try_from(rendered.as_str()).unwrap(),
+        version
+    );
+});
@@ -0,0 +1,129 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::LitStr;
+
+/// [`version!`] builds the `core::SemanticVersion` of a version literal, the `v` being
+/// optional, and fails the build when the literal is not a valid version.
+///
+/// Versions without prerelease nor build metadata can initialize constants. The expansion
+/// names the library `::core`, its package name, which must not be renamed.
+///
+/// # Example
+/// ```ignore
+/// use semver_macros::version;
+///
+/// const MINIMUM: core::SemanticVersion = version!("v1.2.3");
+///
+/// assert_eq!(MINIMUM, core::SemanticVersion::new(1, 2, 3));
+/// assert_eq!(version!("2.0.0-rc.1").pre.as_deref(), Some("rc.1"));
+/// ```
+///
+/// ```compile_fail
+/// let typo = semver_macros::version!("v1.2");
+/// ```
+#[proc_macro]
+pub fn version(input: TokenStream) -> TokenStream {
+    let literal = match syn::parse::<LitStr>(input) {
+        Ok(literal) => literal,
+        Err(error) => return compile_error(error.span(), &error.to_string()),
+    };
+    let value = literal.value();
+
+    let Some((major, minor, patch, pre, build)) = parse(&value) else {
+        return compile_error(
+            literal.span(),
+            &format!("invalid version format: {}", value),
+        );
+    };
+    // `::core` is the semver library: the standard one is reached through `::std`
+    let optional = |value: Option<&str>| match value {
+        Some(value) => quote!(::std::option::Option::Some(::std::string::String::from(#value))),
+        None => quote!(::std::option::Option::None),
+    };
+    let (pre, build) = (optional(pre), optional(build));
+
+    quote! {
+        ::core::SemanticVersion {
+            major: #major,
+            minor: #minor,
+            patch: #patch,
+            pre: #pre,
+            build: #build,
+        }
+    }
+    .into()
+}
+
+/// Fails the build with `message` at `span`, like `syn::Error::to_compile_error` but through
+/// `::std`, `::core` naming the semver library in its users.
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    quote_spanned!(span=> ::std::compile_error!(#message)).into()
+}
+
+/// Components of a version: major, minor, patch, prerelease and build metadata.
+type Components<'a> = (u64, u64, u64, Option<&'a str>, Option<&'a str>);
+
+/// Reads `version` with the grammar of `core::SemanticVersion::try_from`, which a proc-macro
+/// cannot call without building `core` a second time for the host.
+fn parse(version: &str) -> Option<Components<'_>> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (version, pre) = match version.split_once('-') {
+        Some((version, pre)) => (version, Some(pre)),
+        None => (version, None),
+    };
+    let identifiers = |value: &str| {
+        value.split('.').all(|identifier| {
+            !identifier.is_empty()
+                && identifier
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+    };
+    if !pre.into_iter().chain(build).all(identifiers) {
+        return None;
+    }
+
+    let mut numbers = version.split('.').map(|number| {
+        match !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            true => number.parse::<u64>().ok(),
+            false => None,
+        }
+    });
+    let (major, minor, patch) = (numbers.next()??, numbers.next()??, numbers.next()??);
+    if numbers.next().is_some() {
+        return None;
+    }
+
+    Some((major, minor, patch, pre, build))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_follows_the_version_grammar_of_the_library() {
+        assert_eq!(parse("v1.2.3"), Some((1, 2, 3, None, None)));
+        assert_eq!(
+            parse("2.0.0-rc.1+sha.abc-1"),
+            Some((2, 0, 0, Some("rc.1"), Some("sha.abc-1")))
+        );
+
+        for typo in [
+            "v1.2",
+            "1.2.3.4",
+            "1.x.3",
+            "1.2.3-",
+            "1.2.3-rc..1",
+            "1.2.3+",
+            "99999999999999999999.0.0",
+        ] {
+            assert_eq!(parse(typo), None, "{}", typo);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Node addon, so that JavaScript release tooling, e.g. semantic-release plugins or Probot apps,
+//! parses commits and computes versions in-process, exactly like the CLI.
+//!
+//! ```js
+//! const { parseComment, nextVersion, lint } = require("@guiln/semver-node");
+//!
+//! parseComment("feat(api): export.").scope;                   // "api"
+//! nextVersion("v1.2.3", ["fix: a fix.", "feat: export."]);    // "v1.3.0"
+//! lint("refact: simplify.")[0].severity;                       // "warning"
+//! ```
+//!
+//! Every function takes the contents of a `semver.toml` as an optional `config`, and throws when
+//! the input or the configuration does not parse.
+
+use napi::{Error, Result};
+use napi_derive::napi;
+use semver_core::{AggregateBump, Config, LintWarning, SemVerError, SemanticVersion};
+
+/// A parsed semantic comment.
+#[napi(object)]
+pub struct Comment {
+    /// Keyword of the type, e.g. `feat`.
+    pub r#type: String,
+    pub breaking: bool,
+    pub scope: Option<String>,
+    /// Description following the type.
+    pub description: String,
+    pub tickets: Vec<String>,
+    /// The comment as the json printed by `semver --output-json`.
+    pub json: String,
+}
+
+/// A problem reported by [`lint`], failing the message when its severity is `error`.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// `error` or `warning`.
+    pub severity: String,
+    /// Rule of the `[lint]` section, e.g. `scope-enum`, when one is broken.
+    pub rule: Option<String>,
+    pub message: String,
+}
+
+/// Parses `comment` and returns a `Comment`.
+#[napi]
+pub fn parse_comment(comment: String, config: Option<String>) -> Result<Comment> {
+    let config = load_config(config.as_deref())?;
+    let comment = config.comment_parser().parse(&comment).map_err(throw)?;
+
+    Ok(Comment {
+        r#type: comment
+            .semantic_type
+            .keyword(config.refactor_keyword)
+            .to_string(),
+        breaking: comment.semantic_type.metadata().is_breaking,
+        scope: comment.scope.clone(),
+        description: comment.comment.clone(),
+        tickets: comment.tickets.clone(),
+        json: comment.as_json_string().map_err(throw)?,
+    })
+}
+
+/// Returns the version following `currentVersion` for the commit `messages`, evaluating the
+/// bump policy and version scheme of the configuration. Messages which do not parse are left
+/// out, like `semver release` does.
+#[napi]
+pub fn next_version(
+    current_version: String,
+    messages: Vec<String>,
+    config: Option<String>,
+) -> Result<String> {
+    next(
+        &current_version,
+        &messages,
+        &load_config(config.as_deref())?,
+    )
+    .map_err(throw)
+}
+
+/// Lints the commit `message` and returns its issues, empty when it is clean.
+#[napi]
+pub fn lint(message: String, config: Option<String>) -> Result<Vec<LintIssue>> {
+    Ok(lint_issues(&message, &load_config(config.as_deref())?))
+}
+
+fn throw(error: SemVerError) -> Error {
+    Error::from_reason(error.to_string())
+}
+
+fn load_config(config: Option<&str>) -> Result<Config> {
+    config
+        .map_or_else(|| Ok(Config::default()), Config::from_toml_str)
+        .map_err(throw)
+}
+
+fn next(
+    current_version: &str,
+    messages: &[String],
+    config: &Config,
+) -> std::result::Result<String, SemVerError> {
+    let parser = config.comment_parser();
+    let policy = config.bump_policy()?;
+    let mut bump = AggregateBump::default();
+    for comment in messages.iter().filter_map(|m| parser.parse_message(m).ok()) {
+        bump.add_with_policy(&comment, &policy, None);
+    }
+
+    Ok(config
+        .next_version(&SemanticVersion::try_from(current_version)?, bump.bump)?
+        .into())
+}
+
+fn lint_issues(message: &str, config: &Config) -> Vec<LintIssue> {
+    let mut issues = vec![];
+    if let Err(error) = config.lint(message) {
+        issues.push(LintIssue {
+            severity: "error".to_string(),
+            rule: None,
+            message: error.to_string(),
+        });
+    }
+    issues.extend(
+        config
+            .lint_warnings(message)
+            .into_iter()
+            .map(|warning| LintIssue {
+                severity: "warning".to_string(),
+                rule: match &warning {
+                    LintWarning::Rule(violation) => Some(violation.rule.to_string()),
+                    _ => None,
+                },
+                message: warning.to_string(),
+            }),
+    );
+
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_next_takes_the_highest_bump_of_the_parsed_messages() {
+        let messages = ["fix: a fix.", "wip", "feat: export.\n\nDetails."].map(String::from);
+
+        assert_eq!(
+            next("v1.2.3", &messages, &Config::default()).unwrap(),
+            "v1.3.0"
+        );
+        assert_eq!(next("v1.2.3", &[], &Config::default()).unwrap(), "v1.2.3");
+        assert!(next("latest", &messages, &Config::default()).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Python module `semver_py`, so that release scripts and bots parse commits, compute versions
+//! and lint messages in-process, exactly like the CLI.
+//!
+//! ```python
+//! import semver_py
+//!
+//! semver_py.parse_comment("feat(api): export.").scope         # "api"
+//! semver_py.next_version("v1.2.3", "feat: export.")          # "v1.3.0"
+//! [str(issue) for issue in semver_py.lint("wip")]            # ["error: The format provided is invalid! ..."]
+//! ```
+//!
+//! Every function takes the contents of a `semver.toml` as an optional `config`, and raises
+//! `semver_py.SemVerError` when the input or the configuration does not parse.
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+use semver_core::{Config, LintWarning, SemVerError, SemanticComment, SemanticVersion};
+
+create_exception!(
+    semver_py,
+    PySemVerError,
+    PyValueError,
+    "Raised when an input does not parse."
+);
+
+/// A parsed semantic comment.
+#[pyclass(name = "Comment", frozen, get_all)]
+#[derive(Debug, Clone)]
+struct PyComment {
+    /// Keyword of the type, e.g. `feat`.
+    r#type: String,
+    breaking: bool,
+    scope: Option<String>,
+    /// Description following the type.
+    description: String,
+    tickets: Vec<String>,
+    trailers: Vec<(String, String)>,
+    /// The comment as the json printed by `semver --output-json`.
+    json: String,
+}
+
+#[pymethods]
+impl PyComment {
+    fn __repr__(&self) -> String {
+        format!("Comment({})", self.json)
+    }
+}
+
+/// A problem reported by [`lint`], failing the message when its severity is `error`.
+#[pyclass(name = "LintIssue", frozen, get_all)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PyLintIssue {
+    /// `error` or `warning`.
+    severity: &'static str,
+    /// Rule of the `[lint]` section, e.g. `scope-enum`, when one is broken.
+    rule: Option<&'static str>,
+    message: String,
+}
+
+#[pymethods]
+impl PyLintIssue {
+    fn __str__(&self) -> String {
+        format!("{}: {}", self.severity, self.message)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LintIssue({:?}, {:?})", self.severity, self.message)
+    }
+}
+
+/// Parses `comment` and returns a `Comment`.
+#[pyfunction]
+#[pyo3(signature = (comment, config = None))]
+fn parse_comment(comment: &str, config: Option<&str>) -> PyResult<PyComment> {
+    let config = load_config(config)?;
+    let comment = config.comment_parser().parse(comment).map_err(raise)?;
+
+    py_comment(&config, &comment).map_err(raise)
+}
+
+/// Returns the version following `current_version` for `comment`, evaluating the bump policy
+/// and version scheme of the configuration.
+#[pyfunction]
+#[pyo3(signature = (current_version, comment, config = None))]
+fn next_version(current_version: &str, comment: &str, config: Option<&str>) -> PyResult<String> {
+    next(current_version, comment, &load_config(config)?).map_err(raise)
+}
+
+/// Lints the commit `message` and returns its issues, empty when it is clean.
+#[pyfunction]
+#[pyo3(signature = (message, config = None))]
+fn lint(message: &str, config: Option<&str>) -> PyResult<Vec<PyLintIssue>> {
+    Ok(lint_issues(message, &load_config(config)?))
+}
+
+#[pymodule]
+fn semver_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("SemVerError", module.py().get_type::<PySemVerError>())?;
+    module.add_class::<PyComment>()?;
+    module.add_class::<PyLintIssue>()?;
+    module.add_function(wrap_pyfunction!(parse_comment, module)?)?;
+    module.add_function(wrap_pyfunction!(next_version, module)?)?;
+    module.add_function(wrap_pyfunction!(lint, module)?)?;
+
+    Ok(())
+}
+
+fn raise(error: SemVerError) -> PyErr {
+    PySemVerError::new_err(error.to_string())
+}
+
+fn load_config(config: Option<&str>) -> PyResult<Config> {
+    config
+        .map_or_else(|| Ok(Config::default()), Config::from_toml_str)
+        .map_err(raise)
+}
+
+fn py_comment(config: &Config, comment: &SemanticComment) -> Result<PyComment, SemVerError> {
+    Ok(PyComment {
+        r#type: comment
+            .semantic_type
+            .keyword(config.refactor_keyword)
+            .to_string(),
+        breaking: comment.semantic_type.metadata().is_breaking,
+        scope: comment.scope.clone(),
+        description: comment.comment.clone(),
+        tickets: comment.tickets.clone(),
+        trailers: comment.trailers.clone(),
+        json: comment.as_json_string()?,
+    })
+}
+
+fn next(current_version: &str, comment: &str, config: &Config) -> Result<String, SemVerError> {
+    let comment = config.comment_parser().parse(comment)?;
+    let bump = config.bump_policy()?.evaluate(&comment, None);
+
+    Ok(config
+        .next_version(&SemanticVersion::try_from(current_version)?, bump)?
+        .into())
+}
+
+fn lint_issues(message: &str, config: &Config) -> Vec<PyLintIssue> {
+    let mut issues = vec![];
+    if let Err(error) = config.lint(message) {
+        issues.push(PyLintIssue {
+            severity: "error",
+            rule: None,
+            message: error.to_string(),
+        });
+    }
+    issues.extend(
+        config
+            .lint_warnings(message)
+            .into_iter()
+            .map(|warning| PyLintIssue {
+                severity: "warning",
+                rule: match &warning {
+                    LintWarning::Rule(violation) => Some(violation.rule),
+                    _ => None,
+                },
+                message: warning.to_string(),
+            }),
+    );
+
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_next_follows_the_configuration() {
+        assert_eq!(
+            next("v1.2.3", "feat: export.", &Config::default()).unwrap(),
+            "v1.3.0"
+        );
+
+        let config = Config::from_toml_str("scheme = \"calver\"\n").unwrap();
+        assert!(next("v1.2.3", "fix: a fix.", &config)
+            .unwrap()
+            .starts_with("v20"));
+        assert!(next("latest", "fix: a fix.", &Config::default()).is_err());
+    }
+
+    #[test]
+    fn test_lint_issues_reports_errors_and_warnings() {
+        let config = Config::from_toml_str("[lint]\nscopes = [\"api\"]\n").unwrap();
+
+        assert!(lint_issues("feat(api): export.", &config).is_empty());
+        assert_eq!(
+            lint_issues("refact: simplify.", &Config::default())[0].severity,
+            "warning"
+        );
+        let issues = lint_issues("feat(web): export.", &config);
+        assert_eq!(issues[0].severity, "error");
+    }
+}
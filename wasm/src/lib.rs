@@ -0,0 +1,4 @@
+//! WebAssembly module of the `@guiln/semver-wasm` npm package, exporting the JavaScript bindings
+//! of the `wasm` feature of the core crate, built with `make wasm`.
+
+pub use semver_core::wasm::*;